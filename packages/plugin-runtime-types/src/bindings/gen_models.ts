@@ -1,25 +1,128 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
+/**
+ * Extra certificate authorities trusted on top of the platform ones
+ */
+export type CaCertificate = { enabled?: boolean, 
+/**
+ * Host to trust the certificates for, with the same syntax as `ClientCertificate::host`
+ */
+host: string, 
+/**
+ * PEM (or DER) file with one or more CA certificates
+ */
+file: string, };
+
+/**
+ * A certificate (and key) presented to servers that ask for mutual TLS
+ */
+export type ClientCertificate = { enabled?: boolean, 
+/**
+ * Host to use the certificate for. Supports `*` wildcards and an optional port, like
+ * `*.internal.example.com` or `localhost:8443`
+ */
+host: string, 
+/**
+ * PEM file with the certificate chain, used together with `key_file`
+ */
+crtFile: string | null, 
+/**
+ * PEM file with the private key for `crt_file`
+ */
+keyFile: string | null, 
+/**
+ * PKCS#12 bundle with both the certificate chain and key, instead of `crt_file`/`key_file`
+ */
+pfxFile: string | null, passphrase: string | null, };
+
+/**
+ * Resolves a host to a fixed address, like curl's `--resolve`. The URL is left alone, so the
+ * request keeps its Host header and TLS server name.
+ */
+export type DnsOverride = { enabled?: boolean, 
+/**
+ * Host to override. Supports `*` wildcards, like `*.staging.example.com`
+ */
+hostname: string, 
+/**
+ * IPv4 or IPv6 address to connect to instead
+ */
+ip: string, };
+
+/**
+ * Where hosts without an override are looked up
+ */
+export type DnsResolverSetting = { "type": "system" } | { "type": "nameserver", address: string, } | { "type": "https", url: string, };
+
 export type Environment = { model: "environment", id: string, workspaceId: string, createdAt: string, updatedAt: string, name: string, public: boolean, parentModel: string, parentId: string | null, variables: Array<EnvironmentVariable>, color: string | null, sortPriority: number, };
 
 export type EnvironmentVariable = { enabled?: boolean, name: string, value: string, id?: string, };
 
 export type Folder = { model: "folder", id: string, createdAt: string, updatedAt: string, workspaceId: string, folderId: string | null, authentication: Record<string, any>, authenticationType: string | null, description: string, headers: Array<HttpRequestHeader>, name: string, sortPriority: number, };
 
-export type GrpcRequest = { model: "grpc_request", id: string, createdAt: string, updatedAt: string, workspaceId: string, folderId: string | null, authenticationType: string | null, authentication: Record<string, any>, description: string, message: string, metadata: Array<HttpRequestHeader>, method: string | null, name: string, service: string | null, sortPriority: number, url: string, };
+export type GrpcRequest = { model: "grpc_request", id: string, createdAt: string, updatedAt: string, workspaceId: string, folderId: string | null, authenticationType: string | null, authentication: Record<string, any>, description: string, message: string, metadata: Array<HttpRequestHeader>, method: string | null, name: string, service: string | null, sortPriority: number, url: string, 
+/**
+ * Set on the hidden request an inline workflow gRPC node sends through
+ */
+workflowNodeId: string | null, };
 
-export type HttpRequest = { model: "http_request", id: string, createdAt: string, updatedAt: string, workspaceId: string, folderId: string | null, authentication: Record<string, any>, authenticationType: string | null, body: Record<string, any>, bodyType: string | null, description: string, headers: Array<HttpRequestHeader>, method: string, name: string, sortPriority: number, url: string, urlParameters: Array<HttpUrlParameter>, };
+export type HttpRequest = { model: "http_request", id: string, createdAt: string, updatedAt: string, workspaceId: string, folderId: string | null, authentication: Record<string, any>, authenticationType: string | null, body: Record<string, any>, bodyType: string | null, description: string, headers: Array<HttpRequestHeader>, method: string, name: string, sortPriority: number, url: string, urlParameters: Array<HttpUrlParameter>, 
+/**
+ * Set on the hidden request an inline workflow HTTP node sends through
+ */
+workflowNodeId: string | null, };
 
 export type HttpRequestHeader = { enabled?: boolean, name: string, value: string, id?: string, };
 
-export type HttpResponse = { model: "http_response", id: string, createdAt: string, updatedAt: string, workspaceId: string, requestId: string, bodyPath: string | null, contentLength: number | null, elapsed: number, elapsedHeaders: number, error: string | null, headers: Array<HttpResponseHeader>, remoteAddr: string | null, status: number, statusReason: string | null, state: HttpResponseState, url: string, version: string | null, };
+export type HttpResponse = { model: "http_response", id: string, createdAt: string, updatedAt: string, workspaceId: string, requestId: string, bodyPath: string | null, contentLength: number | null, elapsed: number, elapsedHeaders: number, error: string | null, headers: Array<HttpResponseHeader>, redirects: Array<HttpResponseRedirect>, remoteAddr: string | null, request: HttpResponseRequest | null, status: number, statusReason: string | null, state: HttpResponseState, timings: HttpResponseTimings, tls: HttpResponseTls | null, url: string, version: string | null, };
 
 export type HttpResponseHeader = { name: string, value: string, };
 
+/**
+ * A redirect that was followed on the way to the final response
+ */
+export type HttpResponseRedirect = { status: number, method: string, url: string, location: string, headers: Array<HttpResponseHeader>, elapsed: number, };
+
+/**
+ * The request as it was handed to the HTTP client, after rendering and authentication
+ */
+export type HttpResponseRequest = { method: string, url: string, headers: Array<HttpResponseHeader>, 
+/**
+ * The body, cut off after the first few megabytes
+ */
+bodyPath: string | null, 
+/**
+ * Size of the whole body, even when the saved one was cut off
+ */
+contentLength: number | null, };
+
 export type HttpResponseState = "initialized" | "connected" | "closed";
 
+/**
+ * How long each phase of a request took, in milliseconds. The connection phases are zero when
+ * an existing connection was reused.
+ */
+export type HttpResponseTimings = { dns: number, connect: number, tls: number, 
+/**
+ * From the request being sent (or the connection being ready) to the response headers
+ */
+ttfb: number, download: number, };
+
+/**
+ * The TLS connection a response was received over
+ */
+export type HttpResponseTls = { version: string | null, cipher: string | null, 
+/**
+ * PEM encoded certificates sent by the server, starting with its own
+ */
+peerCertificates: Array<string>, };
+
 export type HttpUrlParameter = { enabled?: boolean, name: string, value: string, id?: string, };
 
-export type WebsocketRequest = { model: "websocket_request", id: string, createdAt: string, updatedAt: string, workspaceId: string, folderId: string | null, authentication: Record<string, any>, authenticationType: string | null, description: string, headers: Array<HttpRequestHeader>, message: string, name: string, sortPriority: number, url: string, urlParameters: Array<HttpUrlParameter>, };
+export type WebsocketRequest = { model: "websocket_request", id: string, createdAt: string, updatedAt: string, workspaceId: string, folderId: string | null, authentication: Record<string, any>, authenticationType: string | null, description: string, headers: Array<HttpRequestHeader>, message: string, name: string, sortPriority: number, url: string, urlParameters: Array<HttpUrlParameter>, 
+/**
+ * Set on the hidden request an inline workflow WebSocket node connects through
+ */
+workflowNodeId: string | null, };
 
-export type Workspace = { model: "workspace", id: string, createdAt: string, updatedAt: string, authentication: Record<string, any>, authenticationType: string | null, description: string, headers: Array<HttpRequestHeader>, name: string, encryptionKeyChallenge: string | null, settingValidateCertificates: boolean, settingFollowRedirects: boolean, settingRequestTimeout: number, };
+export type Workspace = { model: "workspace", id: string, createdAt: string, updatedAt: string, authentication: Record<string, any>, authenticationType: string | null, description: string, headers: Array<HttpRequestHeader>, name: string, encryptionKeyChallenge: string | null, settingValidateCertificates: boolean, settingFollowRedirects: boolean, settingMaxRedirects: number, settingRequestTimeout: number, settingClientCertificates: Array<ClientCertificate>, settingCaCertificates: Array<CaCertificate>, settingDnsOverrides: Array<DnsOverride>, settingDnsResolver: DnsResolverSetting, };
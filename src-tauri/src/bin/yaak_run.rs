@@ -0,0 +1,3 @@
+fn main() {
+    tauri_app_lib::cli::run_cli();
+}
@@ -0,0 +1,305 @@
+//! Headless workflow runner, built as the `yaak-run` binary with the `cli` feature.
+//!
+//! The app runs on Tauri's mock runtime so that workflows execute through the exact same
+//! orchestrator as the desktop app, just without any windows.
+//!
+//! Shipping the mock runtime is safe because the runner never touches the parts of Tauri it
+//! fakes. It only stubs out the event loop, windows, webviews and IPC, none of which a workflow
+//! uses. Managed state, plugin setup, events and path resolution are implemented by Tauri itself
+//! on top of any runtime, and the orchestrator and plugins are generic over `Runtime`, so they
+//! run the same code here as in the app. The real runtime would need a display server, which CI
+//! machines don't have. The `tauri/test` feature only adds the `tauri::test` module, and only
+//! the `cli` feature enables it, so the desktop build is unaffected.
+
+use crate::workflow_execution::orchestrator::WorkflowOrchestrator;
+use crate::workflow_execution::report::{ExecutionReport, ReportFormat};
+use std::path::PathBuf;
+use std::process::exit;
+use tauri::Manager;
+use tauri::test::{MockRuntime, mock_builder, mock_context, noop_assets};
+use tokio::sync::Mutex;
+use yaak_grpc::manager::GrpcHandle;
+use yaak_models::models::{NodeExecutionState, Workflow, WorkflowExecutionState};
+use yaak_models::query_manager::QueryManagerExt;
+use yaak_plugins::manager::PluginManager;
+
+const USAGE: &str = "\
+Run a Yaak workflow without the app
+
+Usage: yaak-run [OPTIONS] --workflow <ID|NAME>
+
+Options:
+  --db <PATH>                 Yaak database to load workflows from
+  --sync-dir <DIR>            Directory synced with Yaak's filesystem sync, instead of --db
+  --workflow <ID|NAME>        Workflow to run
+  --workspace <ID|NAME>       Only look for the workflow in this workspace
+  --environment <ID|NAME>     Environment to run with (defaults to the workflow's own)
+  --junit-report <PATH>       Write a JUnit XML report of the run
+  --json-report <PATH>        Write a JSON report of the run
+  -h, --help                  Print this help
+
+Exit codes: 0 when the run completes, 1 when it fails or is cancelled, 2 for usage errors";
+
+#[derive(Debug, Default)]
+struct Args {
+    db: Option<PathBuf>,
+    sync_dir: Option<PathBuf>,
+    workflow: String,
+    workspace: Option<String>,
+    environment: Option<String>,
+    junit_report: Option<PathBuf>,
+    json_report: Option<PathBuf>,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut parsed = Args::default();
+    let mut workflow = None;
+
+    while let Some(arg) = args.next() {
+        // Support both `--flag value` and `--flag=value`
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((f, v)) if f.starts_with("--") => (f.to_string(), Some(v.to_string())),
+            _ => (arg.clone(), None),
+        };
+
+        if flag == "-h" || flag == "--help" {
+            println!("{USAGE}");
+            exit(0);
+        }
+
+        let value = match inline_value.or_else(|| args.next()) {
+            Some(v) => v,
+            None => return Err(format!("Missing value for {flag}")),
+        };
+
+        match flag.as_str() {
+            "--db" => parsed.db = Some(PathBuf::from(value)),
+            "--sync-dir" => parsed.sync_dir = Some(PathBuf::from(value)),
+            "--workflow" => workflow = Some(value),
+            "--workspace" => parsed.workspace = Some(value),
+            "--environment" => parsed.environment = Some(value),
+            "--junit-report" => parsed.junit_report = Some(PathBuf::from(value)),
+            "--json-report" => parsed.json_report = Some(PathBuf::from(value)),
+            _ => return Err(format!("Unknown argument {flag}")),
+        }
+    }
+
+    parsed.workflow = workflow.ok_or("Missing --workflow")?;
+    match (&parsed.db, &parsed.sync_dir) {
+        (None, None) => Err("One of --db or --sync-dir is required".to_string()),
+        (Some(_), Some(_)) => Err("--db and --sync-dir can't be used together".to_string()),
+        _ => Ok(parsed),
+    }
+}
+
+pub fn run_cli() {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{e}\n\n{USAGE}");
+            exit(2);
+        }
+    };
+
+    // A sync dir is imported into a throwaway database so the directory itself is never touched
+    let temp_db = args.sync_dir.as_ref().map(|_| {
+        std::env::temp_dir().join(format!("yaak-run-{}.sqlite", std::process::id()))
+    });
+    let db_path = args.db.clone().or(temp_db.clone()).unwrap();
+
+    // Same identifier as the app, so app data like installed plugins is found in the same place
+    let mut context = mock_context(noop_assets());
+    context.config_mut().identifier = "app.yaak.desktop".to_string();
+
+    let app = match mock_builder()
+        .plugin(tauri_plugin_shell::init())
+        .plugin(yaak_models::init_with_db_path(Some(db_path)))
+        .plugin(yaak_plugins::init())
+        .plugin(yaak_ws::init())
+        .setup(|app| {
+            let grpc_handle = GrpcHandle::new(&app.app_handle());
+            app.manage(Mutex::new(grpc_handle));
+            Ok(())
+        })
+        .build(context)
+    {
+        Ok(app) => app,
+        Err(e) => {
+            eprintln!("Failed to initialize: {e}");
+            exit(1);
+        }
+    };
+
+    let app_handle = app.handle().clone();
+    let code = tauri::async_runtime::block_on(async move {
+        let code = match run(&app_handle, &args).await {
+            Ok(true) => 0,
+            Ok(false) => 1,
+            Err(e) => {
+                eprintln!("{e}");
+                1
+            }
+        };
+        app_handle.state::<PluginManager>().terminate().await;
+        code
+    });
+
+    // The app has to be gone first, so SQLite is done with the database and its WAL files
+    drop(app);
+    if let Some(temp_db) = temp_db {
+        for suffix in ["", "-wal", "-shm"] {
+            let mut path = temp_db.clone().into_os_string();
+            path.push(suffix);
+            let _ = std::fs::remove_file(path);
+        }
+    }
+
+    exit(code);
+}
+
+/// Run the selected workflow, returning whether it completed successfully
+async fn run(app_handle: &tauri::AppHandle<MockRuntime>, args: &Args) -> Result<bool, String> {
+    if let Some(sync_dir) = &args.sync_dir {
+        if !sync_dir.is_dir() {
+            return Err(format!("Sync directory {} does not exist", sync_dir.display()));
+        }
+        yaak_sync::import_dir(app_handle, sync_dir).map_err(|e| e.to_string())?;
+    }
+
+    let workflow = find_workflow(app_handle, &args.workflow, args.workspace.as_deref())?;
+    let environment_id = match &args.environment {
+        None => None,
+        Some(env) => Some(find_environment_id(app_handle, &workflow.workspace_id, env)?),
+    };
+
+    println!("Running workflow {} ({})", workflow.name, workflow.id);
+    let orchestrator = WorkflowOrchestrator::new(app_handle.clone());
+    let execution = orchestrator
+        .execute_and_wait(workflow.id.clone(), environment_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let node_executions = app_handle
+        .db()
+        .get_workflow_node_executions(&execution.id)
+        .map_err(|e| e.to_string())?;
+    for node_execution in &node_executions {
+        let name = match app_handle.db().get_workflow_node(&node_execution.workflow_node_id) {
+            Ok(node) => node.name,
+            Err(_) => node_execution.workflow_node_id.clone(),
+        };
+        let marker = match node_execution.state {
+            NodeExecutionState::Completed => "✓",
+            NodeExecutionState::Failed => "✗",
+            _ => "-",
+        };
+        let elapsed = node_execution.elapsed.map(|e| format!(" ({e}ms)")).unwrap_or_default();
+        println!("  {marker} {name}{elapsed}");
+        if let Some(error) = &node_execution.error {
+            println!("      {error}");
+        }
+    }
+
+    let reports = [(&args.junit_report, ReportFormat::Junit), (&args.json_report, ReportFormat::Json)];
+    for (path, format) in reports {
+        let Some(path) = path else { continue };
+        let report = ExecutionReport::load(&app_handle.db(), &execution.id)
+            .and_then(|r| r.export(format))
+            .map_err(|e| e.to_string())?;
+        std::fs::write(path, report)
+            .map_err(|e| format!("Failed to write report to {}: {e}", path.display()))?;
+    }
+
+    if execution.assertions_passed + execution.assertions_failed > 0 {
+        println!(
+            "Assertions: {} passed, {} failed",
+            execution.assertions_passed, execution.assertions_failed
+        );
+    }
+
+    let elapsed = execution.elapsed.map(|e| format!(" in {e}ms")).unwrap_or_default();
+    match execution.state {
+        WorkflowExecutionState::Completed => {
+            println!("Workflow completed{elapsed}");
+            Ok(true)
+        }
+        state => {
+            let error = execution.error.map(|e| format!(": {e}")).unwrap_or_default();
+            println!("Workflow {}{elapsed}{error}", format!("{state:?}").to_lowercase());
+            Ok(false)
+        }
+    }
+}
+
+fn find_workflow(
+    app_handle: &tauri::AppHandle<MockRuntime>,
+    id_or_name: &str,
+    workspace: Option<&str>,
+) -> Result<Workflow, String> {
+    let db = app_handle.db();
+    let workspaces = db.list_workspaces().map_err(|e| e.to_string())?;
+    let workspaces = workspaces
+        .into_iter()
+        .filter(|w| workspace.is_none_or(|ws| w.id == ws || w.name == ws))
+        .collect::<Vec<_>>();
+    if workspaces.is_empty() {
+        return Err(match workspace {
+            Some(ws) => format!("Workspace {ws} not found"),
+            None => "No workspaces found".to_string(),
+        });
+    }
+
+    let mut matches = Vec::new();
+    for w in workspaces {
+        let workflows = db.list_workflows_by_workspace(&w.id).map_err(|e| e.to_string())?;
+        matches.extend(workflows.into_iter().filter(|wf| wf.id == id_or_name || wf.name == id_or_name));
+    }
+
+    match matches.len() {
+        0 => Err(format!("Workflow {id_or_name} not found")),
+        1 => Ok(matches.remove(0)),
+        n => Err(format!("{n} workflows are named {id_or_name}, pass an ID or --workspace")),
+    }
+}
+
+fn find_environment_id(
+    app_handle: &tauri::AppHandle<MockRuntime>,
+    workspace_id: &str,
+    id_or_name: &str,
+) -> Result<String, String> {
+    let environments =
+        app_handle.db().list_environments_ensure_base(workspace_id).map_err(|e| e.to_string())?;
+    environments
+        .into_iter()
+        .find(|e| e.id == id_or_name || e.name == id_or_name)
+        .map(|e| e.id)
+        .ok_or(format!("Environment {id_or_name} not found"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Result<Args, String> {
+        parse_args(s.split_whitespace().map(|s| s.to_string()))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let parsed = args("--db yaak.sqlite --workflow=Smoke --environment Staging").unwrap();
+        assert_eq!(parsed.db, Some(PathBuf::from("yaak.sqlite")));
+        assert_eq!(parsed.workflow, "Smoke");
+        assert_eq!(parsed.environment.as_deref(), Some("Staging"));
+        assert_eq!(parsed.junit_report, None);
+
+        let parsed = args("--sync-dir ./yaak --workflow Smoke --junit-report out/junit.xml").unwrap();
+        assert_eq!(parsed.sync_dir, Some(PathBuf::from("./yaak")));
+        assert_eq!(parsed.junit_report, Some(PathBuf::from("out/junit.xml")));
+
+        assert!(args("--workflow Smoke").is_err());
+        assert!(args("--db a --sync-dir b --workflow Smoke").is_err());
+        assert!(args("--db a").is_err());
+        assert!(args("--db a --workflow").is_err());
+        assert!(args("--db a --workflow Smoke --verbose 1").is_err());
+    }
+}
@@ -54,24 +54,15 @@ pub(crate) async fn cmd_get_themes<R: Runtime>(
 // Workflow Commands
 // ============================================================================
 
-use crate::workflow_execution::{WorkflowExecutor, ExecuteWorkflowRequest, ExecuteWorkflowResponse};
 use serde::{Deserialize, Serialize};
-use yaak_models::models::{Workflow, WorkflowStep, WorkflowExecution, WorkflowStepExecution};
+use yaak_models::models::{Workflow, WorkflowExecution, WorkflowNodeExecution};
 use yaak_models::query_manager::QueryManagerExt;
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct GetWorkflowWithStepsResponse {
-    pub workflow: Workflow,
-    pub steps: Vec<WorkflowStep>,
-    pub broken_step_ids: Vec<String>,
-}
-
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GetWorkflowExecutionResultsResponse {
     pub execution: WorkflowExecution,
-    pub step_executions: Vec<WorkflowStepExecution>,
+    pub node_executions: Vec<WorkflowNodeExecution>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -80,46 +71,6 @@ pub struct ListWorkflowExecutionsResponse {
     pub executions: Vec<WorkflowExecution>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct CancelWorkflowExecutionRequest {
-    pub execution_id: String,
-}
-
-#[command]
-pub(crate) async fn cmd_execute_workflow<R: Runtime>(
-    app_handle: AppHandle<R>,
-    req: ExecuteWorkflowRequest,
-) -> Result<ExecuteWorkflowResponse> {
-    let executor = WorkflowExecutor::new(app_handle);
-    let execution_id = executor.execute(req.workflow_id, req.environment_id).await?;
-    Ok(ExecuteWorkflowResponse { execution_id })
-}
-
-#[command]
-pub(crate) async fn cmd_cancel_workflow_execution<R: Runtime>(
-    app_handle: AppHandle<R>,
-    req: CancelWorkflowExecutionRequest,
-) -> Result<()> {
-    let executor = WorkflowExecutor::new(app_handle);
-    executor.cancel(req.execution_id);
-    Ok(())
-}
-
-#[command]
-pub(crate) async fn cmd_get_workflow_with_steps<R: Runtime>(
-    app_handle: AppHandle<R>,
-    workflow_id: String,
-) -> Result<GetWorkflowWithStepsResponse> {
-    let db = app_handle.db();
-    let (workflow, steps, broken_step_ids) = db.get_workflow_with_steps(&workflow_id)?;
-    Ok(GetWorkflowWithStepsResponse {
-        workflow,
-        steps,
-        broken_step_ids,
-    })
-}
-
 #[command]
 pub(crate) async fn cmd_get_workflow_execution_results<R: Runtime>(
     app_handle: AppHandle<R>,
@@ -127,10 +78,10 @@ pub(crate) async fn cmd_get_workflow_execution_results<R: Runtime>(
 ) -> Result<GetWorkflowExecutionResultsResponse> {
     let db = app_handle.db();
     let execution = db.get_workflow_execution(&execution_id)?;
-    let step_executions = db.get_workflow_step_executions(&execution_id)?;
+    let node_executions = db.get_workflow_node_executions(&execution_id)?;
     Ok(GetWorkflowExecutionResultsResponse {
         execution,
-        step_executions,
+        node_executions,
     })
 }
 
@@ -163,7 +114,8 @@ pub(crate) async fn cmd_list_workflow_executions<R: Runtime>(
 // Workflow Canvas Commands
 // ============================================================================
 
-use crate::workflow_execution::{WorkflowOrchestrator, GraphBuilder};
+use crate::workflow_execution::load_test::LoadTestConfig;
+use crate::workflow_execution::{ExecutionReport, GraphBuilder, ReportFormat, WorkflowOrchestrator};
 use yaak_models::models::{WorkflowNode, WorkflowEdge, WorkflowViewport, NodeType, EdgeType};
 use yaak_models::util::{UpdateSource, generate_prefixed_id};
 use chrono::Utc;
@@ -190,11 +142,13 @@ pub(crate) async fn cmd_create_workflow_node<R: Runtime>(
 
     // Parse node type
     let node_type = NodeType::from_str(&req.node_type)?;
+    let workflow = db.get_workflow(&req.workflow_id)?;
 
     // Create node with default config
     let node = WorkflowNode {
         id: generate_prefixed_id("wn"),
         model: "workflow_node".to_string(),
+        workspace_id: workflow.workspace_id,
         workflow_id: req.workflow_id,
         node_type,
         node_subtype: req.node_subtype,
@@ -315,11 +269,13 @@ pub(crate) async fn cmd_create_workflow_edge<R: Runtime>(
 
     // Parse edge type
     let edge_type = EdgeType::from_str(&req.edge_type)?;
+    let workflow = db.get_workflow(&req.workflow_id)?;
 
     // Create edge
     let edge = WorkflowEdge {
         id: generate_prefixed_id("we"),
         model: "workflow_edge".to_string(),
+        workspace_id: workflow.workspace_id,
         workflow_id: req.workflow_id,
         source_node_id: req.source_node_id,
         source_anchor: req.source_anchor,
@@ -492,6 +448,49 @@ pub(crate) async fn cmd_cancel_workflow_execution_canvas<R: Runtime>(
     Ok(())
 }
 
+#[command]
+pub(crate) async fn cmd_resume_workflow_execution<R: Runtime>(
+    app_handle: AppHandle<R>,
+    execution_id: String,
+    node_id: Option<String>,
+) -> Result<ExecuteWorkflowCanvasResponse> {
+    let orchestrator = WorkflowOrchestrator::new(app_handle);
+    let execution_id = orchestrator.resume(&execution_id, node_id).await?;
+
+    Ok(ExecuteWorkflowCanvasResponse { execution_id })
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunWorkflowLoadTestRequest {
+    pub workflow_id: String,
+    pub environment_id: Option<String>,
+    #[serde(flatten)]
+    pub config: LoadTestConfig,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunWorkflowLoadTestResponse {
+    pub load_test_id: String,
+}
+
+#[command]
+pub(crate) async fn cmd_run_workflow_load_test<R: Runtime>(
+    app_handle: AppHandle<R>,
+    req: RunWorkflowLoadTestRequest,
+) -> Result<RunWorkflowLoadTestResponse> {
+    let orchestrator = WorkflowOrchestrator::new(app_handle);
+    let load_test_id = orchestrator.load_test(req.workflow_id, req.environment_id, req.config).await?;
+
+    Ok(RunWorkflowLoadTestResponse { load_test_id })
+}
+
+#[command]
+pub(crate) async fn cmd_webhook_base_url() -> Result<Option<String>> {
+    crate::workflow_execution::webhook::base_url().map_err(crate::error::Error::GenericError)
+}
+
 // 5.5 Import/Export Commands
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -524,6 +523,18 @@ pub(crate) async fn cmd_export_workflow_json<R: Runtime>(
     Ok(ExportWorkflowJsonResponse { json })
 }
 
+#[command]
+pub(crate) async fn cmd_export_workflow_execution_report<R: Runtime>(
+    app_handle: AppHandle<R>,
+    execution_id: String,
+    format: ReportFormat,
+    file_path: String,
+) -> Result<()> {
+    let report = ExecutionReport::load(&app_handle.db(), &execution_id)?;
+    std::fs::write(&file_path, report.export(format)?)?;
+    Ok(())
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ImportWorkflowJsonRequest {
@@ -596,6 +607,7 @@ pub(crate) async fn cmd_import_workflow_json<R: Runtime>(
         let old_node_id = node.id.clone();
         let new_node_id = generate_prefixed_id("wn");
         node.id = new_node_id.clone();
+        node.workspace_id = workflow.workspace_id.clone();
         node.workflow_id = new_workflow_id.clone();
         node.created_at = Utc::now().naive_utc();
         node.updated_at = Utc::now().naive_utc();
@@ -607,6 +619,7 @@ pub(crate) async fn cmd_import_workflow_json<R: Runtime>(
     // Create edges with remapped node IDs
     for mut edge in edges {
         edge.id = generate_prefixed_id("we");
+        edge.workspace_id = workflow.workspace_id.clone();
         edge.workflow_id = new_workflow_id.clone();
         edge.source_node_id = node_id_map.get(&edge.source_node_id)
             .cloned()
@@ -634,130 +647,3 @@ pub(crate) async fn cmd_import_workflow_json<R: Runtime>(
         workflow_id: new_workflow_id,
     })
 }
-
-// 5.6 Migration Command
-
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct MigrateWorkflowToCanvasResponse {
-    pub success: bool,
-    pub nodes_created: usize,
-    pub edges_created: usize,
-}
-
-#[command]
-pub(crate) async fn cmd_migrate_workflow_to_canvas<R: Runtime>(
-    app_handle: AppHandle<R>,
-    workflow_id: String,
-) -> Result<MigrateWorkflowToCanvasResponse> {
-    let db = app_handle.db();
-
-    // Load workflow and steps
-    let mut workflow = db.get_workflow(&workflow_id)?;
-    let steps = db.list_workflow_steps(&workflow_id)?;
-
-    if steps.is_empty() {
-        return Ok(MigrateWorkflowToCanvasResponse {
-            success: true,
-            nodes_created: 0,
-            edges_created: 0,
-        });
-    }
-
-    let mut nodes_created = 0;
-    let mut edges_created = 0;
-    let horizontal_spacing = 300.0;
-    let start_x = 100.0;
-    let start_y = 200.0;
-
-    // Create manual trigger node
-    let trigger_node = WorkflowNode {
-        id: generate_prefixed_id("wn"),
-        model: "workflow_node".to_string(),
-        workflow_id: workflow_id.clone(),
-        node_type: NodeType::Trigger,
-        node_subtype: "manual".to_string(),
-        name: "Start".to_string(),
-        description: Some("Manual trigger".to_string()),
-        position_x: start_x,
-        position_y: start_y,
-        width: 200.0,
-        height: 100.0,
-        config: serde_json::json!({}),
-        enabled: true,
-        legacy_step_id: None,
-        created_at: Utc::now().naive_utc(),
-        updated_at: Utc::now().naive_utc(),
-        deleted_at: None,
-    };
-
-    db.upsert(&trigger_node, &UpdateSource::Background)?;
-    nodes_created += 1;
-
-    let mut prev_node_id = trigger_node.id.clone();
-
-    // Convert each step to action node
-    for (idx, step) in steps.iter().enumerate() {
-        let node_subtype = match step.request_model.as_str() {
-            "http_request" => "http_request",
-            "grpc_request" => "grpc_request",
-            _ => "http_request",
-        };
-
-        let action_node = WorkflowNode {
-            id: generate_prefixed_id("wn"),
-            model: "workflow_node".to_string(),
-            workflow_id: workflow_id.clone(),
-            node_type: NodeType::Action,
-            node_subtype: node_subtype.to_string(),
-            name: step.name.clone(),
-            description: None,
-            position_x: start_x + (idx as f64 + 1.0) * horizontal_spacing,
-            position_y: start_y,
-            width: 200.0,
-            height: 100.0,
-            config: serde_json::json!({
-                "request_id": step.request_id,
-                "request_model": step.request_model,
-            }),
-            enabled: step.enabled,
-            legacy_step_id: Some(step.id.clone()),
-            created_at: Utc::now().naive_utc(),
-            updated_at: Utc::now().naive_utc(),
-            deleted_at: None,
-        };
-
-        db.upsert(&action_node, &UpdateSource::Background)?;
-        nodes_created += 1;
-
-        // Create sequential edge
-        let edge = WorkflowEdge {
-            id: generate_prefixed_id("we"),
-            model: "workflow_edge".to_string(),
-            workflow_id: workflow_id.clone(),
-            source_node_id: prev_node_id.clone(),
-            source_anchor: "output".to_string(),
-            target_node_id: action_node.id.clone(),
-            target_anchor: "input".to_string(),
-            edge_type: EdgeType::Sequential,
-            created_at: Utc::now().naive_utc(),
-            updated_at: Utc::now().naive_utc(),
-            deleted_at: None,
-        };
-
-        db.upsert(&edge, &UpdateSource::Background)?;
-        edges_created += 1;
-
-        prev_node_id = action_node.id.clone();
-    }
-
-    // Update workflow timestamp (no canvas_enabled field needed)
-    workflow.updated_at = Utc::now().naive_utc();
-    db.upsert(&workflow, &UpdateSource::Background)?;
-
-    Ok(MigrateWorkflowToCanvasResponse {
-        success: true,
-        nodes_created,
-        edges_created,
-    })
-}
@@ -0,0 +1,268 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use rustls::pki_types::CertificateDer;
+use rustls::{CipherSuite, ProtocolVersion};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Instant;
+use tower_layer::Layer;
+use tower_service::Service;
+use yaak_http::tls::TlsObserver;
+use yaak_models::models::{HttpResponseTimings, HttpResponseTls};
+
+#[derive(Debug, Default)]
+struct Marks {
+    connect_start: Option<Instant>,
+    dns_start: Option<Instant>,
+    dns_end: Option<Instant>,
+    tls_start: Option<Instant>,
+    connect_end: Option<Instant>,
+    tls_version: Option<String>,
+    tls_cipher: Option<String>,
+    peer_certificates: Vec<String>,
+}
+
+/// Records when each phase of the most recent connection of a client happened. It's fed by the
+/// DNS resolver, a connector layer and the TLS config, so a fresh one is needed per client.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ConnectionRecorder {
+    marks: Arc<Mutex<Marks>>,
+}
+
+impl ConnectionRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn mark(&self, f: impl FnOnce(&mut Marks)) {
+        if let Ok(mut marks) = self.marks.lock() {
+            f(&mut marks);
+        }
+    }
+
+    fn connect_started(&self) {
+        self.mark(|m| {
+            *m = Marks {
+                connect_start: Some(Instant::now()),
+                ..Default::default()
+            }
+        });
+    }
+
+    fn connect_finished(&self) {
+        self.mark(|m| m.connect_end = Some(Instant::now()));
+    }
+
+    pub fn dns_started(&self) {
+        self.mark(|m| m.dns_start = Some(Instant::now()));
+    }
+
+    pub fn dns_finished(&self) {
+        self.mark(|m| m.dns_end = Some(Instant::now()));
+    }
+
+    /// Phase timings of a request that was sent at `sent_at`. A connection that was started
+    /// before then belongs to an earlier redirect, so the request reused it.
+    pub fn timings(
+        &self,
+        sent_at: Instant,
+        headers_at: Instant,
+        done_at: Instant,
+    ) -> HttpResponseTimings {
+        let marks = match self.marks.lock() {
+            Ok(m) => m,
+            Err(_) => return HttpResponseTimings::default(),
+        };
+
+        let connect_end = match (marks.connect_start, marks.connect_end) {
+            (Some(start), Some(end)) if start >= sent_at => end,
+            _ => {
+                return HttpResponseTimings {
+                    ttfb: millis(sent_at, headers_at),
+                    download: millis(headers_at, done_at),
+                    ..Default::default()
+                };
+            }
+        };
+
+        let tcp_start = marks.dns_end.or(marks.connect_start).unwrap_or(sent_at);
+        let tcp_end = marks.tls_start.unwrap_or(connect_end);
+        HttpResponseTimings {
+            dns: match (marks.dns_start, marks.dns_end) {
+                (Some(start), Some(end)) => millis(start, end),
+                _ => 0,
+            },
+            connect: millis(tcp_start, tcp_end),
+            tls: marks.tls_start.map(|start| millis(start, connect_end)).unwrap_or_default(),
+            ttfb: millis(connect_end, headers_at),
+            download: millis(headers_at, done_at),
+        }
+    }
+
+    pub fn tls(&self) -> Option<HttpResponseTls> {
+        let marks = self.marks.lock().ok()?;
+        if marks.tls_start.is_none() {
+            return None;
+        }
+
+        Some(HttpResponseTls {
+            version: marks.tls_version.clone(),
+            cipher: marks.tls_cipher.clone(),
+            peer_certificates: marks.peer_certificates.clone(),
+        })
+    }
+}
+
+impl TlsObserver for ConnectionRecorder {
+    fn handshake_started(&self) {
+        // A HelloRetryRequest makes rustls ask again, but the handshake started with the first
+        self.mark(|m| {
+            m.tls_start.get_or_insert_with(Instant::now);
+        });
+    }
+
+    fn protocol_version(&self, version: ProtocolVersion) {
+        let version = match version {
+            ProtocolVersion::TLSv1_2 => "TLSv1.2".to_string(),
+            ProtocolVersion::TLSv1_3 => "TLSv1.3".to_string(),
+            v => format!("{v:?}"),
+        };
+        self.mark(|m| m.tls_version = Some(version));
+    }
+
+    fn cipher_suite(&self, suite: CipherSuite) {
+        self.mark(|m| m.tls_cipher = Some(format!("{suite:?}")));
+    }
+
+    fn server_certificates(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+    ) {
+        let certificates =
+            std::iter::once(end_entity).chain(intermediates).map(|c| to_pem(c)).collect();
+        self.mark(|m| m.peer_certificates = certificates);
+    }
+}
+
+impl<S> Layer<S> for ConnectionRecorder {
+    type Service = RecordingConnector<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RecordingConnector {
+            inner,
+            recorder: self.clone(),
+        }
+    }
+}
+
+/// Wraps the client's connector to mark the start and end of every new connection
+#[derive(Debug, Clone)]
+pub(crate) struct RecordingConnector<S> {
+    inner: S,
+    recorder: ConnectionRecorder,
+}
+
+impl<S, R> Service<R> for RecordingConnector<S>
+where
+    S: Service<R> + 'static,
+    S::Future: Send + 'static,
+    R: 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: R) -> Self::Future {
+        self.recorder.connect_started();
+        let recorder = self.recorder.clone();
+        let connecting = self.inner.call(req);
+        Box::pin(async move {
+            let result = connecting.await;
+            recorder.connect_finished();
+            result
+        })
+    }
+}
+
+pub(crate) fn to_pem(der: &[u8]) -> String {
+    let encoded = STANDARD.encode(der);
+    let lines = encoded
+        .as_bytes()
+        .chunks(64)
+        .map(|c| String::from_utf8_lossy(c))
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!("-----BEGIN CERTIFICATE-----\n{lines}\n-----END CERTIFICATE-----\n")
+}
+
+fn millis(from: Instant, to: Instant) -> i32 {
+    to.saturating_duration_since(from).as_millis() as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_timings_for_new_connection() {
+        let recorder = ConnectionRecorder::new();
+        let sent_at = Instant::now();
+        recorder.connect_started();
+        let connect_start = recorder.marks.lock().unwrap().connect_start.unwrap();
+        {
+            let mut m = recorder.marks.lock().unwrap();
+            m.dns_start = Some(connect_start);
+            m.dns_end = Some(connect_start + Duration::from_millis(5));
+            m.tls_start = Some(connect_start + Duration::from_millis(15));
+            m.connect_end = Some(connect_start + Duration::from_millis(45));
+        }
+
+        let headers_at = connect_start + Duration::from_millis(145);
+        let done_at = headers_at + Duration::from_millis(20);
+        let t = recorder.timings(sent_at, headers_at, done_at);
+        assert_eq!((t.dns, t.connect, t.tls, t.ttfb, t.download), (5, 10, 30, 100, 20));
+    }
+
+    #[test]
+    fn test_timings_for_reused_connection() {
+        let recorder = ConnectionRecorder::new();
+        recorder.connect_started();
+        recorder.connect_finished();
+
+        let sent_at = Instant::now() + Duration::from_millis(10);
+        let headers_at = sent_at + Duration::from_millis(50);
+        let t = recorder.timings(sent_at, headers_at, headers_at);
+        assert_eq!((t.dns, t.connect, t.tls, t.ttfb, t.download), (0, 0, 0, 50, 0));
+        assert!(recorder.tls().is_none());
+    }
+
+    #[test]
+    fn test_tls_details() {
+        let recorder = ConnectionRecorder::new();
+        recorder.connect_started();
+        recorder.handshake_started();
+        recorder.protocol_version(ProtocolVersion::TLSv1_3);
+        recorder.cipher_suite(CipherSuite::TLS13_AES_128_GCM_SHA256);
+        recorder.server_certificates(&CertificateDer::from(vec![1, 2, 3]), &[]);
+
+        let tls = recorder.tls().unwrap();
+        assert_eq!(tls.version.as_deref(), Some("TLSv1.3"));
+        assert_eq!(tls.cipher.as_deref(), Some("TLS13_AES_128_GCM_SHA256"));
+        assert_eq!(
+            tls.peer_certificates,
+            vec!["-----BEGIN CERTIFICATE-----\nAQID\n-----END CERTIFICATE-----\n"]
+        );
+
+        // A new connection starts over
+        recorder.connect_started();
+        assert!(recorder.tls().is_none());
+    }
+}
@@ -2,11 +2,11 @@ use std::collections::BTreeMap;
 
 use crate::error::Result;
 use KeyAndValueRef::{Ascii, Binary};
-use tauri::{Manager, Runtime, WebviewWindow};
+use tauri::{Manager, Runtime};
 use yaak_grpc::{KeyAndValueRef, MetadataMap};
 use yaak_models::models::GrpcRequest;
 use yaak_models::query_manager::QueryManagerExt;
-use yaak_plugins::events::{CallHttpAuthenticationRequest, HttpHeader};
+use yaak_plugins::events::{CallHttpAuthenticationRequest, HttpHeader, PluginWindowContext};
 use yaak_plugins::manager::PluginManager;
 
 pub(crate) fn metadata_to_map(metadata: MetadataMap) -> BTreeMap<String, String> {
@@ -21,28 +21,29 @@ pub(crate) fn metadata_to_map(metadata: MetadataMap) -> BTreeMap<String, String>
 }
 
 pub(crate) fn resolve_grpc_request<R: Runtime>(
-    window: &WebviewWindow<R>,
+    manager: &impl Manager<R>,
     request: &GrpcRequest,
 ) -> Result<(GrpcRequest, String)> {
     let mut new_request = request.clone();
 
     let (authentication_type, authentication, authentication_context_id) =
-        window.db().resolve_auth_for_grpc_request(request)?;
+        manager.db().resolve_auth_for_grpc_request(request)?;
     new_request.authentication_type = authentication_type;
     new_request.authentication = authentication;
 
-    let metadata = window.db().resolve_metadata_for_grpc_request(request)?;
+    let metadata = manager.db().resolve_metadata_for_grpc_request(request)?;
     new_request.metadata = metadata;
 
     Ok((new_request, authentication_context_id))
 }
 
 pub(crate) async fn build_metadata<R: Runtime>(
-    window: &WebviewWindow<R>,
+    manager: &impl Manager<R>,
+    window_context: &PluginWindowContext,
     request: &GrpcRequest,
     authentication_context_id: &str,
 ) -> Result<BTreeMap<String, String>> {
-    let plugin_manager = window.state::<PluginManager>();
+    let plugin_manager = manager.state::<PluginManager>();
     let mut metadata = BTreeMap::new();
 
     // Add the rest of metadata
@@ -81,7 +82,7 @@ pub(crate) async fn build_metadata<R: Runtime>(
                     .collect(),
             };
             let plugin_result = plugin_manager
-                .call_http_authentication(&window, &authentication_type, plugin_req)
+                .call_http_authentication(window_context, &authentication_type, plugin_req)
                 .await?;
             for header in plugin_result.set_headers.unwrap_or_default() {
                 metadata.insert(header.name, header.value);
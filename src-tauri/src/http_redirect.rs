@@ -0,0 +1,301 @@
+use crate::error::Error::GenericError;
+use crate::error::Result;
+use http::header::{
+    AUTHORIZATION, CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE, COOKIE, LOCATION,
+    PROXY_AUTHORIZATION, TRANSFER_ENCODING, WWW_AUTHENTICATE,
+};
+use http::{HeaderMap, HeaderName, StatusCode};
+use log::debug;
+use reqwest::{Client, Method, Request, Response, Url};
+use std::time::Instant;
+use tokio::sync::watch::Receiver;
+use yaak_models::models::{HttpResponseHeader, HttpResponseRedirect};
+
+/// Headers that carry credentials and must never follow a redirect to another origin
+const SENSITIVE_HEADERS: [HeaderName; 4] =
+    [AUTHORIZATION, COOKIE, PROXY_AUTHORIZATION, WWW_AUTHENTICATE];
+
+#[derive(Debug, PartialEq)]
+enum RedirectMethod {
+    /// Resend the request as-is, including the body
+    Keep,
+    /// Resend the request as a GET, without a body
+    Get,
+}
+
+/// Send a request, following redirects one hop at a time so each one can be recorded.
+/// `auth_headers` are headers set by the authentication plugin, which are stripped along
+/// with the usual credential headers whenever a redirect leaves the original origin.
+pub(crate) async fn send_following_redirects(
+    client: &Client,
+    request: Request,
+    max_redirects: usize,
+    auth_headers: &[HeaderName],
+    redirects: &mut Vec<HttpResponseRedirect>,
+    cancelled_rx: &Receiver<bool>,
+    start: Instant,
+) -> Result<Response> {
+    let mut request = request;
+    loop {
+        let method = request.method().clone();
+        let url = request.url().clone();
+        let headers = request.headers().clone();
+        let retry = request.try_clone();
+
+        let response = client.execute(request).await?;
+        let status = response.status();
+        if redirect_method(status, &method).is_none() {
+            return Ok(response);
+        }
+        let Some(location) = response.headers().get(LOCATION).and_then(|l| l.to_str().ok()) else {
+            return Ok(response);
+        };
+
+        let next_url = url
+            .join(location)
+            .map_err(|e| GenericError(format!("Invalid redirect location \"{location}\": {e}")))?;
+
+        redirects.push(HttpResponseRedirect {
+            status: status.as_u16() as i32,
+            method: method.to_string(),
+            url: url.to_string(),
+            location: location.to_string(),
+            headers: response
+                .headers()
+                .iter()
+                .map(|(k, v)| HttpResponseHeader {
+                    name: k.as_str().to_string(),
+                    value: v.to_str().unwrap_or_default().to_string(),
+                })
+                .collect(),
+            elapsed: start.elapsed().as_millis() as i32,
+        });
+
+        if redirects.len() > max_redirects {
+            return Err(GenericError(format!("Too many redirects (max {max_redirects})")));
+        }
+
+        if *cancelled_rx.borrow() {
+            return Err(GenericError("Request was cancelled".to_string()));
+        }
+
+        debug!("Following {status} redirect to {next_url}");
+        request = redirect_request(status, &method, &url, &headers, retry, next_url, auth_headers)?;
+    }
+}
+
+/// How the method and body change when following a redirect with the given status
+fn redirect_method(status: StatusCode, method: &Method) -> Option<RedirectMethod> {
+    match status {
+        // Historically every client turns a POST into a GET for these, so servers expect it
+        StatusCode::MOVED_PERMANENTLY | StatusCode::FOUND if method == Method::POST => {
+            Some(RedirectMethod::Get)
+        }
+        StatusCode::MOVED_PERMANENTLY | StatusCode::FOUND => Some(RedirectMethod::Keep),
+        StatusCode::SEE_OTHER if method == Method::HEAD => Some(RedirectMethod::Keep),
+        StatusCode::SEE_OTHER => Some(RedirectMethod::Get),
+        StatusCode::TEMPORARY_REDIRECT | StatusCode::PERMANENT_REDIRECT => {
+            Some(RedirectMethod::Keep)
+        }
+        _ => None,
+    }
+}
+
+fn is_same_origin(a: &Url, b: &Url) -> bool {
+    a.scheme() == b.scheme()
+        && a.host_str() == b.host_str()
+        && a.port_or_known_default() == b.port_or_known_default()
+}
+
+fn redirect_request(
+    status: StatusCode,
+    method: &Method,
+    url: &Url,
+    headers: &HeaderMap,
+    retry: Option<Request>,
+    next_url: Url,
+    auth_headers: &[HeaderName],
+) -> Result<Request> {
+    let mut request = match redirect_method(status, method) {
+        Some(RedirectMethod::Keep) => match retry {
+            Some(mut r) => {
+                *r.url_mut() = next_url.clone();
+                r
+            }
+            None => {
+                return Err(GenericError(format!(
+                    "Cannot follow {status} redirect because the request body can't be sent again"
+                )));
+            }
+        },
+        Some(RedirectMethod::Get) => {
+            let mut r = Request::new(Method::GET, next_url.clone());
+            *r.headers_mut() = headers.clone();
+            for h in [
+                CONTENT_TYPE,
+                CONTENT_LENGTH,
+                CONTENT_ENCODING,
+                TRANSFER_ENCODING,
+            ] {
+                r.headers_mut().remove(h);
+            }
+            r
+        }
+        None => return Err(GenericError(format!("{status} is not a redirect"))),
+    };
+
+    if !is_same_origin(url, &next_url) {
+        for h in SENSITIVE_HEADERS.iter().chain(auth_headers) {
+            request.headers_mut().remove(h);
+        }
+    }
+
+    Ok(request)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::HeaderValue;
+
+    fn url(s: &str) -> Url {
+        Url::parse(s).unwrap()
+    }
+
+    fn request(method: Method, body: Option<&str>) -> (Request, HeaderMap) {
+        let mut r = Request::new(method, url("https://api.example.com/login"));
+        let headers = r.headers_mut();
+        headers.insert(AUTHORIZATION, HeaderValue::from_static("Bearer abc"));
+        headers.insert(COOKIE, HeaderValue::from_static("session=1"));
+        headers.insert("x-api-key", HeaderValue::from_static("secret"));
+        headers.insert("x-trace", HeaderValue::from_static("1"));
+        if let Some(b) = body {
+            headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+            *r.body_mut() = Some(b.to_string().into());
+        }
+        let headers = r.headers().clone();
+        (r, headers)
+    }
+
+    #[test]
+    fn test_redirect_method() {
+        use RedirectMethod::*;
+        let cases = [
+            (301, Method::POST, Some(Get)),
+            (301, Method::PUT, Some(Keep)),
+            (302, Method::POST, Some(Get)),
+            (302, Method::GET, Some(Keep)),
+            (303, Method::PUT, Some(Get)),
+            (303, Method::HEAD, Some(Keep)),
+            (307, Method::POST, Some(Keep)),
+            (308, Method::PATCH, Some(Keep)),
+            (304, Method::GET, None),
+            (200, Method::GET, None),
+        ];
+        for (status, method, expected) in cases {
+            let status = StatusCode::from_u16(status).unwrap();
+            assert_eq!(redirect_method(status, &method), expected, "{status} {method}");
+        }
+    }
+
+    #[test]
+    fn test_is_same_origin() {
+        assert!(is_same_origin(&url("https://a.com/x"), &url("https://a.com:443/y?z")));
+        assert!(!is_same_origin(&url("https://a.com"), &url("http://a.com")));
+        assert!(!is_same_origin(&url("https://a.com"), &url("https://b.a.com")));
+        assert!(!is_same_origin(&url("http://a.com"), &url("http://a.com:8080")));
+    }
+
+    #[test]
+    fn test_redirect_request_rewrites_post_to_get() {
+        let (r, headers) = request(Method::POST, Some("{}"));
+        let original = r.url().clone();
+        let next = redirect_request(
+            StatusCode::FOUND,
+            &Method::POST,
+            &original,
+            &headers,
+            r.try_clone(),
+            url("https://api.example.com/home"),
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(next.method(), Method::GET);
+        assert_eq!(next.url().as_str(), "https://api.example.com/home");
+        assert!(next.body().is_none());
+        assert!(next.headers().get(CONTENT_TYPE).is_none());
+        assert_eq!(next.headers().get(AUTHORIZATION).unwrap(), "Bearer abc");
+    }
+
+    #[test]
+    fn test_redirect_request_keeps_method_and_body() {
+        let (r, headers) = request(Method::PUT, Some("{}"));
+        let original = r.url().clone();
+        let next = redirect_request(
+            StatusCode::PERMANENT_REDIRECT,
+            &Method::PUT,
+            &original,
+            &headers,
+            r.try_clone(),
+            url("https://api.example.com/v2/login"),
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(next.method(), Method::PUT);
+        assert_eq!(next.body().and_then(|b| b.as_bytes()), Some("{}".as_bytes()));
+        assert_eq!(next.headers().get(CONTENT_TYPE).unwrap(), "application/json");
+    }
+
+    #[test]
+    fn test_redirect_request_without_replayable_body() {
+        let (_, headers) = request(Method::POST, Some("{}"));
+        let result = redirect_request(
+            StatusCode::TEMPORARY_REDIRECT,
+            &Method::POST,
+            &url("https://api.example.com/login"),
+            &headers,
+            None,
+            url("https://api.example.com/other"),
+            &[],
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_redirect_request_strips_credentials_cross_origin() {
+        let (r, headers) = request(Method::GET, None);
+        let original = r.url().clone();
+        let auth_headers = [HeaderName::from_static("x-api-key")];
+
+        let same = redirect_request(
+            StatusCode::FOUND,
+            &Method::GET,
+            &original,
+            &headers,
+            r.try_clone(),
+            url("https://api.example.com/callback"),
+            &auth_headers,
+        )
+        .unwrap();
+        assert!(same.headers().contains_key(AUTHORIZATION));
+        assert!(same.headers().contains_key(COOKIE));
+        assert!(same.headers().contains_key("x-api-key"));
+
+        let cross = redirect_request(
+            StatusCode::FOUND,
+            &Method::GET,
+            &original,
+            &headers,
+            r.try_clone(),
+            url("https://sso.example.org/authorize"),
+            &auth_headers,
+        )
+        .unwrap();
+        assert!(!cross.headers().contains_key(AUTHORIZATION));
+        assert!(!cross.headers().contains_key(COOKIE));
+        assert!(!cross.headers().contains_key("x-api-key"));
+        assert_eq!(cross.headers().get("x-trace").unwrap(), "1");
+    }
+}
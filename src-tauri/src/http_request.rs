@@ -1,12 +1,16 @@
+use crate::connection_recorder::{ConnectionRecorder, to_pem};
 use crate::error::Error::GenericError;
 use crate::error::Result;
+use crate::http_redirect::send_following_redirects;
 use crate::render::render_http_request;
 use crate::response_err;
+use crate::sent_request::SentRequest;
 use http::header::{ACCEPT, USER_AGENT};
 use http::{HeaderMap, HeaderName, HeaderValue};
 use log::{debug, error, warn};
 use mime_guess::Mime;
 use reqwest::redirect::Policy;
+use reqwest::tls::TlsInfo;
 use reqwest::{Method, NoProxy, Response};
 use reqwest::{Proxy, Url, multipart};
 use serde_json::Value;
@@ -15,7 +19,7 @@ use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
-use tauri::{Manager, Runtime, WebviewWindow};
+use tauri::{AppHandle, Manager, Runtime, WebviewWindow};
 use tokio::fs;
 use tokio::fs::{File, create_dir_all};
 use tokio::io::AsyncWriteExt;
@@ -23,8 +27,10 @@ use tokio::sync::watch::Receiver;
 use tokio::sync::{Mutex, oneshot};
 use yaak_models::models::{
     Cookie, CookieJar, Environment, HttpRequest, HttpResponse, HttpResponseHeader,
-    HttpResponseState, ProxySetting, ProxySettingAuth,
+    HttpResponseRedirect, HttpResponseState, HttpResponseTls, ProxySetting, ProxySettingAuth,
 };
+use yaak_http::dns::DnsResolver;
+use yaak_http::tls::TlsOptions;
 use yaak_models::query_manager::QueryManagerExt;
 use yaak_models::util::UpdateSource;
 use yaak_plugins::events::{
@@ -33,7 +39,7 @@ use yaak_plugins::events::{
 use yaak_plugins::manager::PluginManager;
 use yaak_plugins::template_callback::PluginTemplateCallback;
 use yaak_templates::{RenderErrorBehavior, RenderOptions};
-use crate::dns::LocalhostResolver;
+use crate::dns::HttpResolver;
 
 pub async fn send_http_request<R: Runtime>(
     window: &WebviewWindow<R>,
@@ -43,12 +49,36 @@ pub async fn send_http_request<R: Runtime>(
     cookie_jar: Option<CookieJar>,
     cancelled_rx: &mut Receiver<bool>,
 ) -> Result<HttpResponse> {
-    let app_handle = window.app_handle().clone();
+    send_http_request_with_context(
+        window.app_handle(),
+        &PluginWindowContext::new(window),
+        &UpdateSource::from_window(window),
+        unrendered_request,
+        og_response,
+        environment,
+        cookie_jar,
+        cancelled_rx,
+    )
+    .await
+}
+
+/// Send a request without needing a window, for background work like workflow runs
+pub async fn send_http_request_with_context<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    window_context: &PluginWindowContext,
+    update_source: &UpdateSource,
+    unrendered_request: &HttpRequest,
+    og_response: &HttpResponse,
+    environment: Option<Environment>,
+    cookie_jar: Option<CookieJar>,
+    cancelled_rx: &mut Receiver<bool>,
+) -> Result<HttpResponse> {
+    let app_handle = app_handle.clone();
     let plugin_manager = app_handle.state::<PluginManager>();
-    let settings = window.db().get_settings();
-    let workspace = window.db().get_workspace(&unrendered_request.workspace_id)?;
+    let settings = app_handle.db().get_settings();
+    let workspace = app_handle.db().get_workspace(&unrendered_request.workspace_id)?;
     let environment_id = environment.map(|e| e.id);
-    let environment_chain = window.db().resolve_environments(
+    let environment_chain = app_handle.db().resolve_environments(
         &unrendered_request.workspace_id,
         unrendered_request.folder_id.as_deref(),
         environment_id.as_deref(),
@@ -57,26 +87,22 @@ pub async fn send_http_request<R: Runtime>(
     let response_id = og_response.id.clone();
     let response = Arc::new(Mutex::new(og_response.clone()));
 
-    let update_source = UpdateSource::from_window(window);
+    let update_source = update_source.clone();
 
-    let (resolved_request, auth_context_id) = match resolve_http_request(window, unrendered_request)
-    {
-        Ok(r) => r,
-        Err(e) => {
-            return Ok(response_err(
-                &app_handle,
-                &*response.lock().await,
-                e.to_string(),
-                &update_source,
-            ));
-        }
-    };
+    let (resolved_request, auth_context_id) =
+        match resolve_http_request(&app_handle, unrendered_request) {
+            Ok(r) => r,
+            Err(e) => {
+                return Ok(response_err(
+                    &app_handle,
+                    &*response.lock().await,
+                    e.to_string(),
+                    &update_source,
+                ));
+            }
+        };
 
-    let cb = PluginTemplateCallback::new(
-        window.app_handle(),
-        &PluginWindowContext::new(window),
-        RenderPurpose::Send,
-    );
+    let cb = PluginTemplateCallback::new(&app_handle, window_context, RenderPurpose::Send);
 
     let opt = RenderOptions {
         error_behavior: RenderErrorBehavior::Throw,
@@ -102,20 +128,53 @@ pub async fn send_http_request<R: Runtime>(
     }
     debug!("Sending request to {} {url_string}", request.method);
 
+    let url = match Url::from_str(&url_string) {
+        Ok(u) => u,
+        Err(e) => {
+            return Ok(response_err(
+                &app_handle,
+                &*response.lock().await,
+                format!("Failed to parse URL \"{}\": {}", url_string, e.to_string()),
+                &update_source,
+            ));
+        }
+    };
+
+    let recorder = ConnectionRecorder::new();
     let mut client_builder = reqwest::Client::builder()
-        .redirect(match workspace.setting_follow_redirects {
-            true => Policy::limited(10), // TODO: Handle redirects natively
-            false => Policy::none(),
-        })
+        // Redirects are followed by send_following_redirects so every hop can be recorded
+        .redirect(Policy::none())
         .connection_verbose(true)
         .gzip(true)
         .brotli(true)
         .deflate(true)
-        .dns_resolver(LocalhostResolver::new())
+        .dns_resolver(HttpResolver::new(DnsResolver::from(&workspace), recorder.clone()))
+        .connector_layer(recorder.clone())
         .referer(false)
         .tls_info(true);
 
-    let tls_config = yaak_http::tls::get_config(workspace.setting_validate_certificates, true);
+    // NOTE: The certificates are picked for the request's host, so redirects to other hosts
+    //  reuse them
+    let tls_options = TlsOptions {
+        observer: Some(Arc::new(recorder.clone())),
+        ..TlsOptions::from(&workspace)
+    };
+    let tls_config = match yaak_http::tls::get_config(
+        &tls_options,
+        url.host_str().unwrap_or_default(),
+        url.port_or_known_default(),
+        true,
+    ) {
+        Ok(c) => c,
+        Err(e) => {
+            return Ok(response_err(
+                &app_handle,
+                &*response.lock().await,
+                e.to_string(),
+                &update_source,
+            ));
+        }
+    };
     client_builder = client_builder.use_preconfigured_tls(tls_config);
 
     match settings.proxy {
@@ -167,7 +226,7 @@ pub async fn send_http_request<R: Runtime>(
         Some(CookieJar { id, .. }) => {
             // NOTE: WE need to refetch the cookie jar because a chained request might have
             //  updated cookies when we rendered the request.
-            let cj = window.db().get_cookie_jar(&id)?;
+            let cj = app_handle.db().get_cookie_jar(&id)?;
             // HACK: Can't construct Cookie without serde, so we have to do this
             let cookies = cj
                 .cookies
@@ -206,18 +265,6 @@ pub async fn send_http_request<R: Runtime>(
         query_params.push((p.name, p.value));
     }
 
-    let url = match Url::from_str(&url_string) {
-        Ok(u) => u,
-        Err(e) => {
-            return Ok(response_err(
-                &app_handle,
-                &*response.lock().await,
-                format!("Failed to parse URL \"{}\": {}", url_string, e.to_string()),
-                &update_source,
-            ));
-        }
-    };
-
     let m = Method::from_str(&request.method.to_uppercase())
         .map_err(|e| GenericError(e.to_string()))?;
     let mut request_builder = client.request(m, url).query(&query_params);
@@ -447,6 +494,9 @@ pub async fn send_http_request<R: Runtime>(
         }
     };
 
+    // Headers set by the auth plugin, which must not be sent along to other origins
+    let mut auth_headers = Vec::new();
+
     match request.authentication_type {
         None => {
             // No authentication found. Not even inherited
@@ -469,8 +519,9 @@ pub async fn send_http_request<R: Runtime>(
                     })
                     .collect(),
             };
-            let auth_result =
-                plugin_manager.call_http_authentication(&window, &authentication_type, req).await;
+            let auth_result = plugin_manager
+                .call_http_authentication(window_context, &authentication_type, req)
+                .await;
             let plugin_result = match auth_result {
                 Ok(r) => r,
                 Err(e) => {
@@ -487,6 +538,7 @@ pub async fn send_http_request<R: Runtime>(
             for header in plugin_result.set_headers.unwrap_or_default() {
                 match (HeaderName::from_str(&header.name), HeaderValue::from_str(&header.value)) {
                     (Ok(name), Ok(value)) => {
+                        auth_headers.push(name.clone());
                         headers.insert(name, value);
                     }
                     _ => continue,
@@ -502,16 +554,40 @@ pub async fn send_http_request<R: Runtime>(
         }
     }
 
-    let (resp_tx, resp_rx) = oneshot::channel::<std::result::Result<Response, reqwest::Error>>();
+    // Copy the request as it goes out, so the response shows exactly what was sent
+    let sent_request = SentRequest::capture(&mut sendable_req);
+
+    let (resp_tx, resp_rx) = oneshot::channel::<(Result<Response>, Vec<HttpResponseRedirect>)>();
     let (done_tx, done_rx) = oneshot::channel::<HttpResponse>();
 
     let start = std::time::Instant::now();
 
-    tokio::spawn(async move {
-        let _ = resp_tx.send(client.execute(sendable_req).await);
-    });
+    {
+        let follow_redirects = workspace.setting_follow_redirects;
+        let max_redirects = workspace.setting_max_redirects.max(0) as usize;
+        let cancelled_rx = cancelled_rx.clone();
+        tokio::spawn(async move {
+            let mut redirects = Vec::new();
+            let result = match follow_redirects {
+                true => {
+                    send_following_redirects(
+                        &client,
+                        sendable_req,
+                        max_redirects,
+                        &auth_headers,
+                        &mut redirects,
+                        &cancelled_rx,
+                        start,
+                    )
+                    .await
+                }
+                false => client.execute(sendable_req).await.map_err(Into::into),
+            };
+            let _ = resp_tx.send((result, redirects));
+        });
+    }
 
-    let raw_response = tokio::select! {
+    let (raw_response, redirects) = tokio::select! {
         Ok(r) = resp_rx => r,
         _ = cancelled_rx.changed() => {
             let mut r = response.lock().await;
@@ -521,9 +597,24 @@ pub async fn send_http_request<R: Runtime>(
         }
     };
 
+    {
+        let name = if response_id.is_empty() {
+            uuid::Uuid::new_v4().to_string()
+        } else {
+            response_id.clone()
+        };
+        let request_body_path = app_handle
+            .path()
+            .app_data_dir()
+            .unwrap()
+            .join("responses")
+            .join(format!("{name}.request"));
+        let saved = sent_request.save(&request_body_path).await;
+        response.lock().await.request = Some(saved);
+    }
+
     {
         let app_handle = app_handle.clone();
-        let window = window.clone();
         let cancelled_rx = cancelled_rx.clone();
         let response_id = response_id.clone();
         let response = response.clone();
@@ -542,6 +633,13 @@ pub async fn send_http_request<R: Runtime>(
                         base_dir.join(response_id.clone())
                     };
 
+                    // After redirects, the final request went out when the last redirect came in
+                    let headers_at = std::time::Instant::now();
+                    let sent_at = redirects
+                        .last()
+                        .map(|r| start + Duration::from_millis(r.elapsed as u64))
+                        .unwrap_or(start);
+
                     {
                         let mut r = response.lock().await;
                         r.body_path = Some(body_path.to_str().unwrap().to_string());
@@ -557,6 +655,17 @@ pub async fn send_http_request<R: Runtime>(
                             })
                             .collect();
                         r.url = v.url().to_string();
+                        r.redirects = redirects;
+                        r.timings = recorder.timings(sent_at, headers_at, headers_at);
+                        // Fall back to the leaf certificate when the handshake wasn't observed
+                        r.tls = recorder.tls().or_else(|| {
+                            let certificate =
+                                v.extensions().get::<TlsInfo>()?.peer_certificate()?;
+                            Some(HttpResponseTls {
+                                peer_certificates: vec![to_pem(certificate)],
+                                ..Default::default()
+                            })
+                        });
                         r.remote_addr = v.remote_addr().map(|a| a.to_string());
                         r.version = match v.version() {
                             reqwest::Version::HTTP_09 => Some("HTTP/0.9".to_string()),
@@ -625,53 +734,41 @@ pub async fn send_http_request<R: Runtime>(
                             Some(l) => Some(l as i32),
                             None => Some(written_bytes as i32),
                         };
+                        r.timings.download = headers_at.elapsed().as_millis() as i32;
                         r.state = HttpResponseState::Closed;
                         app_handle
                             .db()
-                            .update_http_response_if_id(&r, &UpdateSource::from_window(&window))
+                            .update_http_response_if_id(&r, &update_source)
                             .expect("Failed to update response");
                     };
-
-                    // Add cookie store if specified
-                    if let Some((cookie_store, mut cookie_jar)) = maybe_cookie_manager {
-                        // let cookies = response_headers.get_all(SET_COOKIE).iter().map(|h| {
-                        //     println!("RESPONSE COOKIE: {}", h.to_str().unwrap());
-                        //     cookie_store::RawCookie::from_str(h.to_str().unwrap())
-                        //         .expect("Failed to parse cookie")
-                        // });
-                        // store.store_response_cookies(cookies, &url);
-
-                        let json_cookies: Vec<Cookie> = cookie_store
-                            .lock()
-                            .unwrap()
-                            .iter_any()
-                            .map(|c| {
-                                let json_cookie =
-                                    serde_json::to_value(&c).expect("Failed to serialize cookie");
-                                serde_json::from_value(json_cookie)
-                                    .expect("Failed to deserialize cookie")
-                            })
-                            .collect::<Vec<_>>();
-                        cookie_jar.cookies = json_cookies;
-                        if let Err(e) = app_handle
-                            .db()
-                            .upsert_cookie_jar(&cookie_jar, &UpdateSource::from_window(&window))
-                        {
-                            error!("Failed to update cookie jar: {}", e);
-                        };
-                    }
                 }
                 Err(e) => {
                     warn!("Failed to execute request {e}");
-                    response_err(
-                        &app_handle,
-                        &*response.lock().await,
-                        format!("{e} → {e:?}"),
-                        &update_source,
-                    );
+                    let mut r = response.lock().await;
+                    r.redirects = redirects;
+                    response_err(&app_handle, &r, format!("{e} → {e:?}"), &update_source);
                 }
             };
 
+            // Every hop goes through the client, so the store has the cookies set along the
+            // whole redirect chain, even when a later hop failed
+            if let Some((cookie_store, mut cookie_jar)) = maybe_cookie_manager {
+                let json_cookies: Vec<Cookie> = cookie_store
+                    .lock()
+                    .unwrap()
+                    .iter_any()
+                    .map(|c| {
+                        let json_cookie =
+                            serde_json::to_value(&c).expect("Failed to serialize cookie");
+                        serde_json::from_value(json_cookie).expect("Failed to deserialize cookie")
+                    })
+                    .collect::<Vec<_>>();
+                cookie_jar.cookies = json_cookies;
+                if let Err(e) = app_handle.db().upsert_cookie_jar(&cookie_jar, &update_source) {
+                    error!("Failed to update cookie jar: {}", e);
+                };
+            }
+
             let r = response.lock().await.clone();
             done_tx.send(r).unwrap();
         });
@@ -686,7 +783,7 @@ pub async fn send_http_request<R: Runtime>(
                     r.state = HttpResponseState::Closed;
                     r.elapsed = start.elapsed().as_millis() as i32;
                     r.elapsed_headers = start.elapsed().as_millis() as i32;
-                    app_handle.db().update_http_response_if_id(&r, &UpdateSource::from_window(window))
+                    app_handle.db().update_http_response_if_id(&r, &update_source)
                         .expect("Failed to update response")
                 },
                 _ => {
@@ -698,17 +795,17 @@ pub async fn send_http_request<R: Runtime>(
 }
 
 pub fn resolve_http_request<R: Runtime>(
-    window: &WebviewWindow<R>,
+    manager: &impl Manager<R>,
     request: &HttpRequest,
 ) -> Result<(HttpRequest, String)> {
     let mut new_request = request.clone();
 
     let (authentication_type, authentication, authentication_context_id) =
-        window.db().resolve_auth_for_http_request(request)?;
+        manager.db().resolve_auth_for_http_request(request)?;
     new_request.authentication_type = authentication_type;
     new_request.authentication = authentication;
 
-    let headers = window.db().resolve_headers_for_http_request(request)?;
+    let headers = manager.db().resolve_headers_for_http_request(request)?;
     new_request.headers = headers;
 
     Ok((new_request, authentication_context_id))
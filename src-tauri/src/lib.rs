@@ -31,6 +31,8 @@ use tokio::time;
 use yaak_common::window::WorkspaceWindowTrait;
 use yaak_grpc::manager::{DynamicMessage, GrpcHandle};
 use yaak_grpc::{Code, ServiceDefinition, deserialize_message, serialize_message};
+use yaak_http::dns::DnsResolver;
+use yaak_http::tls::TlsOptions;
 use yaak_models::models::{
     AnyModel, CookieJar, Environment, GrpcConnection, GrpcConnectionState, GrpcEvent,
     GrpcEventType, GrpcRequest, HttpRequest, HttpResponse, HttpResponseState, Plugin, Workspace,
@@ -53,17 +55,22 @@ use yaak_sse::sse::ServerSentEvent;
 use yaak_templates::format_json::format_json;
 use yaak_templates::{RenderErrorBehavior, RenderOptions, Tokens, transform_args};
 
+#[cfg(feature = "cli")]
+pub mod cli;
 mod commands;
+mod connection_recorder;
 mod dns;
 mod encoding;
 mod error;
 mod grpc;
 mod history;
+mod http_redirect;
 mod http_request;
 mod import;
 mod notifications;
 mod plugin_events;
 mod render;
+mod sent_request;
 mod updates;
 mod uri_scheme;
 mod window;
@@ -181,7 +188,9 @@ async fn cmd_grpc_reflect<R: Runtime>(
     .await?;
 
     let uri = safe_uri(&req.url);
-    let metadata = build_metadata(&window, &req, &auth_context_id).await?;
+    let metadata =
+        build_metadata(&window, &PluginWindowContext::new(&window), &req, &auth_context_id)
+            .await?;
 
     Ok(grpc_handle
         .lock()
@@ -191,7 +200,8 @@ async fn cmd_grpc_reflect<R: Runtime>(
             &uri,
             &proto_files.iter().map(|p| PathBuf::from_str(p).unwrap()).collect(),
             &metadata,
-            workspace.setting_validate_certificates,
+            &TlsOptions::from(&workspace),
+            &DnsResolver::from(&workspace),
         )
         .await
         .map_err(|e| GenericError(e.to_string()))?)
@@ -229,7 +239,9 @@ async fn cmd_grpc_go<R: Runtime>(
     )
     .await?;
 
-    let metadata = build_metadata(&window, &request, &auth_context_id).await?;
+    let metadata =
+        build_metadata(&window, &PluginWindowContext::new(&window), &request, &auth_context_id)
+            .await?;
 
     let conn = app_handle.db().upsert_grpc_connection(
         &GrpcConnection {
@@ -278,7 +290,8 @@ async fn cmd_grpc_go<R: Runtime>(
             uri.as_str(),
             &proto_files.iter().map(|p| PathBuf::from_str(p).unwrap()).collect(),
             &metadata,
-            workspace.setting_validate_certificates,
+            &TlsOptions::from(&workspace),
+            &DnsResolver::from(&workspace),
         )
         .await;
 
@@ -1409,6 +1422,10 @@ pub fn run() {
             let grpc_handle = GrpcHandle::new(&app.app_handle());
             app.manage(Mutex::new(grpc_handle));
 
+            // Fire scheduled and webhook workflows
+            workflow_execution::scheduler::start(app.app_handle());
+            workflow_execution::webhook::start(app.app_handle());
+
             monitor_plugin_events(&app.app_handle().clone());
 
             Ok(())
@@ -1459,9 +1476,6 @@ pub fn run() {
             crate::commands::cmd_secure_template,
             crate::commands::cmd_show_workspace_key,
             // Workflow commands
-            crate::commands::cmd_execute_workflow,
-            crate::commands::cmd_cancel_workflow_execution,
-            crate::commands::cmd_get_workflow_with_steps,
             crate::commands::cmd_get_workflow_execution_results,
             crate::commands::cmd_list_workflow_executions,
             // Workflow Canvas commands
@@ -1475,9 +1489,12 @@ pub fn run() {
             crate::commands::cmd_validate_workflow_graph,
             crate::commands::cmd_execute_workflow_canvas,
             crate::commands::cmd_cancel_workflow_execution_canvas,
+            crate::commands::cmd_resume_workflow_execution,
+            crate::commands::cmd_run_workflow_load_test,
+            crate::commands::cmd_webhook_base_url,
             crate::commands::cmd_export_workflow_json,
+            crate::commands::cmd_export_workflow_execution_report,
             crate::commands::cmd_import_workflow_json,
-            crate::commands::cmd_migrate_workflow_to_canvas,
         ])
         .build(tauri::generate_context!())
         .expect("error while running tauri application")
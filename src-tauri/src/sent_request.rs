@@ -0,0 +1,151 @@
+use http_body_util::BodyExt;
+use log::warn;
+use reqwest::{Body, Request};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use tokio::fs;
+use tokio_stream::StreamExt;
+use yaak_models::models::{HttpResponseHeader, HttpResponseRequest};
+
+/// Request bodies are only saved up to this size, so the history doesn't eat the disk
+const MAX_SAVED_BODY_SIZE: usize = 5 * 1024 * 1024;
+
+/// A copy of a request that is about to be sent, to be saved with its response
+pub(crate) struct SentRequest {
+    method: String,
+    url: String,
+    headers: Vec<HttpResponseHeader>,
+    body: Option<Arc<Mutex<SentBody>>>,
+}
+
+#[derive(Debug, Default)]
+struct SentBody {
+    bytes: Vec<u8>,
+    size: usize,
+}
+
+impl SentBody {
+    fn push(&mut self, bytes: &[u8]) {
+        let room = MAX_SAVED_BODY_SIZE.saturating_sub(self.bytes.len());
+        self.bytes.extend_from_slice(&bytes[..bytes.len().min(room)]);
+        self.size += bytes.len();
+    }
+}
+
+impl SentRequest {
+    /// Copy a request right before it's sent. Buffered bodies are copied right away, but
+    /// streamed ones (like multipart forms) can only be read once, so they're copied as they go.
+    pub fn capture(request: &mut Request) -> Self {
+        let body = request.body_mut().take().map(|body| {
+            let sent = Arc::new(Mutex::new(SentBody::default()));
+            let buffered = body.as_bytes().map(|bytes| {
+                if let Ok(mut s) = sent.lock() {
+                    s.push(bytes);
+                }
+            });
+            let body = match buffered {
+                Some(()) => body,
+                None => {
+                    let sent = sent.clone();
+                    Body::wrap_stream(body.into_data_stream().map(move |chunk| {
+                        if let (Ok(bytes), Ok(mut s)) = (&chunk, sent.lock()) {
+                            s.push(bytes);
+                        }
+                        chunk
+                    }))
+                }
+            };
+            *request.body_mut() = Some(body);
+            sent
+        });
+
+        SentRequest {
+            method: request.method().to_string(),
+            url: request.url().to_string(),
+            headers: request
+                .headers()
+                .iter()
+                .map(|(name, value)| HttpResponseHeader {
+                    name: name.to_string(),
+                    value: value.to_str().unwrap_or_default().to_string(),
+                })
+                .collect(),
+            body,
+        }
+    }
+
+    /// Write what was sent of the body to `body_path`, next to the response body
+    pub async fn save(&self, body_path: &Path) -> HttpResponseRequest {
+        let (bytes, size) = match &self.body {
+            Some(body) => body.lock().map(|b| (b.bytes.clone(), Some(b.size))).unwrap_or_default(),
+            None => (Vec::new(), None),
+        };
+
+        let saved_path = match bytes.is_empty() {
+            true => None,
+            false => match write_body(body_path, &bytes).await {
+                Ok(()) => Some(body_path.to_string_lossy().to_string()),
+                Err(e) => {
+                    warn!("Failed to save request body to {body_path:?}: {e}");
+                    None
+                }
+            },
+        };
+
+        HttpResponseRequest {
+            method: self.method.clone(),
+            url: self.url.clone(),
+            headers: self.headers.clone(),
+            body_path: saved_path,
+            content_length: size.map(|s| s as i32),
+        }
+    }
+}
+
+async fn write_body(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).await?;
+    }
+    fs::write(path, bytes).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::Method;
+
+    #[test]
+    fn test_body_is_capped() {
+        let mut body = SentBody::default();
+        body.push(&vec![1; MAX_SAVED_BODY_SIZE - 10]);
+        body.push(&[2; 30]);
+        body.push(&[3; 5]);
+
+        assert_eq!(body.bytes.len(), MAX_SAVED_BODY_SIZE);
+        assert_eq!(body.bytes[MAX_SAVED_BODY_SIZE - 1], 2);
+        assert_eq!(body.size, MAX_SAVED_BODY_SIZE + 25);
+    }
+
+    #[test]
+    fn test_capture_buffered_body() {
+        let mut request = Request::new(Method::POST, "https://example.com/a?b=c".parse().unwrap());
+        request.headers_mut().insert("x-trace", "1".parse().unwrap());
+        *request.body_mut() = Some(Body::from("{}"));
+
+        let sent = SentRequest::capture(&mut request);
+        assert_eq!(sent.method, "POST");
+        assert_eq!(sent.url, "https://example.com/a?b=c");
+        assert_eq!(sent.headers[0].name, "x-trace");
+        assert_eq!(sent.body.unwrap().lock().unwrap().bytes, b"{}");
+
+        // The request can still be sent, and cloned for redirects
+        assert_eq!(request.body().and_then(|b| b.as_bytes()), Some("{}".as_bytes()));
+        assert!(request.try_clone().is_some());
+    }
+
+    #[test]
+    fn test_capture_without_body() {
+        let mut request = Request::new(Method::GET, "https://example.com".parse().unwrap());
+        assert!(SentRequest::capture(&mut request).body.is_none());
+    }
+}
@@ -0,0 +1,278 @@
+use jsonschema::JSONSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value as JsonValue};
+
+use super::expression::{call_function, compare, Expression};
+
+/// A single check configured on an `assertion` node
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Assertion {
+    /// One of `status`, `header`, `json_schema`, `json_path`, `response_time` or `expression`
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// Header (or gRPC metadata) name for `header` assertions
+    pub name: Option<String>,
+    /// Path (eg. `$.body.items[0].id`) for `json_path`, or the full expression for `expression`
+    pub path: Option<String>,
+    /// Schema for `json_schema` assertions
+    pub schema: Option<JsonValue>,
+    pub operator: Option<String>,
+    pub expected: JsonValue,
+}
+
+/// Outcome of a single assertion, as stored in the node execution result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssertionOutcome {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub target: String,
+    pub operator: String,
+    pub passed: bool,
+    pub actual: JsonValue,
+    pub expected: JsonValue,
+    pub message: Option<String>,
+}
+
+/// Run every assertion against the output of a previous node
+pub fn run_assertions(assertions: &[Assertion], source: &JsonValue, elapsed: i32) -> Vec<AssertionOutcome> {
+    assertions.iter().map(|a| check(a, source, elapsed)).collect()
+}
+
+/// Build the node output for a set of outcomes
+pub fn summarize(source_node_id: &str, outcomes: &[AssertionOutcome]) -> JsonValue {
+    let passed = outcomes.iter().filter(|o| o.passed).count();
+    json!({
+        "sourceNodeId": source_node_id,
+        "total": outcomes.len(),
+        "passed": passed,
+        "failed": outcomes.len() - passed,
+        "results": outcomes,
+    })
+}
+
+fn check(assertion: &Assertion, source: &JsonValue, elapsed: i32) -> AssertionOutcome {
+    let default_operator = |default: &str| -> String {
+        match &assertion.operator {
+            Some(op) if !op.is_empty() => op.clone(),
+            _ if assertion.expected.is_null() => "exists".to_string(),
+            _ => default.to_string(),
+        }
+    };
+
+    let (target, operator, actual) = match assertion.kind.as_str() {
+        "status" => ("status".to_string(), default_operator("equals"), field(source, "status")),
+        "header" => {
+            let name = assertion.name.clone().unwrap_or_default();
+            let actual = find_header(source, &name);
+            (name, default_operator("equals"), actual)
+        }
+        "response_time" => {
+            let actual = match field(source, "elapsed") {
+                JsonValue::Null => json!(elapsed),
+                v => v,
+            };
+            ("elapsed".to_string(), default_operator("less_than_or_equal"), actual)
+        }
+        "json_path" => {
+            let path = assertion.path.clone().unwrap_or_default();
+            match Expression::parse(&path).and_then(|e| e.evaluate(source)) {
+                Ok(actual) => (path, default_operator("equals"), actual),
+                Err(e) => return failure(assertion, &path, "equals", format!("Invalid path: {}", e)),
+            }
+        }
+        "json_schema" => return check_schema(assertion, source),
+        "expression" => {
+            let expr = assertion.path.clone().unwrap_or_default();
+            return match Expression::parse(&expr).and_then(|e| e.evaluate_bool(source)) {
+                Ok(passed) => AssertionOutcome {
+                    kind: assertion.kind.clone(),
+                    target: expr,
+                    operator: "truthy".to_string(),
+                    passed,
+                    actual: json!(passed),
+                    expected: json!(true),
+                    message: None,
+                },
+                Err(e) => failure(assertion, &expr, "truthy", e),
+            };
+        }
+        kind => return failure(assertion, "", "", format!("Unknown assertion type '{}'", kind)),
+    };
+
+    match apply_operator(&operator, &actual, &assertion.expected) {
+        Ok(passed) => AssertionOutcome {
+            kind: assertion.kind.clone(),
+            message: (!passed).then(|| {
+                format!("Expected {} {} {}, got {}", target, operator, assertion.expected, actual)
+            }),
+            target,
+            operator,
+            passed,
+            actual,
+            expected: assertion.expected.clone(),
+        },
+        Err(e) => AssertionOutcome {
+            actual,
+            ..failure(assertion, &target, &operator, e)
+        },
+    }
+}
+
+fn check_schema(assertion: &Assertion, source: &JsonValue) -> AssertionOutcome {
+    // gRPC nodes expose their response as `message` rather than `body`
+    let target = if source.get("body").is_some() { "body" } else { "message" };
+    let actual = field(source, target);
+    let schema = assertion.schema.clone().unwrap_or(assertion.expected.clone());
+
+    let compiled = match JSONSchema::compile(&schema) {
+        Ok(s) => s,
+        Err(e) => return failure(assertion, target, "matches_schema", format!("Invalid schema: {}", e)),
+    };
+
+    let errors = match compiled.validate(&actual) {
+        Ok(()) => Vec::new(),
+        Err(errors) => errors
+            .map(|e| {
+                let path = e.instance_path.to_string();
+                if path.is_empty() { e.to_string() } else { format!("{}: {}", path, e) }
+            })
+            .collect(),
+    };
+
+    AssertionOutcome {
+        kind: assertion.kind.clone(),
+        target: target.to_string(),
+        operator: "matches_schema".to_string(),
+        passed: errors.is_empty(),
+        actual: JsonValue::Null,
+        expected: schema.clone(),
+        message: (!errors.is_empty()).then(|| errors.join("; ")),
+    }
+}
+
+fn apply_operator(operator: &str, actual: &JsonValue, expected: &JsonValue) -> Result<bool, String> {
+    let contains = || call_function("contains", &[actual.clone(), expected.clone()]);
+    Ok(match operator {
+        "equals" => compare("==", actual, expected)?,
+        "not_equals" => compare("!=", actual, expected)?,
+        "less_than" => compare("<", actual, expected)?,
+        "less_than_or_equal" => compare("<=", actual, expected)?,
+        "greater_than" => compare(">", actual, expected)?,
+        "greater_than_or_equal" => compare(">=", actual, expected)?,
+        "contains" => contains()? == JsonValue::Bool(true),
+        "not_contains" => contains()? == JsonValue::Bool(false),
+        "matches" => {
+            call_function("matches", &[actual.clone(), expected.clone()])? == JsonValue::Bool(true)
+        }
+        "exists" => !actual.is_null(),
+        "not_exists" => actual.is_null(),
+        op => return Err(format!("Unknown operator '{}'", op)),
+    })
+}
+
+fn field(source: &JsonValue, name: &str) -> JsonValue {
+    source.get(name).cloned().unwrap_or(JsonValue::Null)
+}
+
+/// Look up a header (HTTP) or metadata entry (gRPC) case-insensitively
+pub(super) fn find_header(source: &JsonValue, name: &str) -> JsonValue {
+    ["headers", "metadata"]
+        .iter()
+        .filter_map(|k| source.get(k).and_then(|v| v.as_object()))
+        .flat_map(|m| m.iter())
+        .find(|(k, _)| k.eq_ignore_ascii_case(name))
+        .map(|(_, v)| v.clone())
+        .unwrap_or(JsonValue::Null)
+}
+
+fn failure(assertion: &Assertion, target: &str, operator: &str, message: String) -> AssertionOutcome {
+    AssertionOutcome {
+        kind: assertion.kind.clone(),
+        target: target.to_string(),
+        operator: operator.to_string(),
+        passed: false,
+        actual: JsonValue::Null,
+        expected: assertion.expected.clone(),
+        message: Some(message),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source() -> JsonValue {
+        json!({
+            "status": 201,
+            "headers": { "content-type": "application/json" },
+            "body": { "id": "abc", "tags": ["a", "b"] },
+            "elapsed": 120,
+        })
+    }
+
+    fn assertions(value: JsonValue) -> Vec<Assertion> {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_passing_assertions() {
+        let outcomes = run_assertions(
+            &assertions(json!([
+                { "type": "status", "expected": 201 },
+                { "type": "status", "operator": "less_than", "expected": 300 },
+                { "type": "header", "name": "Content-Type", "operator": "contains", "expected": "json" },
+                { "type": "json_path", "path": "$.body.tags.length", "expected": 2 },
+                { "type": "json_path", "path": "body.id" },
+                { "type": "response_time", "expected": 500 },
+                { "type": "expression", "path": "status == 201 && contains(body.tags, 'b')" },
+                {
+                    "type": "json_schema",
+                    "schema": { "type": "object", "required": ["id"], "properties": { "id": { "type": "string" } } }
+                },
+            ])),
+            &source(),
+            0,
+        );
+
+        let failed: Vec<_> = outcomes.iter().filter(|o| !o.passed).collect();
+        assert!(failed.is_empty(), "Unexpected failures: {:?}", failed);
+    }
+
+    #[test]
+    fn test_failing_assertions() {
+        let outcomes = run_assertions(
+            &assertions(json!([
+                { "type": "status", "expected": 200 },
+                { "type": "header", "name": "x-missing" },
+                { "type": "response_time", "expected": 100 },
+                { "type": "json_schema", "schema": { "type": "object", "required": ["name"] } },
+                { "type": "json_path", "path": "body.id ==", "expected": 1 },
+                { "type": "unknown" },
+            ])),
+            &source(),
+            0,
+        );
+
+        assert!(outcomes.iter().all(|o| !o.passed));
+        assert!(outcomes.iter().all(|o| o.message.is_some()));
+        assert_eq!(outcomes[0].actual, json!(201));
+    }
+
+    #[test]
+    fn test_summarize() {
+        let outcomes = run_assertions(
+            &assertions(json!([
+                { "type": "status", "expected": 201 },
+                { "type": "status", "expected": 500 },
+            ])),
+            &source(),
+            0,
+        );
+        let summary = summarize("node_1", &outcomes);
+        assert_eq!(summary["passed"], json!(1));
+        assert_eq!(summary["failed"], json!(1));
+        assert_eq!(summary["results"].as_array().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock, Weak};
+use tokio::sync::watch;
+
+/// Cancellation signal of a run, shared by everything the run does
+///
+/// Cancelling a token also cancels every token derived from it with [`CancellationToken::child`],
+/// so stopping a run stops its sub-workflows, dataset rows and load test iterations as well.
+#[derive(Debug, Clone)]
+pub struct CancellationToken {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    tx: watch::Sender<bool>,
+    children: Mutex<Vec<Weak<Inner>>>,
+}
+
+impl Default for CancellationToken {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        let (tx, _) = watch::channel(false);
+        Self {
+            inner: Arc::new(Inner {
+                tx,
+                children: Mutex::new(Vec::new()),
+            }),
+        }
+    }
+
+    /// Token that is cancelled along with this one, but can also be cancelled on its own
+    pub fn child(&self) -> Self {
+        let child = Self::new();
+        {
+            let mut children = self.inner.children.lock().unwrap();
+            children.retain(|c| c.strong_count() > 0);
+            children.push(Arc::downgrade(&child.inner));
+        }
+        // Checked after registering the child, so a concurrent cancel can't miss it
+        if self.is_cancelled() {
+            child.cancel();
+        }
+        child
+    }
+
+    pub fn cancel(&self) {
+        if self.inner.tx.send_replace(true) {
+            return;
+        }
+        let children: Vec<Arc<Inner>> =
+            self.inner.children.lock().unwrap().iter().filter_map(Weak::upgrade).collect();
+        for inner in children {
+            CancellationToken { inner }.cancel();
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        *self.inner.tx.borrow()
+    }
+
+    /// Receiver that changes to `true` once the token is cancelled, as taken by
+    /// `send_http_request`
+    pub fn subscribe(&self) -> watch::Receiver<bool> {
+        self.inner.tx.subscribe()
+    }
+
+    /// Wait until the token is cancelled
+    pub async fn cancelled(&self) {
+        let mut rx = self.subscribe();
+        // The sender lives as long as `self`, so this only returns once cancelled
+        let _ = rx.wait_for(|cancelled| *cancelled).await;
+    }
+}
+
+/// Tokens of the runs in progress by ID. Commands each create their own orchestrator, so the
+/// tokens have to outlive it for a later command to find them.
+fn registry() -> &'static Mutex<HashMap<String, Weak<Inner>>> {
+    static TOKENS: OnceLock<Mutex<HashMap<String, Weak<Inner>>>> = OnceLock::new();
+    TOKENS.get_or_init(Default::default)
+}
+
+/// Make `token` cancellable by `id` for as long as the run holds on to it
+pub fn register(id: &str, token: &CancellationToken) {
+    let mut tokens = registry().lock().unwrap();
+    tokens.retain(|_, t| t.strong_count() > 0);
+    tokens.insert(id.to_string(), Arc::downgrade(&token.inner));
+}
+
+/// Cancel the run registered as `id`, returning whether it was still in progress
+pub fn cancel(id: &str) -> bool {
+    let inner = registry().lock().unwrap().get(id).and_then(Weak::upgrade);
+    match inner {
+        Some(inner) => {
+            CancellationToken { inner }.cancel();
+            true
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cancel_children() {
+        let parent = CancellationToken::new();
+        let child = parent.child();
+        let grandchild = child.child();
+        let rx = grandchild.subscribe();
+
+        // Cancelling a child leaves the parent running
+        let sibling = parent.child();
+        sibling.cancel();
+        assert!(sibling.is_cancelled());
+        assert!(!parent.is_cancelled());
+
+        parent.cancel();
+        assert!(child.is_cancelled());
+        assert!(grandchild.is_cancelled());
+        assert!(*rx.borrow());
+
+        // Children of a cancelled token start out cancelled
+        assert!(parent.child().is_cancelled());
+    }
+
+    #[test]
+    fn test_cancel_by_id() {
+        let token = CancellationToken::new();
+        register("we_test_cancel", &token);
+        assert!(cancel("we_test_cancel"));
+        assert!(token.is_cancelled());
+
+        // Tokens that are no longer used can't be cancelled anymore
+        register("we_test_dropped", &CancellationToken::new());
+        assert!(!cancel("we_test_dropped"));
+        assert!(!cancel("we_test_missing"));
+    }
+}
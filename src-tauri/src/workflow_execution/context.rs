@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use std::collections::HashMap;
+use yaak_models::models::WorkflowNodeExecution;
+
+use super::cancellation::CancellationToken;
 
 /// Execution context that flows through workflow execution
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,6 +20,19 @@ pub struct ExecutionContext {
 
     /// Stack of loop contexts (for nested loops)
     pub loop_stack: Vec<LoopContext>,
+
+    /// Output of the start trigger, eg. the fire time for scheduled runs
+    #[serde(default)]
+    pub trigger_payload: Option<JsonValue>,
+
+    /// Completed node executions of an earlier run, keyed by node ID and loop iteration. When
+    /// resuming, these nodes are not run again and their stored results are used instead.
+    #[serde(skip)]
+    pub reused: HashMap<(String, Option<i32>), WorkflowNodeExecution>,
+
+    /// Cancelled when the run is, shared with the parallel branches and sub-workflows it starts
+    #[serde(skip)]
+    pub cancellation: CancellationToken,
 }
 
 impl ExecutionContext {
@@ -28,6 +44,9 @@ impl ExecutionContext {
             variables: HashMap::new(),
             node_results: HashMap::new(),
             loop_stack: Vec::new(),
+            trigger_payload: None,
+            reused: HashMap::new(),
+            cancellation: CancellationToken::new(),
         }
     }
 }
@@ -37,6 +56,9 @@ impl ExecutionContext {
 pub struct NodeResult {
     pub node_id: String,
 
+    /// Name of the node, so later nodes can reference its output by name
+    pub node_name: String,
+
     /// JSON output from the node execution
     pub output: JsonValue,
 
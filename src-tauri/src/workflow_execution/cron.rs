@@ -0,0 +1,174 @@
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+
+/// How far ahead to look for a match before giving up (eg. `0 0 30 2 *` never fires)
+const MAX_LOOKAHEAD_DAYS: i64 = 366 * 5;
+
+const MONTH_NAMES: &[&str] =
+    &["JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC"];
+const WEEKDAY_NAMES: &[&str] = &["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+/// Standard 5-field cron expression: minute, hour, day of month, month and day of week.
+///
+/// Supports `*`, lists, ranges, steps, month/weekday names and the `@hourly`-style macros.
+/// Like Vixie cron, when both day fields are restricted a day matches if either one does.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    days_of_month_any: bool,
+    days_of_week_any: bool,
+}
+
+impl CronSchedule {
+    pub fn parse(expr: &str) -> Result<Self, String> {
+        let expr = match expr.trim() {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            e => e,
+        };
+
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        let &[minute, hour, dom, month, dow] = &fields[..] else {
+            return Err(format!("Expected 5 fields, got {}", fields.len()));
+        };
+
+        let days_of_week = parse_field(dow, 0, 7, WEEKDAY_NAMES).map_err(|e| format!("day of week: {e}"))?;
+        Ok(Self {
+            minutes: parse_field(minute, 0, 59, &[]).map_err(|e| format!("minute: {e}"))?,
+            hours: parse_field(hour, 0, 23, &[]).map_err(|e| format!("hour: {e}"))?,
+            days_of_month: parse_field(dom, 1, 31, &[]).map_err(|e| format!("day of month: {e}"))?,
+            months: parse_field(month, 1, 12, MONTH_NAMES).map_err(|e| format!("month: {e}"))?,
+            // Both 0 and 7 mean Sunday
+            days_of_week: (days_of_week | (days_of_week >> 7)) & 0x7f,
+            days_of_month_any: dom.starts_with('*'),
+            days_of_week_any: dow.starts_with('*'),
+        })
+    }
+
+    /// First matching minute strictly after `after`
+    pub fn next_after(&self, after: NaiveDateTime) -> Option<NaiveDateTime> {
+        let after = after.with_second(0)?.with_nanosecond(0)?;
+        let limit = after + Duration::days(MAX_LOOKAHEAD_DAYS);
+        let mut t = after + Duration::minutes(1);
+
+        while t < limit {
+            if !has(self.months, t.month()) {
+                let (year, month) = if t.month() == 12 { (t.year() + 1, 1) } else { (t.year(), t.month() + 1) };
+                t = NaiveDate::from_ymd_opt(year, month, 1)?.and_time(NaiveTime::MIN);
+            } else if !self.day_matches(t.date()) {
+                t = t.date().succ_opt()?.and_time(NaiveTime::MIN);
+            } else if !has(self.hours, t.hour()) {
+                t = t.with_minute(0)? + Duration::hours(1);
+            } else if !has(self.minutes, t.minute()) {
+                t += Duration::minutes(1);
+            } else {
+                return Some(t);
+            }
+        }
+
+        None
+    }
+
+    fn day_matches(&self, date: NaiveDate) -> bool {
+        let dom = has(self.days_of_month, date.day());
+        let dow = has(self.days_of_week, date.weekday().num_days_from_sunday());
+        if self.days_of_month_any || self.days_of_week_any { dom && dow } else { dom || dow }
+    }
+}
+
+fn has(mask: u64, value: u32) -> bool {
+    mask & (1 << value) != 0
+}
+
+/// Parse a single field into a bitmask of allowed values
+fn parse_field(field: &str, min: u32, max: u32, names: &[&str]) -> Result<u64, String> {
+    let parse_value = |s: &str| -> Result<u32, String> {
+        let value = match names.iter().position(|n| n.eq_ignore_ascii_case(s)) {
+            // Names are indexed from the field's lowest value (JAN = 1, SUN = 0)
+            Some(i) => i as u32 + min,
+            None => s.parse::<u32>().map_err(|_| format!("invalid value '{s}'"))?,
+        };
+        if value < min || value > max {
+            return Err(format!("{value} is out of range {min}-{max}"));
+        }
+        Ok(value)
+    };
+
+    let mut mask = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => match step.parse::<u32>() {
+                Ok(step) if step > 0 => (range, Some(step)),
+                _ => return Err(format!("invalid step '{step}'")),
+            },
+            None => (part, None),
+        };
+
+        let (start, end) = match range.split_once('-') {
+            _ if range == "*" => (min, max),
+            Some((start, end)) => (parse_value(start)?, parse_value(end)?),
+            // `5/15` means every 15 starting at 5
+            None if step.is_some() => (parse_value(range)?, max),
+            None => {
+                let value = parse_value(range)?;
+                (value, value)
+            }
+        };
+        if start > end {
+            return Err(format!("invalid range '{range}'"));
+        }
+
+        for value in (start..=end).step_by(step.unwrap_or(1) as usize) {
+            mask |= 1 << value;
+        }
+    }
+
+    Ok(mask)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn next(expr: &str, after: &str) -> Option<NaiveDateTime> {
+        CronSchedule::parse(expr).unwrap().next_after(at(after))
+    }
+
+    #[test]
+    fn test_next_after() {
+        assert_eq!(next("* * * * *", "2025-01-01 10:00"), Some(at("2025-01-01 10:01")));
+        assert_eq!(next("@hourly", "2025-01-01 10:00"), Some(at("2025-01-01 11:00")));
+        assert_eq!(next("*/15 9-17 * * MON-FRI", "2025-01-03 17:50"), Some(at("2025-01-06 09:00")));
+        assert_eq!(next("30 2 1 * *", "2025-01-31 12:00"), Some(at("2025-02-01 02:30")));
+        assert_eq!(next("0 0 29 FEB *", "2025-03-01 00:00"), Some(at("2028-02-29 00:00")));
+        assert_eq!(next("0 12 * * 7", "2025-01-01 00:00"), Some(at("2025-01-05 12:00")));
+        assert_eq!(next("0 0 30 2 *", "2025-01-01 00:00"), None);
+    }
+
+    #[test]
+    fn test_day_of_month_or_day_of_week() {
+        // Restricting both day fields matches either one: the 1st, or any Monday
+        let schedule = CronSchedule::parse("0 0 1 * 1").unwrap();
+        assert_eq!(schedule.next_after(at("2025-01-01 00:00")), Some(at("2025-01-06 00:00")));
+        assert_eq!(schedule.next_after(at("2025-01-27 00:00")), Some(at("2025-02-01 00:00")));
+    }
+
+    #[test]
+    fn test_invalid_expressions() {
+        assert!(CronSchedule::parse("* * * *").is_err());
+        assert!(CronSchedule::parse("60 * * * *").is_err());
+        assert!(CronSchedule::parse("*/0 * * * *").is_err());
+        assert!(CronSchedule::parse("5-1 * * * *").is_err());
+        assert!(CronSchedule::parse("* * * FOO *").is_err());
+    }
+}
@@ -0,0 +1,162 @@
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+use std::path::Path;
+use yaak_models::error::{Error, Result};
+
+/// Fields of a single dataset row, exposed to the run as template variables
+pub type DatasetRow = HashMap<String, String>;
+
+/// Load the rows of a CSV or JSON dataset. CSV files need a header row, and JSON files an
+/// array of objects. Files ending in `.json` are read as JSON, everything else as CSV.
+pub fn load_dataset(path: &Path) -> Result<Vec<DatasetRow>> {
+    let contents = std::fs::read_to_string(path).map_err(|e| {
+        Error::GenericError(format!("Failed to read dataset {}: {}", path.display(), e))
+    })?;
+
+    let is_json = path.extension().is_some_and(|e| e.eq_ignore_ascii_case("json"));
+    let rows = if is_json { parse_json(&contents) } else { parse_csv(&contents) };
+    rows.map_err(|e| Error::GenericError(format!("Invalid dataset {}: {}", path.display(), e)))
+}
+
+fn parse_json(contents: &str) -> std::result::Result<Vec<DatasetRow>, String> {
+    let value: JsonValue = serde_json::from_str(contents).map_err(|e| e.to_string())?;
+    let items = value.as_array().ok_or("Expected an array of objects")?;
+
+    items
+        .iter()
+        .enumerate()
+        .map(|(i, item)| {
+            let object = item.as_object().ok_or_else(|| format!("Row {} is not an object", i + 1))?;
+            Ok(object
+                .iter()
+                .map(|(k, v)| {
+                    let v = match v {
+                        JsonValue::Null => String::new(),
+                        JsonValue::String(s) => s.clone(),
+                        v => v.to_string(),
+                    };
+                    (k.clone(), v)
+                })
+                .collect())
+        })
+        .collect()
+}
+
+fn parse_csv(contents: &str) -> std::result::Result<Vec<DatasetRow>, String> {
+    let mut records = parse_csv_records(contents.trim_start_matches('\u{feff}'))?.into_iter();
+    let Some((_, header)) = records.next() else {
+        return Ok(Vec::new());
+    };
+
+    records
+        .filter(|(_, r)| !(r.len() == 1 && r[0].is_empty()))
+        .map(|(line, record)| {
+            if record.len() != header.len() {
+                return Err(format!(
+                    "Line {} has {} fields, expected {}",
+                    line,
+                    record.len(),
+                    header.len()
+                ));
+            }
+            Ok(header.iter().cloned().zip(record).collect())
+        })
+        .collect()
+}
+
+/// Split CSV text into records, each with the 1-based line of the file it starts on. Fields may
+/// be quoted to contain commas, newlines or doubled quotes (`""`).
+fn parse_csv_records(contents: &str) -> std::result::Result<Vec<(usize, Vec<String>)>, String> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut record_line = 1;
+    let mut quote_line = 1;
+    let mut chars = contents.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' if in_quotes => in_quotes = false,
+            '"' if field.is_empty() => {
+                in_quotes = true;
+                quote_line = line;
+            }
+            ',' if !in_quotes => record.push(std::mem::take(&mut field)),
+            '\r' if !in_quotes && chars.peek() == Some(&'\n') => {}
+            '\n' if !in_quotes => {
+                record.push(std::mem::take(&mut field));
+                records.push((record_line, std::mem::take(&mut record)));
+                line += 1;
+                record_line = line;
+            }
+            c => {
+                // Newlines in quoted fields still start a new line of the file
+                if c == '\n' {
+                    line += 1;
+                }
+                field.push(c);
+            }
+        }
+    }
+
+    if in_quotes {
+        return Err(format!("Unterminated quoted field on line {}", quote_line));
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push((record_line, record));
+    }
+
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(pairs: &[(&str, &str)]) -> DatasetRow {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_parse_csv() {
+        let csv = "email,name,note\r\na@yaak.app,Ann,plain\nb@yaak.app,\"Doe, Bob\",\"says \"\"hi\"\"\nthen leaves\"\n\n";
+        assert_eq!(
+            parse_csv(csv).unwrap(),
+            vec![
+                row(&[("email", "a@yaak.app"), ("name", "Ann"), ("note", "plain")]),
+                row(&[("email", "b@yaak.app"), ("name", "Doe, Bob"), ("note", "says \"hi\"\nthen leaves")]),
+            ]
+        );
+
+        assert_eq!(parse_csv("email,name").unwrap(), Vec::<DatasetRow>::new());
+        assert_eq!(parse_csv("").unwrap(), Vec::<DatasetRow>::new());
+        assert!(parse_csv("a\n\"open").is_err());
+
+        // Errors point at the line in the file, counting the header and quoted newlines
+        let err = parse_csv("a,b\n1,2,3").unwrap_err();
+        assert_eq!(err, "Line 2 has 3 fields, expected 2");
+        let err = parse_csv("a,b\n\"multi\nline\",2\n\n1,2,3").unwrap_err();
+        assert_eq!(err, "Line 5 has 3 fields, expected 2");
+        let err = parse_csv("a,b\n1,2\n\"open,3").unwrap_err();
+        assert_eq!(err, "Unterminated quoted field on line 3");
+    }
+
+    #[test]
+    fn test_parse_json() {
+        let json = r#"[{ "email": "a@yaak.app", "age": 30, "admin": true, "team": null }]"#;
+        assert_eq!(
+            parse_json(json).unwrap(),
+            vec![row(&[("email", "a@yaak.app"), ("age", "30"), ("admin", "true"), ("team", "")])]
+        );
+
+        assert!(parse_json(r#"{ "email": "a@yaak.app" }"#).is_err());
+        assert!(parse_json(r#"["a@yaak.app"]"#).is_err());
+    }
+}
@@ -0,0 +1,640 @@
+use regex::Regex;
+use serde_json::{json, Value as JsonValue};
+use yaak_templates::{Parser as TemplateParser, Token as TemplateToken};
+
+/// A small, side-effect free expression language used by conditional nodes.
+///
+/// Supported syntax:
+///   - Literals: `200`, `1.5`, `'text'`, `"text"`, `true`, `false`, `null`
+///   - Paths: `status`, `body.items[0].id`, `headers['content-type']`, `$.body`
+///   - `.length` on arrays, strings and objects
+///   - Comparisons: `==`, `!=`, `<`, `<=`, `>`, `>=`
+///   - Boolean logic: `&&`, `||`, `!` and parentheses
+///   - Functions: `contains`, `startsWith`, `endsWith`, `matches`, `lower`, `upper`, `trim`,
+///     `len`, `exists`
+///
+/// Missing fields evaluate to `null` rather than failing, so `body.error == null` works.
+#[derive(Debug, Clone)]
+pub struct Expression {
+    ast: Expr,
+}
+
+impl Expression {
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let ast = parser.parse_or()?;
+        if let Some(t) = parser.peek() {
+            return Err(format!("Unexpected token {:?}", t));
+        }
+        Ok(Self { ast })
+    }
+
+    /// Evaluate against a scope. Identifiers are looked up as keys of the scope object and
+    /// `$` refers to the scope itself.
+    pub fn evaluate(&self, scope: &JsonValue) -> Result<JsonValue, String> {
+        eval(&self.ast, scope)
+    }
+
+    pub fn evaluate_bool(&self, scope: &JsonValue) -> Result<bool, String> {
+        Ok(is_truthy(&self.evaluate(scope)?))
+    }
+}
+
+/// A conditional node's condition, with its template tags taken out of the expression.
+///
+/// Every tag is replaced by a variable that its rendered value gets bound to, so a rendered value
+/// is only ever data and can't change the expression around it.
+#[derive(Debug, Clone)]
+pub struct Condition {
+    /// The expression, with a variable in place of every template tag
+    pub source: String,
+    /// The variables of the expression and the template tags they stand for
+    pub tags: Vec<(String, String)>,
+}
+
+impl Condition {
+    pub fn parse(condition: &str) -> Result<Self, String> {
+        let condition = upgrade_legacy_tags(condition);
+        let tokens = TemplateParser::new(&condition).parse().map_err(|e| e.to_string())?;
+        let mut source = String::new();
+        let mut tags = Vec::new();
+        for token in tokens.tokens {
+            match token {
+                TemplateToken::Raw { text } => source.push_str(&text),
+                TemplateToken::Tag { .. } => {
+                    let name = format!("__tag{}", tags.len());
+                    source.push_str(&format!(" {} ", name));
+                    tags.push((name, token.to_string()));
+                }
+                TemplateToken::Eof => {}
+            }
+        }
+        Ok(Self { source, tags })
+    }
+
+    /// Conditions that are nothing but a template tag aren't expressions. They are checked for
+    /// truthiness of the rendered text, like before conditions were expressions.
+    pub fn is_lone_tag(&self) -> bool {
+        matches!(self.tags.as_slice(), [(name, _)] if self.source.trim() == name)
+    }
+}
+
+/// Conditions saved before they were expressions use `{{ ... }}`, eg. the old default
+/// `{{step[0].response.status}} == 200`. Step references become the `step` path of the scope, and
+/// anything else becomes the template tag it stands for, so it's rendered and bound like any other.
+fn upgrade_legacy_tags(condition: &str) -> String {
+    let re = Regex::new(r"\{\{\s*([^{}]+?)\s*\}\}").expect("valid regex");
+    re.replace_all(condition, |caps: &regex::Captures| {
+        let path = &caps[1];
+        if path.starts_with("step[") { format!(" {} ", path) } else { format!("${{[ {} ]}}", path) }
+    })
+    .into_owned()
+}
+
+/// The value a rendered template tag is bound as. Valid JSON is bound as that JSON, so a tag can
+/// render to a number or an array, and anything else is bound as a string.
+pub fn bound_value(rendered: &str) -> JsonValue {
+    serde_json::from_str(rendered).unwrap_or_else(|_| JsonValue::String(rendered.to_string()))
+}
+
+/// Truthiness of rendered text, as conditions were checked before they were expressions
+pub fn is_truthy_text(value: &str) -> bool {
+    match value.trim().to_lowercase().as_str() {
+        "true" | "1" | "yes" => true,
+        "false" | "0" | "no" | "" => false,
+        // Non-zero numbers and any other text are true
+        v => !matches!(v.parse::<i64>(), Ok(0)),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Str(String),
+    Ident(String),
+    Dollar,
+    Dot,
+    Comma,
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Op(&'static str),
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Literal(JsonValue),
+    Root,
+    Var(String),
+    Member(Box<Expr>, String),
+    Index(Box<Expr>, Box<Expr>),
+    Call(String, Vec<Expr>),
+    Not(Box<Expr>),
+    Neg(Box<Expr>),
+    Binary(&'static str, Box<Expr>, Box<Expr>),
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        let two: Option<&'static str> = match (c, next) {
+            ('=', Some('=')) => Some("=="),
+            ('!', Some('=')) => Some("!="),
+            ('<', Some('=')) => Some("<="),
+            ('>', Some('=')) => Some(">="),
+            ('&', Some('&')) => Some("&&"),
+            ('|', Some('|')) => Some("||"),
+            _ => None,
+        };
+        if let Some(op) = two {
+            tokens.push(Token::Op(op));
+            i += 2;
+            continue;
+        }
+
+        match c {
+            '<' => tokens.push(Token::Op("<")),
+            '>' => tokens.push(Token::Op(">")),
+            '!' => tokens.push(Token::Op("!")),
+            '-' => tokens.push(Token::Op("-")),
+            '$' => tokens.push(Token::Dollar),
+            '.' => tokens.push(Token::Dot),
+            ',' => tokens.push(Token::Comma),
+            '(' => tokens.push(Token::LParen),
+            ')' => tokens.push(Token::RParen),
+            '[' => tokens.push(Token::LBracket),
+            ']' => tokens.push(Token::RBracket),
+            '\'' | '"' => {
+                let quote = c;
+                let mut text = String::new();
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        None => return Err("Unterminated string".to_string()),
+                        Some('\\') => {
+                            match chars.get(i + 1) {
+                                Some(escaped) => text.push(*escaped),
+                                None => return Err("Unterminated string".to_string()),
+                            }
+                            i += 2;
+                        }
+                        Some(ch) if *ch == quote => break,
+                        Some(ch) => {
+                            text.push(*ch);
+                            i += 1;
+                        }
+                    }
+                }
+                tokens.push(Token::Str(text));
+            }
+            _ if c.is_ascii_digit() => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                    // Stop at a dot that isn't followed by a digit (eg. member access)
+                    if chars[i] == '.' && !chars.get(i + 1).is_some_and(|c| c.is_ascii_digit()) {
+                        break;
+                    }
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().collect();
+                let n = text.parse::<f64>().map_err(|_| format!("Invalid number {}", text))?;
+                tokens.push(Token::Number(n));
+                continue;
+            }
+            _ if c.is_alphabetic() || c == '_' => {
+                let start = i;
+                while i < chars.len()
+                    && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '-')
+                {
+                    i += 1;
+                }
+                tokens.push(Token::Ident(chars[start..i].iter().collect()));
+                continue;
+            }
+            _ => return Err(format!("Unexpected character '{}'", c)),
+        }
+        i += 1;
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let t = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        t
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), String> {
+        match self.next() {
+            Some(t) if t == expected => Ok(()),
+            Some(t) => Err(format!("Expected {:?} but found {:?}", expected, t)),
+            None => Err(format!("Expected {:?} but reached end of expression", expected)),
+        }
+    }
+
+    fn eat_op(&mut self, ops: &[&'static str]) -> Option<&'static str> {
+        match self.peek() {
+            Some(Token::Op(op)) if ops.contains(op) => {
+                let op = *op;
+                self.pos += 1;
+                Some(op)
+            }
+            _ => None,
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.parse_and()?;
+        while let Some(op) = self.eat_op(&["||"]) {
+            let rhs = self.parse_and()?;
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.parse_comparison()?;
+        while let Some(op) = self.eat_op(&["&&"]) {
+            let rhs = self.parse_comparison()?;
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_comparison(&mut self) -> Result<Expr, String> {
+        let lhs = self.parse_unary()?;
+        if let Some(op) = self.eat_op(&["==", "!=", "<", "<=", ">", ">="]) {
+            let rhs = self.parse_unary()?;
+            return Ok(Expr::Binary(op, Box::new(lhs), Box::new(rhs)));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, String> {
+        if self.eat_op(&["!"]).is_some() {
+            return Ok(Expr::Not(Box::new(self.parse_unary()?)));
+        }
+        if self.eat_op(&["-"]).is_some() {
+            return Ok(Expr::Neg(Box::new(self.parse_unary()?)));
+        }
+        self.parse_postfix()
+    }
+
+    fn parse_postfix(&mut self) -> Result<Expr, String> {
+        let mut expr = self.parse_primary()?;
+        loop {
+            match self.peek() {
+                Some(Token::Dot) => {
+                    self.pos += 1;
+                    match self.next() {
+                        Some(Token::Ident(name)) => expr = Expr::Member(Box::new(expr), name),
+                        // Allow `items.0` as a shorthand for `items[0]`
+                        Some(Token::Number(n)) => {
+                            expr = Expr::Index(Box::new(expr), Box::new(Expr::Literal(json!(n))))
+                        }
+                        t => return Err(format!("Expected field name after '.', found {:?}", t)),
+                    }
+                }
+                Some(Token::LBracket) => {
+                    self.pos += 1;
+                    let index = self.parse_or()?;
+                    self.expect(Token::RBracket)?;
+                    expr = Expr::Index(Box::new(expr), Box::new(index));
+                }
+                _ => return Ok(expr),
+            }
+        }
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, String> {
+        match self.next() {
+            Some(Token::Number(n)) => Ok(Expr::Literal(json!(n))),
+            Some(Token::Str(s)) => Ok(Expr::Literal(JsonValue::String(s))),
+            Some(Token::Dollar) => Ok(Expr::Root),
+            Some(Token::LParen) => {
+                let expr = self.parse_or()?;
+                self.expect(Token::RParen)?;
+                Ok(expr)
+            }
+            Some(Token::Ident(name)) => match name.as_str() {
+                "true" => Ok(Expr::Literal(JsonValue::Bool(true))),
+                "false" => Ok(Expr::Literal(JsonValue::Bool(false))),
+                "null" => Ok(Expr::Literal(JsonValue::Null)),
+                _ if self.peek() == Some(&Token::LParen) => {
+                    self.pos += 1;
+                    let mut args = Vec::new();
+                    if self.peek() != Some(&Token::RParen) {
+                        loop {
+                            args.push(self.parse_or()?);
+                            if self.peek() == Some(&Token::Comma) {
+                                self.pos += 1;
+                            } else {
+                                break;
+                            }
+                        }
+                    }
+                    self.expect(Token::RParen)?;
+                    check_function(&name, args.len())?;
+                    Ok(Expr::Call(name, args))
+                }
+                _ => Ok(Expr::Var(name)),
+            },
+            Some(t) => Err(format!("Unexpected token {:?}", t)),
+            None => Err("Unexpected end of expression".to_string()),
+        }
+    }
+}
+
+fn check_function(name: &str, arg_count: usize) -> Result<(), String> {
+    let expected = match name {
+        "contains" | "startsWith" | "endsWith" | "matches" => 2,
+        "lower" | "upper" | "trim" | "len" | "exists" => 1,
+        _ => return Err(format!("Unknown function '{}'", name)),
+    };
+    if arg_count != expected {
+        return Err(format!("{}() takes {} arguments but got {}", name, expected, arg_count));
+    }
+    Ok(())
+}
+
+fn eval(expr: &Expr, scope: &JsonValue) -> Result<JsonValue, String> {
+    Ok(match expr {
+        Expr::Literal(v) => v.clone(),
+        Expr::Root => scope.clone(),
+        Expr::Var(name) => scope.get(name).cloned().unwrap_or(JsonValue::Null),
+        Expr::Member(target, name) => {
+            let target = eval(target, scope)?;
+            match (&target, name.as_str()) {
+                (JsonValue::Object(o), key) if o.contains_key(key) => o[key].clone(),
+                (_, "length") => length(&target),
+                (JsonValue::Object(o), key) => o.get(key).cloned().unwrap_or(JsonValue::Null),
+                _ => JsonValue::Null,
+            }
+        }
+        Expr::Index(target, index) => {
+            let target = eval(target, scope)?;
+            match (&target, eval(index, scope)?) {
+                (JsonValue::Array(a), JsonValue::Number(n)) => {
+                    let i = n.as_f64().unwrap_or(-1.0) as i64;
+                    // Negative indexes count from the end
+                    let i = if i < 0 { a.len() as i64 + i } else { i };
+                    a.get(i as usize).cloned().unwrap_or(JsonValue::Null)
+                }
+                (JsonValue::Object(o), JsonValue::String(key)) => {
+                    o.get(&key).cloned().unwrap_or(JsonValue::Null)
+                }
+                _ => JsonValue::Null,
+            }
+        }
+        Expr::Not(inner) => JsonValue::Bool(!is_truthy(&eval(inner, scope)?)),
+        Expr::Neg(inner) => match as_number(&eval(inner, scope)?) {
+            Some(n) => json!(-n),
+            None => return Err("Cannot negate a non-numeric value".to_string()),
+        },
+        Expr::Binary("&&", lhs, rhs) => {
+            JsonValue::Bool(is_truthy(&eval(lhs, scope)?) && is_truthy(&eval(rhs, scope)?))
+        }
+        Expr::Binary("||", lhs, rhs) => {
+            JsonValue::Bool(is_truthy(&eval(lhs, scope)?) || is_truthy(&eval(rhs, scope)?))
+        }
+        Expr::Binary(op, lhs, rhs) => {
+            let lhs = eval(lhs, scope)?;
+            let rhs = eval(rhs, scope)?;
+            JsonValue::Bool(compare(op, &lhs, &rhs)?)
+        }
+        Expr::Call(name, args) => {
+            let args = args.iter().map(|a| eval(a, scope)).collect::<Result<Vec<_>, _>>()?;
+            call_function(name, &args)?
+        }
+    })
+}
+
+pub(super) fn compare(op: &str, lhs: &JsonValue, rhs: &JsonValue) -> Result<bool, String> {
+    match op {
+        "==" => return Ok(loose_eq(lhs, rhs)),
+        "!=" => return Ok(!loose_eq(lhs, rhs)),
+        _ => {}
+    }
+
+    let ordering = match (lhs, rhs) {
+        (JsonValue::String(a), JsonValue::String(b)) => a.cmp(b),
+        _ => match (as_number(lhs), as_number(rhs)) {
+            (Some(a), Some(b)) => a.partial_cmp(&b).ok_or("Cannot compare NaN")?,
+            _ => return Err(format!("Cannot compare {} {} {}", lhs, op, rhs)),
+        },
+    };
+
+    Ok(match op {
+        "<" => ordering.is_lt(),
+        "<=" => ordering.is_le(),
+        ">" => ordering.is_gt(),
+        ">=" => ordering.is_ge(),
+        _ => return Err(format!("Unknown operator {}", op)),
+    })
+}
+
+/// Equality that treats numeric strings and numbers as equal, since values often come from
+/// headers or rendered templates as strings.
+fn loose_eq(lhs: &JsonValue, rhs: &JsonValue) -> bool {
+    match (lhs, rhs) {
+        (JsonValue::Number(_), JsonValue::String(_)) | (JsonValue::String(_), JsonValue::Number(_)) => {
+            matches!((as_number(lhs), as_number(rhs)), (Some(a), Some(b)) if a == b)
+        }
+        (JsonValue::Number(a), JsonValue::Number(b)) => a.as_f64() == b.as_f64(),
+        _ => lhs == rhs,
+    }
+}
+
+pub(super) fn call_function(name: &str, args: &[JsonValue]) -> Result<JsonValue, String> {
+    let str_arg = |i: usize| -> String {
+        match &args[i] {
+            JsonValue::String(s) => s.clone(),
+            JsonValue::Null => String::new(),
+            v => v.to_string(),
+        }
+    };
+
+    Ok(match name {
+        "contains" => JsonValue::Bool(match &args[0] {
+            JsonValue::Array(a) => a.iter().any(|v| loose_eq(v, &args[1])),
+            JsonValue::Object(o) => o.contains_key(&str_arg(1)),
+            _ => str_arg(0).contains(&str_arg(1)),
+        }),
+        "startsWith" => JsonValue::Bool(str_arg(0).starts_with(&str_arg(1))),
+        "endsWith" => JsonValue::Bool(str_arg(0).ends_with(&str_arg(1))),
+        "matches" => {
+            let re = Regex::new(&str_arg(1)).map_err(|e| format!("Invalid regex: {}", e))?;
+            JsonValue::Bool(re.is_match(&str_arg(0)))
+        }
+        "lower" => JsonValue::String(str_arg(0).to_lowercase()),
+        "upper" => JsonValue::String(str_arg(0).to_uppercase()),
+        "trim" => JsonValue::String(str_arg(0).trim().to_string()),
+        "len" => length(&args[0]),
+        "exists" => JsonValue::Bool(!args[0].is_null()),
+        _ => return Err(format!("Unknown function '{}'", name)),
+    })
+}
+
+fn length(v: &JsonValue) -> JsonValue {
+    match v {
+        JsonValue::Array(a) => json!(a.len()),
+        JsonValue::Object(o) => json!(o.len()),
+        JsonValue::String(s) => json!(s.chars().count()),
+        _ => JsonValue::Null,
+    }
+}
+
+fn as_number(v: &JsonValue) -> Option<f64> {
+    match v {
+        JsonValue::Number(n) => n.as_f64(),
+        JsonValue::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+pub fn is_truthy(v: &JsonValue) -> bool {
+    match v {
+        JsonValue::Null => false,
+        JsonValue::Bool(b) => *b,
+        JsonValue::Number(n) => n.as_f64().is_some_and(|n| n != 0.0),
+        JsonValue::String(s) => !s.is_empty() && s != "false",
+        JsonValue::Array(a) => !a.is_empty(),
+        JsonValue::Object(_) => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scope() -> JsonValue {
+        json!({
+            "status": 200,
+            "headers": { "content-type": "application/json" },
+            "body": { "items": [{ "id": 1, "name": "Alice" }, { "id": 2, "name": "Bob" }], "error": null },
+        })
+    }
+
+    fn check(expr: &str) -> bool {
+        Expression::parse(expr).unwrap().evaluate_bool(&scope()).unwrap()
+    }
+
+    #[test]
+    fn test_comparisons_and_logic() {
+        assert!(check("status == 200"));
+        assert!(check("status == '200'"));
+        assert!(check("status >= 200 && status < 300"));
+        assert!(check("status == 404 || status == 200"));
+        assert!(!check("!(status == 200)"));
+        assert!(check("body.items.length > 0"));
+    }
+
+    #[test]
+    fn test_paths() {
+        assert!(check("body.items[1].name == 'Bob'"));
+        assert!(check("body.items.0.id == 1"));
+        assert!(check("body.items[-1].id == 2"));
+        assert!(check("headers['content-type'] == 'application/json'"));
+        assert!(check("$.status == 200"));
+        assert!(check("body.error == null"));
+        assert!(check("body.missing.deeply == null"));
+    }
+
+    #[test]
+    fn test_functions() {
+        assert!(check("contains(headers['content-type'], 'json')"));
+        assert!(check("startsWith(lower(body.items[0].name), 'al')"));
+        assert!(check("matches(body.items[1].name, '^B.b$')"));
+        assert!(check("len(body.items) == 2"));
+        assert!(check("exists(body.items) && !exists(body.error)"));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Expression::parse("status ==").is_err());
+        assert!(Expression::parse("status == 200)").is_err());
+        assert!(Expression::parse("unknown(status)").is_err());
+        assert!(Expression::parse("contains(status)").is_err());
+        assert!(Expression::parse("'unterminated").is_err());
+        assert!(Expression::parse("{{step[0].response.status}} == 200").is_err());
+    }
+
+    #[test]
+    fn test_condition_binds_tags() {
+        let condition = Condition::parse("${[ status ]} == 200 && contains(body, 'x')").unwrap();
+        assert_eq!(condition.tags.len(), 1);
+        assert!(!condition.is_lone_tag());
+
+        // A rendered value is bound as data, even if it looks like an expression
+        let mut scope = scope();
+        let expr = Expression::parse(&condition.source).unwrap();
+        scope["__tag0"] = bound_value("200");
+        scope["body"] = json!("xyz");
+        assert!(expr.evaluate_bool(&scope).unwrap());
+        scope["__tag0"] = bound_value("0 || true");
+        assert!(!expr.evaluate_bool(&scope).unwrap());
+        scope["__tag0"] = bound_value("1) || (true");
+        assert!(!expr.evaluate_bool(&scope).unwrap());
+    }
+
+    #[test]
+    fn test_legacy_conditions() {
+        // The default condition before expressions
+        let condition = Condition::parse("{{step[0].response.status}} == 200").unwrap();
+        assert!(condition.tags.is_empty());
+        let expr = Expression::parse(&condition.source).unwrap();
+        let mut scope = scope();
+        scope["step"] = json!([{ "response": { "status": 200 } }]);
+        assert!(expr.evaluate_bool(&scope).unwrap());
+        scope["step"] = json!([{ "response": { "status": 404 } }]);
+        assert!(!expr.evaluate_bool(&scope).unwrap());
+
+        let condition = Condition::parse("{{ vars.enabled }}").unwrap();
+        assert_eq!(condition.tags, vec![("__tag0".to_string(), "${[ vars.enabled ]}".to_string())]);
+        assert!(condition.is_lone_tag());
+    }
+
+    #[test]
+    fn test_lone_tag_conditions() {
+        assert!(Condition::parse(" ${[ vars.enabled ]} ").unwrap().is_lone_tag());
+        assert!(!Condition::parse("!${[ vars.enabled ]}").unwrap().is_lone_tag());
+        assert!(!Condition::parse("status == 200").unwrap().is_lone_tag());
+
+        assert!(is_truthy_text("yes"));
+        assert!(is_truthy_text("anything"));
+        assert!(is_truthy_text("-2"));
+        assert!(!is_truthy_text(" No "));
+        assert!(!is_truthy_text("0"));
+        assert!(!is_truthy_text(""));
+    }
+
+    #[test]
+    fn test_runtime_errors() {
+        let expr = Expression::parse("body.items > 1").unwrap();
+        assert!(expr.evaluate(&scope()).is_err());
+    }
+}
@@ -0,0 +1,141 @@
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+
+use super::assertion::find_header;
+use super::expression::Expression;
+
+/// A single variable captured by a `set_variable` node
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Extraction {
+    /// Name of the variable to set
+    pub name: String,
+    /// One of `json_path`, `regex` or `header`
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// Path (eg. `$.body.token`) for `json_path`, the pattern for `regex`, or the header name
+    pub path: String,
+    /// Used when nothing matches, instead of failing the node
+    pub default: Option<String>,
+}
+
+/// Capture every variable from the output of a previous node, in order
+pub fn extract_all(extractions: &[Extraction], source: &JsonValue) -> Result<Vec<(String, String)>, String> {
+    extractions.iter().map(|e| Ok((e.name.clone(), extract(e, source)?))).collect()
+}
+
+fn extract(extraction: &Extraction, source: &JsonValue) -> Result<String, String> {
+    let value = match extraction.kind.as_str() {
+        "json_path" => {
+            let value = Expression::parse(&extraction.path)
+                .and_then(|e| e.evaluate(source))
+                .map_err(|e| format!("Invalid path for '{}': {}", extraction.name, e))?;
+            to_variable(value)
+        }
+        "regex" => {
+            let re = Regex::new(&extraction.path)
+                .map_err(|e| format!("Invalid pattern for '{}': {}", extraction.name, e))?;
+            // The first capture group if there is one, otherwise the whole match
+            re.captures(&body_text(source))
+                .and_then(|c| c.get(1).or_else(|| c.get(0)))
+                .map(|m| m.as_str().to_string())
+        }
+        "header" => to_variable(find_header(source, &extraction.path)),
+        kind => return Err(format!("Unknown extraction type '{}'", kind)),
+    };
+
+    value.or_else(|| extraction.default.clone()).ok_or_else(|| {
+        format!("Nothing matched {} '{}' for '{}'", extraction.kind, extraction.path, extraction.name)
+    })
+}
+
+/// Variables are strings, so anything else is stored as JSON
+fn to_variable(value: JsonValue) -> Option<String> {
+    match value {
+        JsonValue::Null => None,
+        JsonValue::String(s) => Some(s),
+        v => Some(v.to_string()),
+    }
+}
+
+/// Text that regex extractions run against. gRPC nodes expose their response as `message`
+/// rather than `body`.
+fn body_text(source: &JsonValue) -> String {
+    match source.get("body").or_else(|| source.get("message")).unwrap_or(source) {
+        JsonValue::String(s) => s.clone(),
+        v => v.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn source() -> JsonValue {
+        json!({
+            "status": 200,
+            "headers": { "Content-Type": "application/json", "X-Request-Id": "req_1" },
+            "body": { "token": "abc123", "user": { "id": 42 }, "redirect": "/login?next=%2Fhome" },
+        })
+    }
+
+    fn extraction(kind: &str, path: &str) -> Extraction {
+        Extraction {
+            name: "value".to_string(),
+            kind: kind.to_string(),
+            path: path.to_string(),
+            default: None,
+        }
+    }
+
+    #[test]
+    fn test_extract() {
+        let source = source();
+        let extract = |kind, path| extract(&extraction(kind, path), &source);
+
+        assert_eq!(extract("json_path", "$.body.token"), Ok("abc123".to_string()));
+        assert_eq!(extract("json_path", "body.user.id"), Ok("42".to_string()));
+        assert_eq!(extract("json_path", "body.user"), Ok(r#"{"id":42}"#.to_string()));
+        assert_eq!(extract("header", "x-request-id"), Ok("req_1".to_string()));
+        assert_eq!(extract("regex", r#"next=([^"&]+)"#), Ok("%2Fhome".to_string()));
+        assert_eq!(extract("regex", r"abc\d+"), Ok("abc123".to_string()));
+
+        let plain = json!({ "body": "session=s3cret; Path=/" });
+        assert_eq!(
+            super::extract(&extraction("regex", "session=(\\w+)"), &plain),
+            Ok("s3cret".to_string())
+        );
+    }
+
+    #[test]
+    fn test_extract_missing() {
+        let source = source();
+        assert!(extract(&extraction("json_path", "$.body.missing"), &source).is_err());
+        assert!(extract(&extraction("header", "Authorization"), &source).is_err());
+        assert!(extract(&extraction("regex", "("), &source).is_err());
+        assert!(extract(&extraction("xpath", "//a"), &source).is_err());
+
+        let fallback = Extraction {
+            default: Some("none".to_string()),
+            ..extraction("json_path", "$.body.missing")
+        };
+        assert_eq!(extract(&fallback, &source), Ok("none".to_string()));
+    }
+
+    #[test]
+    fn test_extract_all() {
+        let extractions = vec![
+            Extraction { name: "token".to_string(), ..extraction("json_path", "$.body.token") },
+            Extraction { name: "requestId".to_string(), ..extraction("header", "X-Request-Id") },
+        ];
+        assert_eq!(
+            extract_all(&extractions, &source()),
+            Ok(vec![
+                ("token".to_string(), "abc123".to_string()),
+                ("requestId".to_string(), "req_1".to_string()),
+            ])
+        );
+    }
+}
@@ -2,11 +2,16 @@ use std::collections::{HashMap, HashSet};
 use yaak_models::db_context::DbContext;
 use yaak_models::error::{Error, Result};
 use yaak_models::models::{
-    get_node_type_definitions, EdgeType, NodeType, ValidationError, ValidationResult,
+    get_node_policy_schema, get_node_type_definitions, EdgeType, NodeType, ValidationError, ValidationResult,
     WorkflowEdge, WorkflowNode,
 };
 use jsonschema::JSONSchema;
 
+use super::expression::{Condition, Expression};
+
+/// How deeply sub-workflows may call other sub-workflows
+pub const MAX_SUB_WORKFLOW_DEPTH: usize = 5;
+
 /// Execution graph representation
 #[derive(Debug, Clone)]
 pub struct ExecutionGraph {
@@ -23,6 +28,7 @@ pub enum ExecutionStep {
         node_id: String,
     },
     Parallel {
+        node_id: String,
         node_ids: Vec<String>,
     },
     Conditional {
@@ -47,7 +53,8 @@ impl GraphBuilder {
         let edges = db.get_workflow_edges(workflow_id)?;
 
         // 2. Validate graph structure
-        let validation_result = Self::validate_graph(&nodes, &edges);
+        let mut validation_result = Self::validate_graph(&nodes, &edges);
+        Self::validate_sub_workflows(workflow_id, &nodes, db, &mut validation_result);
         if !validation_result.is_valid() {
             let error_messages: Vec<String> = validation_result
                 .errors
@@ -268,6 +275,7 @@ impl GraphBuilder {
                 let parallel_branches = Self::get_parallel_targets(node_id, edges);
 
                 execution_steps.push(ExecutionStep::Parallel {
+                    node_id: node_id.to_string(),
                     node_ids: parallel_branches,
                 });
             }
@@ -456,6 +464,153 @@ impl GraphBuilder {
                 });
             }
         }
+
+        if let Some(policy) = node.config.get("policy") {
+            Self::validate_policy(node, policy, result);
+        }
+
+        if node.node_subtype == "conditional" {
+            Self::validate_condition(node, result);
+        }
+    }
+
+    /// Check that sub-workflow nodes call an existing workflow in the same workspace, and that
+    /// sub-workflows don't end up calling themselves or nest too deeply
+    fn validate_sub_workflows(
+        workflow_id: &str,
+        nodes: &[WorkflowNode],
+        db: &DbContext,
+        result: &mut ValidationResult,
+    ) {
+        let mut calls_any = false;
+        for node in nodes.iter().filter(|n| Self::is_sub_workflow_call(n)) {
+            let child_id = node.config.get("workflow_id").and_then(|v| v.as_str()).unwrap_or_default();
+            if child_id.is_empty() {
+                continue; // Reported by the schema check
+            }
+            calls_any = true;
+
+            let message = match db.get_workflow(child_id) {
+                Ok(w) if w.workspace_id != node.workspace_id => {
+                    Some("Sub-workflow must be in the same workspace".to_string())
+                }
+                Ok(_) => None,
+                Err(_) => Some(format!("Sub-workflow {} does not exist", child_id)),
+            };
+            if let Some(message) = message {
+                result.add_error(ValidationError::with_field(
+                    message,
+                    node.id.clone(),
+                    "workflow_id".to_string(),
+                ));
+            }
+        }
+
+        if !calls_any {
+            return;
+        }
+
+        let calls = |id: &str| -> Vec<String> {
+            if id == workflow_id {
+                return Self::sub_workflow_ids(nodes);
+            }
+            db.get_workflow_nodes(id).map(|n| Self::sub_workflow_ids(&n)).unwrap_or_default()
+        };
+        if let Err(e) = Self::check_sub_workflow_calls(workflow_id, &calls) {
+            result.add_error(ValidationError::new(e));
+        }
+    }
+
+    fn is_sub_workflow_call(node: &WorkflowNode) -> bool {
+        node.node_subtype == "sub_workflow" && node.enabled && node.deleted_at.is_none()
+    }
+
+    /// Workflows called by the sub-workflow nodes in `nodes`
+    fn sub_workflow_ids(nodes: &[WorkflowNode]) -> Vec<String> {
+        nodes
+            .iter()
+            .filter(|n| Self::is_sub_workflow_call(n))
+            .filter_map(|n| n.config.get("workflow_id").and_then(|v| v.as_str()))
+            .filter(|id| !id.is_empty())
+            .map(|id| id.to_string())
+            .collect()
+    }
+
+    /// Walk the sub-workflow calls starting at `workflow_id`, where `calls` returns the workflows
+    /// a workflow calls. Fails on a cycle or on nesting deeper than `MAX_SUB_WORKFLOW_DEPTH`.
+    fn check_sub_workflow_calls(
+        workflow_id: &str,
+        calls: &dyn Fn(&str) -> Vec<String>,
+    ) -> std::result::Result<(), String> {
+        fn walk(path: &mut Vec<String>, calls: &dyn Fn(&str) -> Vec<String>) -> std::result::Result<(), String> {
+            let current = path.last().cloned().unwrap_or_default();
+            for child in calls(&current) {
+                if path.contains(&child) {
+                    return Err(format!(
+                        "Sub-workflows call each other in a cycle: {} -> {}",
+                        path.join(" -> "),
+                        child
+                    ));
+                }
+                if path.len() > MAX_SUB_WORKFLOW_DEPTH {
+                    return Err(format!(
+                        "Sub-workflows are nested more than {} levels deep: {} -> {}",
+                        MAX_SUB_WORKFLOW_DEPTH,
+                        path.join(" -> "),
+                        child
+                    ));
+                }
+                path.push(child);
+                walk(path, calls)?;
+                path.pop();
+            }
+            Ok(())
+        }
+
+        walk(&mut vec![workflow_id.to_string()], calls)
+    }
+
+    /// Validate the retry/timeout policy shared by all node types
+    fn validate_policy(node: &WorkflowNode, policy: &serde_json::Value, result: &mut ValidationResult) {
+        let schema = match JSONSchema::compile(&get_node_policy_schema()) {
+            Ok(s) => s,
+            Err(e) => {
+                result.add_error(ValidationError::with_node(
+                    format!("Invalid policy schema: {}", e),
+                    node.id.clone(),
+                ));
+                return;
+            }
+        };
+
+        if let Err(validation_errors) = schema.validate(policy) {
+            for error in validation_errors {
+                let field = format!("policy{}", error.instance_path);
+                result.add_error(ValidationError::with_field(
+                    format!("Field '{}': {}", field, error),
+                    node.id.clone(),
+                    field,
+                ));
+            }
+        }
+    }
+
+    /// Check that a conditional node's expression parses. Template tags are only known at
+    /// runtime, but they always stand for a value, so the expression can be checked without them.
+    fn validate_condition(node: &WorkflowNode, result: &mut ValidationResult) {
+        let Some(condition) = node.config.get("condition").and_then(|v| v.as_str()) else {
+            return;
+        };
+
+        let parsed =
+            Condition::parse(condition).and_then(|c| Expression::parse(&c.source).map(|_| ()));
+        if let Err(e) = parsed {
+            result.add_error(ValidationError::with_field(
+                format!("Invalid condition: {}", e),
+                node.id.clone(),
+                "condition".to_string(),
+            ));
+        }
     }
 }
 
@@ -480,6 +635,7 @@ mod tests {
             created_at: Utc::now().naive_utc(),
             updated_at: Utc::now().naive_utc(),
             deleted_at: None,
+            workspace_id: "test_workspace".to_string(),
             workflow_id: "test_workflow".to_string(),
             node_type,
             node_subtype: node_subtype.to_string(),
@@ -507,6 +663,7 @@ mod tests {
             created_at: Utc::now().naive_utc(),
             updated_at: Utc::now().naive_utc(),
             deleted_at: None,
+            workspace_id: "test_workspace".to_string(),
             workflow_id: "test_workflow".to_string(),
             source_node_id: source.to_string(),
             target_node_id: target.to_string(),
@@ -731,6 +888,40 @@ mod tests {
         assert!(result.errors.iter().any(|e| e.message.contains("url")), "Should report missing 'url' field");
     }
 
+    #[test]
+    fn test_json_schema_validation_request_reference() {
+        // HTTP request node referencing a saved request doesn't need an inline method/url
+        let mut node = create_test_node("http1", NodeType::Action, "http_request");
+        node.config = json!({
+            "request_id": "rq_123",
+            "request_model": "http_request"
+        });
+
+        let mut result = ValidationResult::new();
+        GraphBuilder::validate_node_config(&node, &mut result);
+        assert!(result.is_valid(), "Request reference should pass. Errors: {:?}", result.errors);
+    }
+
+    #[test]
+    fn test_json_schema_validation_grpc_inline() {
+        let mut node = create_test_node("grpc1", NodeType::Action, "grpc_request");
+        node.config = json!({
+            "url": "localhost:50051",
+            "service": "helloworld.Greeter",
+            "method": "SayHello",
+            "message": { "name": "Yaak" }
+        });
+
+        let mut result = ValidationResult::new();
+        GraphBuilder::validate_node_config(&node, &mut result);
+        assert!(result.is_valid(), "Inline gRPC config should pass. Errors: {:?}", result.errors);
+
+        node.config = json!({ "url": "localhost:50051" });
+        let mut result = ValidationResult::new();
+        GraphBuilder::validate_node_config(&node, &mut result);
+        assert!(result.errors.iter().any(|e| e.message.contains("service")));
+    }
+
     #[test]
     fn test_json_schema_validation_invalid_enum() {
         // Create HTTP request node with invalid method
@@ -764,6 +955,76 @@ mod tests {
         assert!(result.is_valid(), "Valid webhook config should pass. Errors: {:?}", result.errors);
     }
 
+    #[test]
+    fn test_validation_conditional_expression() {
+        let mut node = create_test_node("cond1", NodeType::Logic, "conditional");
+
+        let mut result = ValidationResult::new();
+        GraphBuilder::validate_node_config(&node, &mut result);
+        assert!(result.is_valid(), "Default condition should pass. Errors: {:?}", result.errors);
+
+        node.config = json!({ "condition": "${[ workflow.node.login.status ]} == 200 && contains(body.name, 'x')" });
+        let mut result = ValidationResult::new();
+        GraphBuilder::validate_node_config(&node, &mut result);
+        assert!(result.is_valid(), "Template tags should be allowed. Errors: {:?}", result.errors);
+
+        node.config = json!({ "condition": "{{step[0].response.status}} == 200" });
+        let mut result = ValidationResult::new();
+        GraphBuilder::validate_node_config(&node, &mut result);
+        assert!(result.is_valid(), "Legacy conditions should pass. Errors: {:?}", result.errors);
+
+        node.config = json!({ "condition": "status == && true" });
+        let mut result = ValidationResult::new();
+        GraphBuilder::validate_node_config(&node, &mut result);
+        assert!(!result.is_valid());
+        assert_eq!(result.errors[0].field.as_deref(), Some("condition"));
+    }
+
+    #[test]
+    fn test_json_schema_validation_assertion() {
+        let mut node = create_test_node("assert1", NodeType::Logic, "assertion");
+
+        let mut result = ValidationResult::new();
+        GraphBuilder::validate_node_config(&node, &mut result);
+        assert!(result.is_valid(), "Default assertion config should pass. Errors: {:?}", result.errors);
+
+        node.config = json!({ "assertions": [{ "type": "status", "operator": "about" }] });
+        let mut result = ValidationResult::new();
+        GraphBuilder::validate_node_config(&node, &mut result);
+        assert!(!result.is_valid());
+    }
+
+    #[test]
+    fn test_json_schema_validation_set_variable() {
+        let mut node = create_test_node("set1", NodeType::Logic, "set_variable");
+
+        let mut result = ValidationResult::new();
+        GraphBuilder::validate_node_config(&node, &mut result);
+        assert!(result.is_valid(), "Default set_variable config should pass. Errors: {:?}", result.errors);
+
+        node.config = json!({ "variables": [{ "name": "token", "type": "xpath", "path": "//token" }] });
+        let mut result = ValidationResult::new();
+        GraphBuilder::validate_node_config(&node, &mut result);
+        assert!(!result.is_valid());
+    }
+
+    #[test]
+    fn test_validation_node_policy() {
+        let mut node = create_test_node("http1", NodeType::Action, "http_request");
+        node.config["url"] = json!("https://yaak.app");
+        node.config["policy"] = json!({ "max_retries": 3, "timeout_ms": 5000, "continue_on_error": true });
+
+        let mut result = ValidationResult::new();
+        GraphBuilder::validate_node_config(&node, &mut result);
+        assert!(result.is_valid(), "Valid policy should pass. Errors: {:?}", result.errors);
+
+        node.config["policy"] = json!({ "max_retries": -1 });
+        let mut result = ValidationResult::new();
+        GraphBuilder::validate_node_config(&node, &mut result);
+        assert!(!result.is_valid());
+        assert_eq!(result.errors[0].field.as_deref(), Some("policy/max_retries"));
+    }
+
     #[test]
     fn test_validation_collects_multiple_errors() {
         // Create nodes with multiple validation errors
@@ -782,6 +1043,58 @@ mod tests {
         assert!(result.errors.len() >= 2, "Should collect multiple errors. Got: {:?}", result.errors);
     }
 
+    #[test]
+    fn test_json_schema_validation_sub_workflow() {
+        let mut node = create_test_node("sub1", NodeType::Action, "sub_workflow");
+
+        // A workflow has to be picked
+        let mut result = ValidationResult::new();
+        GraphBuilder::validate_node_config(&node, &mut result);
+        assert!(!result.is_valid());
+
+        node.config = json!({ "workflow_id": "wf_child", "inputs": { "userId": "42" }, "outputs": ["Login"] });
+        let mut result = ValidationResult::new();
+        GraphBuilder::validate_node_config(&node, &mut result);
+        assert!(result.is_valid(), "Errors: {:?}", result.errors);
+    }
+
+    #[test]
+    fn test_sub_workflow_calls() {
+        let graph: HashMap<&str, Vec<&str>> = HashMap::from([
+            ("a", vec!["b", "c"]),
+            ("b", vec!["c"]),
+            ("c", vec![]),
+            ("loop_1", vec!["loop_2"]),
+            ("loop_2", vec!["loop_1"]),
+            ("self", vec!["self"]),
+        ]);
+        let calls = |id: &str| -> Vec<String> {
+            graph.get(id).map(|c| c.iter().map(|s| s.to_string()).collect()).unwrap_or_default()
+        };
+
+        // Calling the same workflow from two places is fine
+        assert!(GraphBuilder::check_sub_workflow_calls("a", &calls).is_ok());
+
+        let err = GraphBuilder::check_sub_workflow_calls("loop_1", &calls).unwrap_err();
+        assert!(err.contains("loop_1 -> loop_2 -> loop_1"), "{}", err);
+        assert!(GraphBuilder::check_sub_workflow_calls("self", &calls).is_err());
+    }
+
+    #[test]
+    fn test_sub_workflow_depth() {
+        // w0 -> w1 -> ... -> wN
+        let chain = |depth: usize| {
+            move |id: &str| -> Vec<String> {
+                let i: usize = id[1..].parse().unwrap();
+                if i < depth { vec![format!("w{}", i + 1)] } else { vec![] }
+            }
+        };
+
+        assert!(GraphBuilder::check_sub_workflow_calls("w0", &chain(MAX_SUB_WORKFLOW_DEPTH)).is_ok());
+        let err = GraphBuilder::check_sub_workflow_calls("w0", &chain(MAX_SUB_WORKFLOW_DEPTH + 1)).unwrap_err();
+        assert!(err.contains("nested more than"), "{}", err);
+    }
+
     #[test]
     fn test_unknown_node_type() {
         let mut node = create_test_node("unknown1", NodeType::Action, "unknown_action");
@@ -0,0 +1,412 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use super::graph_builder::ExecutionGraph;
+
+/// Upper bounds for a single load test, so a typo can't queue millions of runs
+const MAX_ITERATIONS: u32 = 100_000;
+const MAX_CONCURRENCY: u32 = 200;
+const MAX_RAMP_UP_MS: u64 = 10 * 60 * 1000;
+
+/// How often the load test record is updated while it runs
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Values below this are counted exactly, larger ones in buckets of at most 1/64th of their size
+const EXACT_LIMIT: u64 = 128;
+const SUB_BUCKETS: u64 = 64;
+
+/// How many times to run a workflow, and how fast
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct LoadTestConfig {
+    /// Total number of runs
+    pub iterations: u32,
+    /// Number of runs in flight at once
+    pub concurrency: u32,
+    /// Workers are started evenly spread over this time, instead of all at once
+    pub ramp_up_ms: u64,
+}
+
+impl Default for LoadTestConfig {
+    fn default() -> Self {
+        Self {
+            iterations: 100,
+            concurrency: 10,
+            ramp_up_ms: 0,
+        }
+    }
+}
+
+impl LoadTestConfig {
+    /// Clamp the config to sensible bounds
+    pub fn normalized(self) -> Self {
+        let iterations = self.iterations.clamp(1, MAX_ITERATIONS);
+        Self {
+            iterations,
+            concurrency: self.concurrency.clamp(1, MAX_CONCURRENCY.min(iterations)),
+            ramp_up_ms: self.ramp_up_ms.min(MAX_RAMP_UP_MS),
+        }
+    }
+
+    /// Delay before `worker` (0-based) starts its first run
+    pub fn start_delay(&self, worker: u32) -> Duration {
+        Duration::from_millis(self.ramp_up_ms * worker as u64 / self.concurrency.max(1) as u64)
+    }
+}
+
+/// Latency histogram with a fixed relative precision, so memory stays bounded no matter how
+/// many values are recorded
+#[derive(Debug, Clone, Default)]
+pub struct LatencyHistogram {
+    buckets: Vec<u64>,
+    count: u64,
+    sum: u64,
+    min: u64,
+    max: u64,
+}
+
+/// Percentiles of a histogram, in milliseconds
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatencyStats {
+    pub min: u64,
+    pub max: u64,
+    pub mean: f64,
+    pub p50: u64,
+    pub p95: u64,
+    pub p99: u64,
+}
+
+impl LatencyHistogram {
+    pub fn record(&mut self, ms: u64) {
+        let index = bucket_index(ms);
+        if index >= self.buckets.len() {
+            self.buckets.resize(index + 1, 0);
+        }
+        self.buckets[index] += 1;
+
+        self.min = if self.count == 0 { ms } else { self.min.min(ms) };
+        self.max = self.max.max(ms);
+        self.count += 1;
+        self.sum += ms;
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Value at or below which `p` percent of the recorded values fall
+    pub fn percentile(&self, p: f64) -> u64 {
+        if self.count == 0 {
+            return 0;
+        }
+
+        let rank = ((p / 100.0) * self.count as f64).ceil().max(1.0) as u64;
+        let mut seen = 0;
+        for (index, count) in self.buckets.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return bucket_value(index).clamp(self.min, self.max);
+            }
+        }
+        self.max
+    }
+
+    pub fn stats(&self) -> Option<LatencyStats> {
+        if self.count == 0 {
+            return None;
+        }
+        Some(LatencyStats {
+            min: self.min,
+            max: self.max,
+            mean: self.sum as f64 / self.count as f64,
+            p50: self.percentile(50.0),
+            p95: self.percentile(95.0),
+            p99: self.percentile(99.0),
+        })
+    }
+}
+
+fn bucket_index(ms: u64) -> usize {
+    if ms < EXACT_LIMIT {
+        return ms as usize;
+    }
+    // Each power of two above the exact range is split into SUB_BUCKETS equal buckets
+    let shift = 63 - ms.leading_zeros() as u64 - SUB_BUCKETS.trailing_zeros() as u64;
+    ((shift + 1) * SUB_BUCKETS + ((ms >> shift) - SUB_BUCKETS)) as usize
+}
+
+/// Representative value of a bucket: the midpoint of the values it holds
+fn bucket_value(index: usize) -> u64 {
+    let index = index as u64;
+    if index < EXACT_LIMIT {
+        return index;
+    }
+    let shift = index / SUB_BUCKETS - 1;
+    let lower = (index % SUB_BUCKETS + SUB_BUCKETS) << shift;
+    lower + ((1 << shift) - 1) / 2
+}
+
+/// Timing of a single node execution in one of the runs
+#[derive(Debug, Clone)]
+pub struct NodeSample {
+    pub node_id: String,
+    pub elapsed: u64,
+    pub failed: bool,
+    /// Time until the response headers arrived, for HTTP requests
+    pub ttfb: Option<u64>,
+}
+
+#[derive(Debug, Default)]
+struct NodeStats {
+    errors: u64,
+    latency: LatencyHistogram,
+    ttfb: LatencyHistogram,
+}
+
+/// Running totals of a load test, updated as the runs finish
+#[derive(Debug)]
+pub struct LoadTestStats {
+    started: Instant,
+    last_progress: Instant,
+    completed: u64,
+    failed: u64,
+    iterations: LatencyHistogram,
+    /// Node IDs in the order they were first seen, which is roughly the execution order
+    node_order: Vec<String>,
+    nodes: HashMap<String, NodeStats>,
+    sample_failed_execution_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoadTestSummary {
+    pub completed: u64,
+    pub failed: u64,
+    pub error_rate: f64,
+    /// Finished runs per second
+    pub throughput: f64,
+    pub latency: Option<LatencyStats>,
+    pub nodes: Vec<NodeSummary>,
+    /// One failed run is kept in full so the failure can be inspected
+    pub sample_failed_execution_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeSummary {
+    pub node_id: String,
+    pub node_name: String,
+    pub node_subtype: String,
+    pub count: u64,
+    pub errors: u64,
+    pub error_rate: f64,
+    /// Node executions per second
+    pub throughput: f64,
+    pub latency: Option<LatencyStats>,
+    pub ttfb: Option<LatencyStats>,
+}
+
+impl LoadTestStats {
+    pub fn start() -> Self {
+        Self {
+            started: Instant::now(),
+            last_progress: Instant::now(),
+            completed: 0,
+            failed: 0,
+            iterations: LatencyHistogram::default(),
+            node_order: Vec::new(),
+            nodes: HashMap::new(),
+            sample_failed_execution_id: None,
+        }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    pub fn completed(&self) -> u64 {
+        self.completed
+    }
+
+    pub fn failed(&self) -> u64 {
+        self.failed
+    }
+
+    pub fn record_iteration(&mut self, elapsed: u64, succeeded: bool, samples: Vec<NodeSample>) {
+        if succeeded {
+            self.completed += 1;
+        } else {
+            self.failed += 1;
+        }
+        self.iterations.record(elapsed);
+
+        for sample in samples {
+            if !self.nodes.contains_key(&sample.node_id) {
+                self.node_order.push(sample.node_id.clone());
+            }
+            let stats = self.nodes.entry(sample.node_id).or_default();
+            stats.latency.record(sample.elapsed);
+            if sample.failed {
+                stats.errors += 1;
+            }
+            if let Some(ttfb) = sample.ttfb {
+                stats.ttfb.record(ttfb);
+            }
+        }
+    }
+
+    /// Keep the first failed run as the sample, returning whether `execution_id` was kept
+    pub fn keep_failed_sample(&mut self, execution_id: &str) -> bool {
+        if self.sample_failed_execution_id.is_some() {
+            return false;
+        }
+        self.sample_failed_execution_id = Some(execution_id.to_string());
+        true
+    }
+
+    /// Whether enough time passed since the last progress update to send another one
+    pub fn progress_due(&mut self) -> bool {
+        if self.last_progress.elapsed() < PROGRESS_INTERVAL {
+            return false;
+        }
+        self.last_progress = Instant::now();
+        true
+    }
+
+    pub fn summary(&self, graph: &ExecutionGraph) -> LoadTestSummary {
+        let seconds = self.elapsed().as_secs_f64().max(0.001);
+        let total = self.completed + self.failed;
+
+        let nodes = self
+            .node_order
+            .iter()
+            .map(|node_id| {
+                let stats = &self.nodes[node_id];
+                let node = graph.nodes.get(node_id);
+                let count = stats.latency.count();
+                NodeSummary {
+                    node_id: node_id.clone(),
+                    node_name: node.map(|n| n.name.clone()).unwrap_or_default(),
+                    node_subtype: node.map(|n| n.node_subtype.clone()).unwrap_or_default(),
+                    count,
+                    errors: stats.errors,
+                    error_rate: rate(stats.errors, count),
+                    throughput: count as f64 / seconds,
+                    latency: stats.latency.stats(),
+                    ttfb: stats.ttfb.stats(),
+                }
+            })
+            .collect();
+
+        LoadTestSummary {
+            completed: self.completed,
+            failed: self.failed,
+            error_rate: rate(self.failed, total),
+            throughput: total as f64 / seconds,
+            latency: self.iterations.stats(),
+            nodes,
+            sample_failed_execution_id: self.sample_failed_execution_id.clone(),
+        }
+    }
+}
+
+fn rate(part: u64, total: u64) -> f64 {
+    if total == 0 { 0.0 } else { part as f64 / total as f64 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buckets_are_contiguous() {
+        let mut previous = bucket_index(0);
+        for ms in 1..100_000 {
+            let index = bucket_index(ms);
+            assert!(index == previous || index == previous + 1, "gap at {}ms", ms);
+            previous = index;
+        }
+        assert_eq!(bucket_value(bucket_index(127)), 127);
+        assert_eq!(bucket_index(128), 128);
+        assert_eq!(bucket_value(bucket_index(i32::MAX as u64)) >> 24, (i32::MAX as u64) >> 24);
+    }
+
+    #[test]
+    fn test_percentiles() {
+        let mut histogram = LatencyHistogram::default();
+        assert_eq!(histogram.stats(), None);
+
+        for ms in 1..=100 {
+            histogram.record(ms);
+        }
+        let stats = histogram.stats().unwrap();
+        assert_eq!((stats.min, stats.max, stats.mean), (1, 100, 50.5));
+        assert_eq!((stats.p50, stats.p95, stats.p99), (50, 95, 99));
+
+        // Large values are approximate, but within the bucket precision
+        let mut histogram = LatencyHistogram::default();
+        for ms in 1..=10_000 {
+            histogram.record(ms);
+        }
+        for (p, expected) in [(50.0, 5000.0), (95.0, 9500.0), (99.0, 9900.0)] {
+            let actual = histogram.percentile(p) as f64;
+            assert!((actual - expected).abs() / expected < 1.0 / SUB_BUCKETS as f64, "p{}: {}", p, actual);
+        }
+        assert_eq!(histogram.percentile(100.0), 10_000);
+    }
+
+    #[test]
+    fn test_config_bounds() {
+        let config = LoadTestConfig { iterations: 5, concurrency: 50, ramp_up_ms: 1000 }.normalized();
+        assert_eq!(config.concurrency, 5);
+        assert_eq!(config.start_delay(0), Duration::ZERO);
+        assert_eq!(config.start_delay(4), Duration::from_millis(800));
+
+        let config = LoadTestConfig { iterations: 0, concurrency: 0, ramp_up_ms: 0 }.normalized();
+        assert_eq!((config.iterations, config.concurrency), (1, 1));
+
+        let config = LoadTestConfig { iterations: u32::MAX, concurrency: u32::MAX, ramp_up_ms: 0 }.normalized();
+        assert_eq!((config.iterations, config.concurrency), (MAX_ITERATIONS, MAX_CONCURRENCY));
+
+        let config = LoadTestConfig { ramp_up_ms: u64::MAX, ..Default::default() }.normalized();
+        assert_eq!(config.ramp_up_ms, MAX_RAMP_UP_MS);
+    }
+
+    #[test]
+    fn test_summary() {
+        let graph = ExecutionGraph {
+            nodes: HashMap::new(),
+            edges: Vec::new(),
+            start_node_id: "start".to_string(),
+            execution_order: Vec::new(),
+        };
+        let sample = |node_id: &str, elapsed, failed, ttfb| NodeSample {
+            node_id: node_id.to_string(),
+            elapsed,
+            failed,
+            ttfb,
+        };
+
+        let mut stats = LoadTestStats::start();
+        stats.record_iteration(30, true, vec![sample("start", 0, false, None), sample("http", 30, false, Some(10))]);
+        stats.record_iteration(50, true, vec![sample("start", 0, false, None), sample("http", 50, false, Some(20))]);
+        stats.record_iteration(90, false, vec![sample("start", 0, false, None), sample("http", 90, true, Some(40))]);
+        assert!(stats.keep_failed_sample("we_1"));
+        assert!(!stats.keep_failed_sample("we_2"));
+
+        let summary = stats.summary(&graph);
+        assert_eq!((summary.completed, summary.failed), (2, 1));
+        assert!((summary.error_rate - 1.0 / 3.0).abs() < f64::EPSILON);
+        assert_eq!(summary.sample_failed_execution_id.as_deref(), Some("we_1"));
+
+        let node_ids: Vec<&str> = summary.nodes.iter().map(|n| n.node_id.as_str()).collect();
+        assert_eq!(node_ids, vec!["start", "http"]);
+        let http = &summary.nodes[1];
+        assert_eq!((http.count, http.errors), (3, 1));
+        assert_eq!(http.latency.as_ref().map(|l| l.p50), Some(50));
+        assert_eq!(http.ttfb.as_ref().map(|l| l.max), Some(40));
+        assert_eq!(summary.nodes[0].ttfb, None);
+    }
+}
@@ -1,12 +1,20 @@
+pub mod assertion;
+pub mod cancellation;
 pub mod context;
-pub mod executor;
+pub mod cron;
+pub mod dataset;
+pub mod expression;
+pub mod extract;
 pub mod graph_builder;
+pub mod load_test;
 pub mod orchestrator;
+pub mod policy;
+pub mod report;
+pub mod resume;
+pub mod scheduler;
+pub mod webhook;
 
 pub use context::{ExecutionContext, LoopContext, NodeResult};
-pub use executor::{
-    ExecuteWorkflowRequest, ExecuteWorkflowResponse, WorkflowExecutionUpdate,
-    WorkflowStepCompleted, WorkflowExecutor,
-};
 pub use graph_builder::{ExecutionGraph, ExecutionStep, GraphBuilder};
 pub use orchestrator::WorkflowOrchestrator;
+pub use report::{ExecutionReport, ReportFormat};
@@ -1,42 +1,114 @@
-use std::collections::HashMap;
-use std::sync::{Arc, RwLock};
-use std::time::Instant;
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use std::pin::Pin;
 use std::future::Future;
 use serde_json::{json, Value as JsonValue};
-use tauri::{AppHandle, Emitter, Runtime};
+use tauri::{AppHandle, Emitter, Listener, Manager, Runtime};
 use yaak_models::error::{Error, Result};
+use log::warn;
+use regex::Regex;
+use tokio::sync::{Mutex, Semaphore};
+use tokio::task::JoinSet;
+use yaak_grpc::manager::GrpcHandle;
+use yaak_grpc::{Code, serialize_message};
+use yaak_http::dns::DnsResolver;
+use yaak_http::tls::TlsOptions;
 use yaak_models::models::{
-    NodeExecutionState, WorkflowExecution, WorkflowExecutionState,
-    WorkflowNode, WorkflowNodeExecution,
+    EnvironmentVariable, GrpcConnection, GrpcConnectionState, GrpcEvent, GrpcEventType, GrpcRequest,
+    HttpRequest, HttpRequestHeader, HttpResponse, HttpResponseState, NodeExecutionState,
+    WebsocketConnectionState, WebsocketEvent, WebsocketEventType, WebsocketRequest, WorkflowExecution,
+    WorkflowExecutionState, WorkflowLoadTest, WorkflowNode, WorkflowNodeExecution, Workspace,
 };
+use yaak_models::db_context::DbContext;
 use yaak_models::query_manager::QueryManagerExt;
+use yaak_models::render::make_vars_hashmap;
 use yaak_models::util::{generate_prefixed_id, UpdateSource};
+use yaak_plugins::events::{PluginWindowContext, RenderPurpose};
+use yaak_plugins::template_callback::PluginTemplateCallback;
+use yaak_templates::renderer::{
+    node_name_to_ident, parse_and_render_with_workflow, render_json_value_raw_with_workflow,
+    LoopContext as TemplateLoopContext, WorkflowContext,
+};
+use yaak_templates::{RenderErrorBehavior, RenderOptions};
+use yaak_ws::connection::{connect_websocket, send_websocket_message, ConnectParams};
+use yaak_ws::manager::WebsocketManager;
+
+use crate::grpc::{build_metadata, metadata_to_map, resolve_grpc_request};
+use crate::http_request::send_http_request_with_context;
+use crate::render::render_grpc_request;
+use crate::safe_uri;
 
+use super::assertion::{run_assertions, summarize, Assertion};
+use super::cancellation::{self, CancellationToken};
 use super::context::{ExecutionContext, LoopContext, NodeResult};
+use super::dataset::{load_dataset, DatasetRow};
+use super::expression::{bound_value, is_truthy_text, Condition, Expression};
+use super::extract::{extract_all, Extraction};
 use super::graph_builder::{ExecutionGraph, ExecutionStep, GraphBuilder};
+use super::load_test::{LoadTestConfig, LoadTestStats, NodeSample};
+use super::policy::NodePolicy;
+use super::resume::ResumePlan;
+
+/// Error of nodes and runs that were stopped by cancelling the execution
+const CANCELLED: &str = "Workflow execution was cancelled";
+
+fn cancelled_error() -> Error {
+    Error::GenericError(CANCELLED.to_string())
+}
+
+/// How a new execution relates to earlier ones
+#[derive(Debug, Default)]
+struct ExecutionLinks {
+    resumed_from_id: Option<String>,
+    parent_execution_id: Option<String>,
+    dataset_row: Option<i32>,
+    load_test_id: Option<String>,
+    caller_execution_id: Option<String>,
+    /// Token of the run this one is part of, so cancelling that run cancels this one too
+    cancellation: Option<CancellationToken>,
+}
+
+/// A rendered unary gRPC call of a node, along with the connection it's recorded under
+struct GrpcUnaryCall {
+    request: GrpcRequest,
+    service: String,
+    method: String,
+    message: String,
+    metadata: BTreeMap<String, String>,
+    proto_files: Vec<String>,
+    workspace: Workspace,
+    /// Event the call's events are based on, which ties them to the connection
+    base_event: GrpcEvent,
+}
+
+/// The message a gRPC node's config sends, given either as JSON text or as an object
+fn grpc_config_message(config: &JsonValue) -> Option<String> {
+    match config.get("message") {
+        Some(JsonValue::String(m)) if !m.is_empty() => Some(m.clone()),
+        Some(m @ JsonValue::Object(_)) => Some(m.to_string()),
+        _ => None,
+    }
+}
 
 /// Workflow orchestrator manages canvas-based workflow execution
 pub struct WorkflowOrchestrator<R: Runtime> {
     app_handle: AppHandle<R>,
-    cancellation_tokens: Arc<RwLock<HashMap<String, bool>>>,
 }
 
 impl<R: Runtime> Clone for WorkflowOrchestrator<R> {
     fn clone(&self) -> Self {
         Self {
             app_handle: self.app_handle.clone(),
-            cancellation_tokens: Arc::clone(&self.cancellation_tokens),
         }
     }
 }
 
 impl<R: Runtime> WorkflowOrchestrator<R> {
     pub fn new(app_handle: AppHandle<R>) -> Self {
-        Self {
-            app_handle,
-            cancellation_tokens: Arc::new(RwLock::new(HashMap::new())),
-        }
+        Self { app_handle }
     }
 
     /// Execute workflow (non-blocking)
@@ -46,15 +118,163 @@ impl<R: Runtime> WorkflowOrchestrator<R> {
         workflow_id: String,
         environment_id: Option<String>,
     ) -> Result<String> {
-        let db = self.app_handle.db();
+        self.execute_with_trigger(workflow_id, environment_id, None).await
+    }
 
-        // 1. Build execution graph (validates workflow)
-        let graph = GraphBuilder::build(&workflow_id, &db)?;
+    /// Execute workflow (non-blocking), with `trigger_payload` as the output of the start trigger
+    pub async fn execute_with_trigger(
+        &self,
+        workflow_id: String,
+        environment_id: Option<String>,
+        trigger_payload: Option<JsonValue>,
+    ) -> Result<String> {
+        // Build execution graph (validates workflow)
+        let graph = GraphBuilder::build(&workflow_id, &self.app_handle.db())?;
+        let dataset = self.load_workflow_dataset(&workflow_id)?;
+        let mut context = self.create_execution(&workflow_id, environment_id, ExecutionLinks::default())?;
+        context.trigger_payload = trigger_payload;
 
-        // 2. Load workflow
-        let workflow = db.get_workflow(&workflow_id)?;
+        Ok(self.spawn_run(graph, context, dataset))
+    }
 
-        // 3. Create WorkflowExecution record
+    /// Execute workflow (blocking)
+    /// Waits for the run to finish and returns the final execution record, for headless runs
+    pub async fn execute_and_wait(
+        &self,
+        workflow_id: String,
+        environment_id: Option<String>,
+    ) -> Result<WorkflowExecution> {
+        let graph = GraphBuilder::build(&workflow_id, &self.app_handle.db())?;
+        let dataset = self.load_workflow_dataset(&workflow_id)?;
+        let context = self.create_execution(&workflow_id, environment_id, ExecutionLinks::default())?;
+        let execution_id = context.execution_id.clone();
+
+        self.run_to_end(graph, context, dataset).await;
+        self.app_handle.db().get_workflow_execution(&execution_id)
+    }
+
+    /// Resume a finished execution (non-blocking)
+    ///
+    /// Runs the workflow again as a new execution, starting from `from_node_id` or the first
+    /// node that failed. Nodes that already completed are not sent again; their stored results
+    /// are reused so requests that created something on the server don't create it twice.
+    pub async fn resume(&self, execution_id: &str, from_node_id: Option<String>) -> Result<String> {
+        let (previous, node_executions) = {
+            let db = self.app_handle.db();
+            (db.get_workflow_execution(execution_id)?, db.get_workflow_node_executions(execution_id)?)
+        };
+
+        if matches!(
+            previous.state,
+            WorkflowExecutionState::Initialized | WorkflowExecutionState::Running
+        ) {
+            return Err(Error::GenericError("Execution is still running".to_string()));
+        }
+
+        let is_dataset_parent = self
+            .app_handle
+            .db()
+            .list_workflow_executions(&previous.workflow_id)?
+            .iter()
+            .any(|e| e.parent_execution_id.as_deref() == Some(previous.id.as_str()));
+        if is_dataset_parent {
+            return Err(Error::GenericError("Resume one of the failed dataset rows instead".to_string()));
+        }
+
+        // Resume against the workflow as it is now, so fixes made since the failure apply
+        let graph = GraphBuilder::build(&previous.workflow_id, &self.app_handle.db())?;
+        let plan = ResumePlan::new(&graph, node_executions, from_node_id)?;
+
+        // A dataset row runs again with the same row, read from the dataset as it is now
+        let row = match previous.dataset_row {
+            Some(index) => Some(
+                self.load_workflow_dataset(&previous.workflow_id)?
+                    .and_then(|rows| rows.into_iter().nth(index as usize))
+                    .ok_or_else(|| {
+                        Error::GenericError(format!("Dataset row {} no longer exists", index + 1))
+                    })?,
+            ),
+            None => None,
+        };
+
+        let mut context = self.create_execution(
+            &previous.workflow_id,
+            previous.environment_id.clone(),
+            ExecutionLinks {
+                resumed_from_id: Some(previous.id.clone()),
+                dataset_row: previous.dataset_row,
+                ..Default::default()
+            },
+        )?;
+        context.trigger_payload = plan.trigger_payload;
+        context.reused = plan.reused;
+        context.variables.extend(row.unwrap_or_default());
+
+        Ok(self.spawn_run(graph, context, None))
+    }
+
+    /// Run the workflow many times concurrently (non-blocking)
+    ///
+    /// Returns the load test ID immediately. Only the aggregated timings are kept: each run's
+    /// execution is deleted once it's been counted, except for one failed run kept as a sample.
+    /// Runs cycle through the rows of the workflow's dataset, if it has one.
+    pub async fn load_test(
+        &self,
+        workflow_id: String,
+        environment_id: Option<String>,
+        config: LoadTestConfig,
+    ) -> Result<String> {
+        let config = config.normalized();
+        let graph = GraphBuilder::build(&workflow_id, &self.app_handle.db())?;
+        let dataset = self.load_workflow_dataset(&workflow_id)?.filter(|rows| !rows.is_empty());
+
+        let load_test = {
+            let db = self.app_handle.db();
+            let workflow = db.get_workflow(&workflow_id)?;
+            db.upsert(
+                &WorkflowLoadTest {
+                    id: generate_prefixed_id("wl"),
+                    model: "workflow_load_test".to_string(),
+                    created_at: chrono::Utc::now().naive_utc(),
+                    updated_at: chrono::Utc::now().naive_utc(),
+                    workflow_id: workflow.id,
+                    workspace_id: workflow.workspace_id,
+                    environment_id: environment_id.or(workflow.environment_id),
+                    state: WorkflowExecutionState::Running,
+                    iterations: config.iterations as i32,
+                    concurrency: config.concurrency as i32,
+                    ramp_up_ms: config.ramp_up_ms as i32,
+                    ..Default::default()
+                },
+                &UpdateSource::Background,
+            )?
+        };
+
+        let cancellation = CancellationToken::new();
+        cancellation::register(&load_test.id, &cancellation);
+
+        let load_test_id = load_test.id.clone();
+        let orchestrator = self.clone();
+        tauri::async_runtime::spawn(async move {
+            orchestrator.run_load_test(graph, load_test, config, dataset, cancellation).await;
+        });
+        Ok(load_test_id)
+    }
+
+    /// Create the execution record, falling back to the workflow's own environment
+    fn create_execution(
+        &self,
+        workflow_id: &str,
+        environment_id: Option<String>,
+        links: ExecutionLinks,
+    ) -> Result<ExecutionContext> {
+        let db = self.app_handle.db();
+
+        // 1. Load workflow
+        let workflow = db.get_workflow(workflow_id)?;
+        let environment_id = environment_id.or(workflow.environment_id.clone());
+
+        // 2. Create WorkflowExecution record
         let execution = WorkflowExecution {
             id: generate_prefixed_id("we"),
             model: "workflow_execution".to_string(),
@@ -67,46 +287,345 @@ impl<R: Runtime> WorkflowOrchestrator<R> {
             elapsed: None,
             state: WorkflowExecutionState::Initialized,
             error: None,
+            assertions_passed: 0,
+            assertions_failed: 0,
+            resumed_from_id: links.resumed_from_id,
+            parent_execution_id: links.parent_execution_id,
+            dataset_row: links.dataset_row,
+            load_test_id: links.load_test_id,
+            caller_execution_id: links.caller_execution_id,
         };
 
         let execution_id = execution.id.clone();
         db.upsert(&execution, &UpdateSource::Background)?;
 
-        // 4. Register cancellation token
-        self.cancellation_tokens
-            .write()
-            .unwrap()
-            .insert(execution_id.clone(), false);
+        // 3. Register cancellation token
+        let mut context = ExecutionContext::new(workflow.id, execution_id, environment_id);
+        if let Some(parent) = links.cancellation {
+            context.cancellation = parent.child();
+        }
+        cancellation::register(&context.execution_id, &context.cancellation);
+
+        Ok(context)
+    }
 
-        // 5. Spawn async execution task
+    /// Rows of the workflow's dataset, or `None` if it doesn't have one
+    fn load_workflow_dataset(&self, workflow_id: &str) -> Result<Option<Vec<DatasetRow>>> {
+        let workflow = self.app_handle.db().get_workflow(workflow_id)?;
+        match workflow.dataset_path.filter(|p| !p.is_empty()) {
+            Some(path) => Ok(Some(load_dataset(&PathBuf::from(path))?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Spawn async execution task, returning the execution ID immediately
+    fn spawn_run(
+        &self,
+        graph: ExecutionGraph,
+        context: ExecutionContext,
+        dataset: Option<Vec<DatasetRow>>,
+    ) -> String {
+        let execution_id = context.execution_id.clone();
         let orchestrator = self.clone();
-        let exec_id_for_spawn = execution_id.clone();
-        let workflow_id_for_spawn = workflow_id.clone();
         tauri::async_runtime::spawn(async move {
-            if let Err(e) = orchestrator.run_workflow(exec_id_for_spawn.clone(), workflow_id_for_spawn, graph, environment_id).await {
-                eprintln!("Workflow execution failed: {}", e);
-                let _ = orchestrator.update_execution_state(
-                    &exec_id_for_spawn,
-                    WorkflowExecutionState::Failed,
-                    Some(e.to_string()),
+            orchestrator.run_to_end(graph, context, dataset).await;
+        });
+        execution_id
+    }
+
+    /// Run a prepared execution, recording any error on the execution record
+    async fn run_to_end(
+        &self,
+        graph: ExecutionGraph,
+        mut context: ExecutionContext,
+        dataset: Option<Vec<DatasetRow>>,
+    ) {
+        let execution_id = context.execution_id.clone();
+        let result = match dataset {
+            Some(rows) => self.run_dataset(&graph, &context, rows).await,
+            None => self.run_workflow(&graph, &mut context).await,
+        };
+        if let Err(e) = result {
+            eprintln!("Workflow execution failed: {}", e);
+            let _ = self.update_execution_state(
+                &execution_id,
+                WorkflowExecutionState::Failed,
+                Some(e.to_string()),
+                None,
+            ).await;
+        }
+    }
+
+    /// Run the graph once per dataset row, each as its own execution grouped under `parent`
+    ///
+    /// Rows run one after another with their fields as variables. A failing row doesn't stop
+    /// the rest; the parent fails once all rows ran if any of them did.
+    async fn run_dataset(
+        &self,
+        graph: &ExecutionGraph,
+        parent: &ExecutionContext,
+        rows: Vec<DatasetRow>,
+    ) -> Result<()> {
+        let start_time = Instant::now();
+        let execution_id = parent.execution_id.clone();
+        self.update_execution_state(&execution_id, WorkflowExecutionState::Running, None, None).await?;
+
+        let total = rows.len();
+        let mut failed = 0;
+        for (index, row) in rows.into_iter().enumerate() {
+            if parent.cancellation.is_cancelled() {
+                let elapsed = start_time.elapsed().as_millis() as i32;
+                self.update_execution_state(
+                    &execution_id,
+                    WorkflowExecutionState::Cancelled,
                     None,
-                ).await;
+                    Some(elapsed),
+                ).await?;
+                return Ok(());
             }
-        });
 
-        // 6. Return execution ID immediately
-        Ok(execution_id)
+            let mut context = self.create_execution(
+                &parent.workflow_id,
+                parent.environment_id.clone(),
+                ExecutionLinks {
+                    parent_execution_id: Some(execution_id.clone()),
+                    dataset_row: Some(index as i32),
+                    cancellation: Some(parent.cancellation.clone()),
+                    ..Default::default()
+                },
+            )?;
+            context.trigger_payload = parent.trigger_payload.clone();
+            context.variables = row;
+
+            // Failures are recorded on the row's own execution
+            let _ = self.run_workflow(graph, &mut context).await;
+
+            {
+                let db = self.app_handle.db();
+                let row_execution = db.get_workflow_execution(&context.execution_id)?;
+                if row_execution.state != WorkflowExecutionState::Completed {
+                    failed += 1;
+                }
+                let mut execution = db.get_workflow_execution(&execution_id)?;
+                execution.assertions_passed += row_execution.assertions_passed;
+                execution.assertions_failed += row_execution.assertions_failed;
+                db.upsert(&execution, &UpdateSource::Background)?;
+            }
+        }
+
+        let elapsed = start_time.elapsed().as_millis() as i32;
+        let (state, error) = match failed {
+            0 => (WorkflowExecutionState::Completed, None),
+            n => (WorkflowExecutionState::Failed, Some(format!("{} of {} dataset rows failed", n, total))),
+        };
+        self.update_execution_state(&execution_id, state, error, Some(elapsed)).await
     }
 
-    /// Main execution loop
-    async fn run_workflow(
+    /// Run the iterations of a load test on `config.concurrency` workers, then record the
+    /// final summary
+    async fn run_load_test(
         &self,
-        execution_id: String,
-        workflow_id: String,
         graph: ExecutionGraph,
-        environment_id: Option<String>,
+        load_test: WorkflowLoadTest,
+        config: LoadTestConfig,
+        dataset: Option<Vec<DatasetRow>>,
+        cancellation: CancellationToken,
+    ) {
+        let graph = Arc::new(graph);
+        let load_test = Arc::new(load_test);
+        let dataset = Arc::new(dataset.unwrap_or_default());
+        let stats = Arc::new(Mutex::new(LoadTestStats::start()));
+        let next_iteration = Arc::new(AtomicU32::new(0));
+
+        let mut workers = JoinSet::new();
+        for worker in 0..config.concurrency {
+            let orchestrator = self.clone();
+            let graph = Arc::clone(&graph);
+            let load_test = Arc::clone(&load_test);
+            let dataset = Arc::clone(&dataset);
+            let stats = Arc::clone(&stats);
+            let next_iteration = Arc::clone(&next_iteration);
+            let cancellation = cancellation.clone();
+            let delay = config.start_delay(worker);
+            let iterations = config.iterations;
+
+            workers.spawn(async move {
+                tokio::select! {
+                    _ = tokio::time::sleep(delay) => {}
+                    _ = cancellation.cancelled() => {}
+                }
+                while !cancellation.is_cancelled() {
+                    let index = next_iteration.fetch_add(1, Ordering::SeqCst);
+                    if index >= iterations {
+                        break;
+                    }
+                    let row = match dataset.len() {
+                        0 => DatasetRow::new(),
+                        n => dataset[index as usize % n].clone(),
+                    };
+                    let result = orchestrator
+                        .run_load_test_iteration(&graph, &load_test, row, &stats, &cancellation)
+                        .await;
+                    if result.is_err() {
+                        // Stop the other workers too, the error is recorded on the load test
+                        cancellation.cancel();
+                        return result;
+                    }
+                }
+                Ok(())
+            });
+        }
+
+        let mut error = None;
+        while let Some(joined) = workers.join_next().await {
+            match joined {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => {
+                    error.get_or_insert(e.to_string());
+                }
+                Err(e) => {
+                    error.get_or_insert(format!("Load test worker failed: {}", e));
+                }
+            }
+        }
+
+        let state = match (&error, cancellation.is_cancelled()) {
+            (Some(_), _) => WorkflowExecutionState::Failed,
+            (None, true) => WorkflowExecutionState::Cancelled,
+            (None, false) => WorkflowExecutionState::Completed,
+        };
+        let stats = stats.lock().await;
+        if let Err(e) = self.update_load_test(&load_test.id, &stats, &graph, Some((state, error))) {
+            eprintln!("Failed to record load test results: {}", e);
+        }
+    }
+
+    /// Run one iteration of a load test, add its timings to `stats` and delete its execution
+    async fn run_load_test_iteration(
+        &self,
+        graph: &ExecutionGraph,
+        load_test: &WorkflowLoadTest,
+        row: DatasetRow,
+        stats: &Mutex<LoadTestStats>,
+        cancellation: &CancellationToken,
     ) -> Result<()> {
+        // Linking the runs to the load test keeps them out of the execution history
+        let mut context = self.create_execution(
+            &load_test.workflow_id,
+            load_test.environment_id.clone(),
+            ExecutionLinks {
+                load_test_id: Some(load_test.id.clone()),
+                cancellation: Some(cancellation.clone()),
+                ..Default::default()
+            },
+        )?;
+        context.variables = row;
+
         let start_time = Instant::now();
+        // Failures are recorded on the run's own execution
+        let _ = self.run_workflow(graph, &mut context).await;
+        let elapsed = start_time.elapsed().as_millis() as u64;
+
+        // Runs interrupted by cancelling the load test would skew the numbers
+        if cancellation.is_cancelled() {
+            return Self::discard_execution(&self.app_handle.db(), &context.execution_id);
+        }
+
+        let (succeeded, samples) = {
+            let db = self.app_handle.db();
+            let execution = db.get_workflow_execution(&context.execution_id)?;
+            let samples: Vec<NodeSample> = db
+                .get_workflow_node_executions(&context.execution_id)?
+                .into_iter()
+                .filter(|n| matches!(n.state, NodeExecutionState::Completed | NodeExecutionState::Failed))
+                .map(|n| NodeSample {
+                    ttfb: match (n.response_model.as_deref(), n.response_id.as_deref()) {
+                        (Some("http_response"), Some(id)) => {
+                            db.get_http_response(id).ok().map(|r| r.elapsed_headers.max(0) as u64)
+                        }
+                        _ => None,
+                    },
+                    elapsed: n.elapsed.unwrap_or_default().max(0) as u64,
+                    failed: n.state == NodeExecutionState::Failed,
+                    node_id: n.workflow_node_id,
+                })
+                .collect();
+            (execution.state == WorkflowExecutionState::Completed, samples)
+        };
+
+        let keep = {
+            let mut stats = stats.lock().await;
+            stats.record_iteration(elapsed, succeeded, samples);
+            let keep = !succeeded && stats.keep_failed_sample(&context.execution_id);
+            if stats.progress_due() {
+                self.update_load_test(&load_test.id, &stats, graph, None)?;
+            }
+            keep
+        };
+
+        if !keep {
+            Self::discard_execution(&self.app_handle.db(), &context.execution_id)?;
+        }
+        Ok(())
+    }
+
+    /// Record the totals of a load test so far, along with its final state once it finished
+    fn update_load_test(
+        &self,
+        load_test_id: &str,
+        stats: &LoadTestStats,
+        graph: &ExecutionGraph,
+        finished: Option<(WorkflowExecutionState, Option<String>)>,
+    ) -> Result<()> {
+        let db = self.app_handle.db();
+        let mut load_test = db.get_workflow_load_test(load_test_id)?;
+        load_test.completed = stats.completed() as i32;
+        load_test.failed = stats.failed() as i32;
+        load_test.summary = Some(serde_json::to_value(stats.summary(graph))?);
+        if let Some((state, error)) = finished {
+            load_test.state = state;
+            load_test.error = error;
+            load_test.elapsed = Some(stats.elapsed().as_millis() as i32);
+        }
+        db.upsert(&load_test, &UpdateSource::Background)?;
+        Ok(())
+    }
+
+    /// Delete an execution along with everything its nodes created: responses, connections and
+    /// the runs of sub-workflows
+    fn discard_execution(db: &DbContext, execution_id: &str) -> Result<()> {
+        let source = &UpdateSource::Background;
+        for node_execution in db.get_workflow_node_executions(execution_id)? {
+            let Some(response_id) = node_execution.response_id.as_deref() else {
+                continue;
+            };
+            // Responses may already be gone, pruned from the history of their request
+            match node_execution.response_model.as_deref() {
+                Some("http_response") => {
+                    if let Ok(response) = db.get_http_response(response_id) {
+                        db.delete_http_response(&response, source)?;
+                    }
+                }
+                Some("grpc_connection") => {
+                    let _ = db.delete_grpc_connection_by_id(response_id, source);
+                }
+                Some("websocket_connection") => {
+                    let _ = db.delete_websocket_connection_by_id(response_id, source);
+                }
+                Some("workflow_execution") => Self::discard_execution(db, response_id)?,
+                _ => {}
+            }
+        }
+
+        let execution = db.get_workflow_execution(execution_id)?;
+        db.delete_workflow_execution(&execution, source)?;
+        Ok(())
+    }
+
+    /// Main execution loop
+    async fn run_workflow(&self, graph: &ExecutionGraph, context: &mut ExecutionContext) -> Result<()> {
+        let start_time = Instant::now();
+        let execution_id = context.execution_id.clone();
 
         // Update state to Running
         self.update_execution_state(
@@ -116,39 +635,42 @@ impl<R: Runtime> WorkflowOrchestrator<R> {
             None,
         ).await?;
 
-        // Initialize execution context
-        let mut context = ExecutionContext::new(
-            workflow_id.clone(),
-            execution_id.clone(),
-            environment_id,
-        );
-
         // Execute steps in order
+        let mut result = Ok(());
         for step in &graph.execution_order {
-            // Check cancellation
-            if self.is_cancelled(&execution_id) {
-                self.update_execution_state(
-                    &execution_id,
-                    WorkflowExecutionState::Cancelled,
-                    None,
-                    None,
-                ).await?;
-                return Ok(());
+            if context.cancellation.is_cancelled() {
+                break;
             }
-
-            // Execute step
-            if let Err(e) = self.execute_step(step, &graph, &mut context).await {
-                let elapsed = start_time.elapsed().as_millis() as i32;
-                self.update_execution_state(
-                    &execution_id,
-                    WorkflowExecutionState::Failed,
-                    Some(e.to_string()),
-                    Some(elapsed),
-                ).await?;
-                return Err(e);
+            result = self.execute_step(step, graph, context).await;
+            if result.is_err() {
+                break;
             }
         }
 
+        // Nodes that were still running when the run got cancelled never finished
+        if context.cancellation.is_cancelled() {
+            let elapsed = start_time.elapsed().as_millis() as i32;
+            self.skip_unfinished_nodes(&execution_id, None, CANCELLED)?;
+            self.update_execution_state(
+                &execution_id,
+                WorkflowExecutionState::Cancelled,
+                None,
+                Some(elapsed),
+            ).await?;
+            return Ok(());
+        }
+
+        if let Err(e) = result {
+            let elapsed = start_time.elapsed().as_millis() as i32;
+            self.update_execution_state(
+                &execution_id,
+                WorkflowExecutionState::Failed,
+                Some(e.to_string()),
+                Some(elapsed),
+            ).await?;
+            return Err(e);
+        }
+
         // Mark as completed
         let elapsed = start_time.elapsed().as_millis() as i32;
         self.update_execution_state(
@@ -173,8 +695,8 @@ impl<R: Runtime> WorkflowOrchestrator<R> {
                 ExecutionStep::Sequential { node_id } => {
                     self.execute_node(node_id, graph, context).await
                 }
-                ExecutionStep::Parallel { node_ids } => {
-                    self.execute_parallel(node_ids, graph, context).await
+                ExecutionStep::Parallel { node_id, node_ids } => {
+                    self.execute_parallel(node_id, node_ids, graph, context).await
                 }
                 ExecutionStep::Conditional { node_id, true_branch, false_branch } => {
                     self.execute_conditional(node_id, true_branch, false_branch, graph, context).await
@@ -186,7 +708,7 @@ impl<R: Runtime> WorkflowOrchestrator<R> {
         })
     }
 
-    /// Execute single node
+    /// Execute single node, applying its retry, timeout and error handling policy
     async fn execute_node(
         &self,
         node_id: &str,
@@ -201,8 +723,132 @@ impl<R: Runtime> WorkflowOrchestrator<R> {
             return Ok(());
         }
 
+        if context.cancellation.is_cancelled() {
+            return Err(cancelled_error());
+        }
+
+        // Nodes that completed in the run being resumed aren't run again
+        let loop_iteration = context.loop_stack.last().map(|l| l.index as i32);
+        if let Some(previous) = context.reused.get(&(node.id.clone(), loop_iteration)).cloned() {
+            return self.reuse_node_result(node, previous, context);
+        }
+
+        let policy = NodePolicy::from_config(&node.config);
+        let mut attempt = 1;
+        loop {
+            let err = match self.execute_node_attempt(node, graph, context, attempt, &policy).await {
+                Ok(()) => return Ok(()),
+                Err(e) => e,
+            };
+
+            if context.cancellation.is_cancelled() {
+                return Err(err);
+            }
+
+            if attempt <= policy.max_retries {
+                tokio::select! {
+                    _ = tokio::time::sleep(policy.backoff(attempt)) => {}
+                    _ = context.cancellation.cancelled() => return Err(cancelled_error()),
+                }
+                attempt += 1;
+                continue;
+            }
+
+            if !policy.continue_on_error {
+                return Err(err);
+            }
+
+            // Expose the failure as the node's output so later nodes can branch on it
+            warn!("Continuing after node {} failed: {}", node.id, err);
+            context.node_results.insert(node.id.clone(), NodeResult {
+                node_id: node.id.clone(),
+                node_name: node.name.clone(),
+                output: json!({ "error": err.to_string(), "attempts": attempt }),
+                elapsed: 0,
+                loop_results: None,
+                parallel_results: None,
+            });
+            return Ok(());
+        }
+    }
+
+    /// Record a node execution of an earlier run as part of this one, and expose its output
+    fn reuse_node_result(
+        &self,
+        node: &WorkflowNode,
+        previous: WorkflowNodeExecution,
+        context: &mut ExecutionContext,
+    ) -> Result<()> {
+        let output = previous.result.clone().unwrap_or(JsonValue::Null);
+        let elapsed = previous.elapsed.unwrap_or_default();
+
+        {
+            let db = self.app_handle.db();
+            db.upsert(
+                &WorkflowNodeExecution {
+                    id: generate_prefixed_id("wne"),
+                    created_at: chrono::Utc::now().naive_utc(),
+                    updated_at: chrono::Utc::now().naive_utc(),
+                    workflow_execution_id: context.execution_id.clone(),
+                    // The response belongs to the earlier run, and goes away along with it
+                    response_id: None,
+                    response_model: None,
+                    ..previous
+                },
+                &UpdateSource::Background,
+            )?;
+
+            // Carry over the assertion outcomes, so the totals cover the whole run
+            if node.node_subtype == "assertion" {
+                let count = |key: &str| output.get(key).and_then(|v| v.as_i64()).unwrap_or_default();
+                let mut execution = db.get_workflow_execution(&context.execution_id)?;
+                execution.assertions_passed += count("passed") as i32;
+                execution.assertions_failed += count("failed") as i32;
+                db.upsert(&execution, &UpdateSource::Background)?;
+            }
+        }
+
+        // Variables set in the earlier run are needed by the nodes that run again
+        if node.node_subtype == "set_variable" {
+            if let Some(variables) = output.get("variables").and_then(|v| v.as_object()) {
+                for (name, value) in variables {
+                    if let Some(value) = value.as_str() {
+                        context.variables.insert(name.clone(), value.to_string());
+                    }
+                }
+            }
+        }
+
+        context.node_results.insert(node.id.clone(), NodeResult {
+            node_id: node.id.clone(),
+            node_name: node.name.clone(),
+            output,
+            elapsed,
+            loop_results: None,
+            parallel_results: None,
+        });
+
+        let _ = self.app_handle.emit("workflow_node_completed", json!({
+            "executionId": context.execution_id,
+            "nodeId": node.id,
+            "state": "completed",
+            "reused": true,
+        }));
+
+        Ok(())
+    }
+
+    /// Run a single attempt of a node, recording it as its own node execution
+    async fn execute_node_attempt(
+        &self,
+        node: &WorkflowNode,
+        graph: &ExecutionGraph,
+        context: &mut ExecutionContext,
+        attempt: u32,
+        policy: &NodePolicy,
+    ) -> Result<()> {
         // Create node execution record
-        let node_execution = WorkflowNodeExecution {
+        let mut node_execution = WorkflowNodeExecution {
             id: generate_prefixed_id("wne"),
             model: "workflow_node_execution".to_string(),
             created_at: chrono::Utc::now().naive_utc(),
@@ -215,6 +861,9 @@ impl<R: Runtime> WorkflowOrchestrator<R> {
             error: None,
             result: None,
             loop_iteration: context.loop_stack.last().map(|l| l.index as i32),
+            response_id: None,
+            response_model: None,
+            attempt: attempt as i32,
         };
 
         self.app_handle.db().upsert(&node_execution, &UpdateSource::Background)?;
@@ -223,38 +872,79 @@ impl<R: Runtime> WorkflowOrchestrator<R> {
         let _ = self.app_handle.emit("workflow_node_started", json!({
             "executionId": context.execution_id,
             "nodeId": node.id,
+            "attempt": attempt,
         }));
 
         let start_time = Instant::now();
 
         // Execute based on node subtype
-        let result = match node.node_subtype.as_str() {
-            "manual_trigger" | "webhook_trigger" | "timer_trigger" => {
-                // Triggers just pass through
-                Ok(json!({}))
-            }
-            "http_request" => {
-                self.execute_http_request(node, context).await
-            }
-            "grpc_request" => {
-                self.execute_grpc_request(node, context).await
-            }
-            "email" => {
-                self.execute_email(node, context).await
-            }
-            "database" => {
-                self.execute_database(node, context).await
-            }
-            "websocket" => {
-                self.execute_websocket(node, context).await
+        let run = async {
+            match node.node_subtype.as_str() {
+                "manual_trigger" | "webhook_trigger" | "timer_trigger" => {
+                    // Triggers just pass through whatever started the run
+                    Ok(context.trigger_payload.clone().unwrap_or(json!({})))
+                }
+                "http_request" => {
+                    self.execute_http_request(node, context, &mut node_execution).await
+                }
+                "grpc_request" => {
+                    self.execute_grpc_request(node, context, &mut node_execution).await
+                }
+                "email" => {
+                    self.execute_email(node, context).await
+                }
+                "database" => {
+                    self.execute_database(node, context).await
+                }
+                "websocket" => {
+                    self.execute_websocket(node, context, &mut node_execution).await
+                }
+                "assertion" => {
+                    let last_attempt = attempt > policy.max_retries;
+                    self.execute_assertion(node, graph, context, &mut node_execution, last_attempt)
+                        .await
+                }
+                "sub_workflow" => {
+                    self.execute_sub_workflow(node, context, &mut node_execution).await
+                }
+                "set_variable" => {
+                    self.execute_set_variable(node, graph, context).await
+                }
+                _ => {
+                    Err(Error::GenericError(format!("Unknown node subtype: {}", node.node_subtype)))
+                }
             }
-            _ => {
-                Err(Error::GenericError(format!("Unknown node subtype: {}", node.node_subtype)))
+        };
+        let result = match policy.timeout() {
+            Some(timeout) => {
+                let timed = tokio::time::timeout(timeout, run).await;
+                match timed {
+                    Ok(r) => r,
+                    Err(_) => {
+                        let error = format!("Node timed out after {}ms", timeout.as_millis());
+                        // The node was dropped mid-flight, so nothing closed what it started
+                        self.close_in_flight(&node_execution, &error).await?;
+                        Err(Error::GenericError(error))
+                    }
+                }
             }
+            None => run.await,
         };
 
         let elapsed = start_time.elapsed().as_millis() as i32;
 
+        // Whatever the node returned, it didn't get to finish
+        if context.cancellation.is_cancelled() {
+            let updated_execution = WorkflowNodeExecution {
+                elapsed: Some(elapsed),
+                state: NodeExecutionState::Skipped,
+                error: Some(CANCELLED.to_string()),
+                ..node_execution
+            };
+            self.app_handle.db().upsert(&updated_execution, &UpdateSource::Background)?;
+            return Err(cancelled_error());
+        }
+
         // Update node execution with result
         match result {
             Ok(output) => {
@@ -269,6 +959,7 @@ impl<R: Runtime> WorkflowOrchestrator<R> {
                 // Store result in context
                 context.node_results.insert(node.id.clone(), NodeResult {
                     node_id: node.id.clone(),
+                    node_name: node.name.clone(),
                     output: output.clone(),
                     elapsed,
                     loop_results: None,
@@ -296,6 +987,8 @@ impl<R: Runtime> WorkflowOrchestrator<R> {
                     "executionId": context.execution_id,
                     "nodeId": node.id,
                     "error": e.to_string(),
+                    "attempt": attempt,
+                    "willRetry": attempt <= policy.max_retries,
                 }));
 
                 Err(e)
@@ -304,52 +997,522 @@ impl<R: Runtime> WorkflowOrchestrator<R> {
     }
 
     /// Execute HTTP request node
+    ///
+    /// The node either references a saved request via `request_id` (optionally overriding
+    /// `method`, `url`, `headers` and `body`) or describes the request inline. Either way it
+    /// goes through the regular send pipeline so environments, cookies, auth, proxy and TLS
+    /// settings all apply, and the resulting response is linked to the node execution.
     async fn execute_http_request(
         &self,
         node: &WorkflowNode,
         context: &ExecutionContext,
+        node_execution: &mut WorkflowNodeExecution,
     ) -> Result<JsonValue> {
-        // 1. Render template variables in config
-        let rendered_config = self.render_node_config(&node.config, context)?;
+        let rendered_config = self.render_node_config(&node.config, context).await?;
+
+        let request = self.build_http_request(node, &rendered_config)?;
+
+        let (environment, cookie_jar, response) = {
+            let db = self.app_handle.db();
+
+            let environment = match &context.environment_id {
+                Some(id) => Some(db.get_environment(id)?),
+                None => None,
+            };
+
+            let cookie_jar = match rendered_config.get("cookie_jar_id").and_then(|v| v.as_str()) {
+                Some(id) if !id.is_empty() => Some(db.get_cookie_jar(id)?),
+                _ => db.list_cookie_jars(&request.workspace_id)?.into_iter().next(),
+            };
+
+            let response = db.upsert_http_response(
+                &HttpResponse {
+                    request_id: request.id.clone(),
+                    workspace_id: request.workspace_id.clone(),
+                    ..Default::default()
+                },
+                &UpdateSource::Background,
+            )?;
+
+            // Link the response before sending so it can be followed while in flight
+            node_execution.response_id = Some(response.id.clone());
+            node_execution.response_model = Some("http_response".to_string());
+            db.upsert(&*node_execution, &UpdateSource::Background)?;
+
+            (environment, cookie_jar, response)
+        };
 
-        // 2. Extract HTTP request params
-        let method = rendered_config.get("method")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| Error::GenericError("Missing 'method' in HTTP request config".to_string()))?;
+        // Cancelling the response only stops this request, cancelling the run stops it too
+        let cancellation = context.cancellation.child();
+        let mut cancel_rx = cancellation.subscribe();
+        let cancel_event_id =
+            self.app_handle.listen_any(format!("cancel_http_response_{}", response.id), move |_| {
+                cancellation.cancel();
+            });
+
+        let result = send_http_request_with_context(
+            &self.app_handle,
+            &PluginWindowContext::None,
+            &UpdateSource::Background,
+            &request,
+            &response,
+            environment,
+            cookie_jar,
+            &mut cancel_rx,
+        )
+        .await;
+        self.app_handle.unlisten(cancel_event_id);
+
+        let response = match result {
+            Ok(r) => r,
+            Err(e) => {
+                let db = self.app_handle.db();
+                let resp = db.get_http_response(&response.id)?;
+                db.upsert_http_response(
+                    &HttpResponse {
+                        state: HttpResponseState::Closed,
+                        error: Some(e.to_string()),
+                        ..resp
+                    },
+                    &UpdateSource::Background,
+                )?;
+                return Err(Error::GenericError(format!("HTTP request failed: {}", e)));
+            }
+        };
+
+        if let Some(e) = &response.error {
+            return Err(Error::GenericError(format!("HTTP request failed: {}", e)));
+        }
+
+        let body = match &response.body_path {
+            Some(p) => {
+                let bytes = tokio::fs::read(p)
+                    .await
+                    .map_err(|e| Error::GenericError(format!("Failed to read response body: {}", e)))?;
+                let text = String::from_utf8_lossy(&bytes).to_string();
+                // Expose JSON bodies as structured values so later nodes can reference fields
+                serde_json::from_str::<JsonValue>(&text).unwrap_or(JsonValue::String(text))
+            }
+            None => JsonValue::Null,
+        };
+
+        let headers: serde_json::Map<String, JsonValue> = response
+            .headers
+            .iter()
+            .map(|h| (h.name.to_lowercase(), JsonValue::String(h.value.clone())))
+            .collect();
+
+        Ok(json!({
+            "responseId": response.id,
+            "status": response.status,
+            "statusText": response.status_reason,
+            "headers": headers,
+            "body": body,
+            "elapsed": response.elapsed,
+            "url": response.url,
+        }))
+    }
+
+    /// Build the request to send for an HTTP request node
+    fn build_http_request(&self, node: &WorkflowNode, config: &JsonValue) -> Result<HttpRequest> {
+        let db = self.app_handle.db();
+
+        let saved_id =
+            config.get("request_id").and_then(|v| v.as_str()).filter(|id| !id.is_empty());
+        let mut request = match saved_id {
+            Some(id) => db.get_http_request(id)?,
+            None => {
+                let workflow = db.get_workflow(&node.workflow_id)?;
+                HttpRequest {
+                    id: db
+                        .get_workflow_node_http_request(&node.id)
+                        .map(|r| r.id)
+                        .unwrap_or_default(),
+                    workspace_id: workflow.workspace_id,
+                    workflow_node_id: Some(node.id.clone()),
+                    name: node.name.clone(),
+                    ..Default::default()
+                }
+            }
+        };
+
+        if let Some(method) = config.get("method").and_then(|v| v.as_str()) {
+            if !method.is_empty() {
+                request.method = method.to_string();
+            }
+        }
+
+        if let Some(url) = config.get("url").and_then(|v| v.as_str()) {
+            if !url.is_empty() {
+                request.url = url.to_string();
+            }
+        }
+
+        if let Some(headers) = config.get("headers").and_then(|v| v.as_array()) {
+            if !headers.is_empty() {
+                request.headers = headers
+                    .iter()
+                    .filter_map(|h| serde_json::from_value::<HttpRequestHeader>(h.clone()).ok())
+                    .collect();
+            }
+        }
+
+        if let Some(body) = config.get("body").and_then(|v| v.as_str()) {
+            if !body.is_empty() {
+                let body_type = config
+                    .get("body_type")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("text/plain");
+                request.body_type = Some(body_type.to_string());
+                request.body = BTreeMap::from([("text".to_string(), json!(body))]);
+            }
+        }
+
+        if request.url.is_empty() {
+            return Err(Error::GenericError("Missing 'url' in HTTP request config".to_string()));
+        }
+
+        if request.method.is_empty() {
+            request.method = "GET".to_string();
+        }
+
+        if saved_id.is_none() {
+            request = db.upsert_http_request(&request, &UpdateSource::Background)?;
+        }
+
+        Ok(request)
+    }
+
+    /// Execute gRPC request node
+    ///
+    /// Like HTTP nodes, this either references a saved `GrpcRequest` via `request_id` or
+    /// describes the call inline with `url`, `service`, `method` and `message`. Only unary
+    /// methods are supported. The call is recorded as a regular connection with events.
+    async fn execute_grpc_request(
+        &self,
+        node: &WorkflowNode,
+        context: &ExecutionContext,
+        node_execution: &mut WorkflowNodeExecution,
+    ) -> Result<JsonValue> {
+        let rendered_config = self.render_node_config(&node.config, context).await?;
+
+        let unrendered_request = self.build_grpc_request(node, &rendered_config)?;
+        let (resolved_request, auth_context_id) =
+            resolve_grpc_request(&self.app_handle, &unrendered_request)
+            .map_err(|e| Error::GenericError(e.to_string()))?;
+
+        let (environment_chain, workspace, proto_files) = {
+            let db = self.app_handle.db();
+            let environment_chain = db.resolve_environments(
+                &unrendered_request.workspace_id,
+                unrendered_request.folder_id.as_deref(),
+                context.environment_id.as_deref(),
+            )?;
+            let workspace = db.get_workspace(&unrendered_request.workspace_id)?;
+
+            // Fall back to the proto files selected for the saved request in the UI
+            let proto_files: Vec<String> = match rendered_config.get("proto_files") {
+                Some(v) => serde_json::from_value(v.clone()).unwrap_or_default(),
+                None => db
+                    .get_key_value_raw("global", &format!("proto_files::{}", unrendered_request.id))
+                    .and_then(|kv| serde_json::from_str(&kv.value).ok())
+                    .unwrap_or_default(),
+            };
+
+            (environment_chain, workspace, proto_files)
+        };
+
+        let cb = PluginTemplateCallback::new(
+            &self.app_handle,
+            &PluginWindowContext::None,
+            RenderPurpose::Send,
+        );
+        let opt = RenderOptions {
+            error_behavior: RenderErrorBehavior::Throw,
+        };
+
+        let request = render_grpc_request(&resolved_request, environment_chain, &cb, &opt)
+            .await
+            .map_err(|e| Error::GenericError(e.to_string()))?;
+
+        // A message from the config was rendered along with it, only a saved one still needs it
+        let message = match grpc_config_message(&rendered_config) {
+            Some(message) => message,
+            None if request.message.is_empty() => "{}".to_string(),
+            None => self.render_template(&request.message, context).await?,
+        };
+
+        let metadata =
+            build_metadata(&self.app_handle, &PluginWindowContext::None, &request, &auth_context_id)
+                .await
+                .map_err(|e| Error::GenericError(e.to_string()))?;
+
+        let (service, method) = match (request.service.clone(), request.method.clone()) {
+            (Some(service), Some(method)) if !service.is_empty() && !method.is_empty() => {
+                (service, method)
+            }
+            _ => return Err(Error::GenericError("Service and method are required".to_string())),
+        };
+
+        let conn = {
+            let db = self.app_handle.db();
+            let conn = db.upsert_grpc_connection(
+                &GrpcConnection {
+                    workspace_id: request.workspace_id.clone(),
+                    request_id: request.id.clone(),
+                    status: -1,
+                    elapsed: 0,
+                    state: GrpcConnectionState::Initialized,
+                    service: service.clone(),
+                    method: method.clone(),
+                    url: request.url.clone(),
+                    ..Default::default()
+                },
+                &UpdateSource::Background,
+            )?;
+
+            node_execution.response_id = Some(conn.id.clone());
+            node_execution.response_model = Some("grpc_connection".to_string());
+            db.upsert(&*node_execution, &UpdateSource::Background)?;
+
+            conn
+        };
+
+        let call = GrpcUnaryCall {
+            base_event: GrpcEvent {
+                workspace_id: request.workspace_id.clone(),
+                request_id: request.id.clone(),
+                connection_id: conn.id.clone(),
+                ..Default::default()
+            },
+            request,
+            service,
+            method,
+            message,
+            metadata,
+            proto_files,
+            workspace,
+        };
+
+        let start = Instant::now();
+        let result = tokio::select! {
+            r = self.send_grpc_unary(&call) => r,
+            _ = context.cancellation.cancelled() => {
+                Err((Code::Cancelled, "Request was cancelled".to_string()))
+            }
+        };
+
+        let (status, error) = match &result {
+            Ok(_) => (Code::Ok as i32, None),
+            Err((code, e)) => (*code as i32, Some(e.clone())),
+        };
+
+        self.app_handle.db().upsert_grpc_event(
+            &GrpcEvent {
+                content: match &error {
+                    None => "Connection complete".to_string(),
+                    Some(_) => "Failed to connect".to_string(),
+                },
+                error: error.clone(),
+                event_type: GrpcEventType::ConnectionEnd,
+                status: Some(status),
+                ..call.base_event.clone()
+            },
+            &UpdateSource::Background,
+        )?;
+
+        let elapsed = start.elapsed().as_millis() as i32;
+        self.app_handle.db().upsert_grpc_connection(
+            &GrpcConnection {
+                elapsed,
+                status,
+                error: error.clone(),
+                state: GrpcConnectionState::Closed,
+                ..conn.clone()
+            },
+            &UpdateSource::Background,
+        )?;
+
+        let (message, response_metadata) = result.map_err(|(code, e)| {
+            Error::GenericError(format!("gRPC request failed ({:?}): {}", code, e))
+        })?;
+
+        Ok(json!({
+            "connectionId": conn.id,
+            "status": status,
+            "service": call.service,
+            "method": call.method,
+            "message": message,
+            "metadata": response_metadata,
+            "elapsed": elapsed,
+        }))
+    }
+
+    /// Connect and perform a unary gRPC call, recording events along the way
+    async fn send_grpc_unary(
+        &self,
+        call: &GrpcUnaryCall,
+    ) -> std::result::Result<(JsonValue, BTreeMap<String, String>), (Code, String)> {
+        let GrpcUnaryCall {
+            request,
+            service,
+            method,
+            message,
+            metadata,
+            base_event,
+            ..
+        } = call;
+        let db_err = |e: Error| (Code::Internal, e.to_string());
+
+        self.app_handle
+            .db()
+            .upsert_grpc_event(
+                &GrpcEvent {
+                    content: format!("Connecting to {}", request.url),
+                    event_type: GrpcEventType::ConnectionStart,
+                    metadata: metadata.clone(),
+                    ..base_event.clone()
+                },
+                &UpdateSource::Background,
+            )
+            .map_err(db_err)?;
+
+        let grpc_handle = self.app_handle.state::<Mutex<GrpcHandle>>();
+        let connection = grpc_handle
+            .lock()
+            .await
+            .connect(
+                &request.id,
+                safe_uri(&request.url).as_str(),
+                &call.proto_files.iter().map(PathBuf::from).collect(),
+                metadata,
+                &TlsOptions::from(&call.workspace),
+                &DnsResolver::from(&call.workspace),
+            )
+            .await
+            .map_err(|e| (Code::Unavailable, e))?;
+
+        let method_desc =
+            connection.method(service, method).map_err(|e| (Code::Unimplemented, e))?;
+        if method_desc.is_client_streaming() || method_desc.is_server_streaming() {
+            return Err((
+                Code::Unimplemented,
+                "Only unary gRPC methods are supported in workflows".to_string(),
+            ));
+        }
+
+        self.app_handle
+            .db()
+            .upsert_grpc_event(
+                &GrpcEvent {
+                    content: message.clone(),
+                    event_type: GrpcEventType::ClientMessage,
+                    ..base_event.clone()
+                },
+                &UpdateSource::Background,
+            )
+            .map_err(db_err)?;
+
+        let response = connection.unary(service, method, message, metadata).await.map_err(|e| {
+            match e.status {
+                Some(s) => (s.code(), s.message().to_string()),
+                None => (Code::Unknown, e.message),
+            }
+        })?;
+
+        let response_metadata = metadata_to_map(response.metadata().clone());
+        let content = serialize_message(&response.into_inner()).map_err(|e| (Code::Internal, e))?;
+
+        self.app_handle
+            .db()
+            .upsert_grpc_event(
+                &GrpcEvent {
+                    metadata: response_metadata.clone(),
+                    content: if response_metadata.is_empty() {
+                        "Received response"
+                    } else {
+                        "Received response with metadata"
+                    }
+                    .to_string(),
+                    event_type: GrpcEventType::Info,
+                    ..base_event.clone()
+                },
+                &UpdateSource::Background,
+            )
+            .map_err(db_err)?;
+        self.app_handle
+            .db()
+            .upsert_grpc_event(
+                &GrpcEvent {
+                    content: content.clone(),
+                    event_type: GrpcEventType::ServerMessage,
+                    ..base_event.clone()
+                },
+                &UpdateSource::Background,
+            )
+            .map_err(db_err)?;
+
+        let message = serde_json::from_str(&content).unwrap_or(JsonValue::String(content));
+        Ok((message, response_metadata))
+    }
+
+    /// Build the request to send for a gRPC request node
+    fn build_grpc_request(&self, node: &WorkflowNode, config: &JsonValue) -> Result<GrpcRequest> {
+        let db = self.app_handle.db();
+
+        let saved_id =
+            config.get("request_id").and_then(|v| v.as_str()).filter(|id| !id.is_empty());
+        let mut request = match saved_id {
+            Some(id) => db.get_grpc_request(id)?,
+            None => {
+                let workflow = db.get_workflow(&node.workflow_id)?;
+                GrpcRequest {
+                    id: db
+                        .get_workflow_node_grpc_request(&node.id)
+                        .map(|r| r.id)
+                        .unwrap_or_default(),
+                    workspace_id: workflow.workspace_id,
+                    workflow_node_id: Some(node.id.clone()),
+                    name: node.name.clone(),
+                    ..Default::default()
+                }
+            }
+        };
 
-        let url = rendered_config.get("url")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| Error::GenericError("Missing 'url' in HTTP request config".to_string()))?;
+        let get_str = |key: &str| {
+            config.get(key).and_then(|v| v.as_str()).filter(|v| !v.is_empty()).map(|v| v.to_string())
+        };
 
-        // 3. Build basic response (placeholder - integrate with existing HTTP logic later)
-        // TODO: Integrate with existing send_http_request logic from http_request.rs
-        Ok(json!({
-            "status": 200,
-            "statusText": "OK",
-            "headers": {},
-            "body": format!("HTTP {} {} - Integration pending", method, url),
-            "elapsed": 0,
-            "url": url,
-        }))
-    }
+        if let Some(url) = get_str("url") {
+            request.url = url;
+        }
+        if let Some(service) = get_str("service") {
+            request.service = Some(service);
+        }
+        if let Some(method) = get_str("method") {
+            request.method = Some(method);
+        }
+        if let Some(message) = grpc_config_message(config) {
+            request.message = message;
+        }
+        if let Some(metadata) = config.get("metadata").and_then(|v| v.as_array()) {
+            if !metadata.is_empty() {
+                request.metadata = metadata
+                    .iter()
+                    .filter_map(|h| serde_json::from_value::<HttpRequestHeader>(h.clone()).ok())
+                    .collect();
+            }
+        }
 
-    /// Execute gRPC request node
-    async fn execute_grpc_request(
-        &self,
-        node: &WorkflowNode,
-        context: &ExecutionContext,
-    ) -> Result<JsonValue> {
-        let rendered_config = self.render_node_config(&node.config, context)?;
+        if request.url.is_empty() {
+            return Err(Error::GenericError("Missing 'url' in gRPC request config".to_string()));
+        }
 
-        let service = rendered_config.get("service")
-            .and_then(|v| v.as_str())
-            .unwrap_or("UnknownService");
+        if saved_id.is_none() {
+            request = db.upsert_grpc_request(&request, &UpdateSource::Background)?;
+        }
 
-        // TODO: Integrate with existing gRPC execution logic
-        Ok(json!({
-            "service": service,
-            "response": "gRPC integration pending",
-        }))
+        Ok(request)
     }
 
     /// Execute email node
@@ -358,7 +1521,7 @@ impl<R: Runtime> WorkflowOrchestrator<R> {
         node: &WorkflowNode,
         context: &ExecutionContext,
     ) -> Result<JsonValue> {
-        let rendered_config = self.render_node_config(&node.config, context)?;
+        let rendered_config = self.render_node_config(&node.config, context).await?;
 
         let to = rendered_config.get("to")
             .and_then(|v| v.as_str())
@@ -383,7 +1546,7 @@ impl<R: Runtime> WorkflowOrchestrator<R> {
         node: &WorkflowNode,
         context: &ExecutionContext,
     ) -> Result<JsonValue> {
-        let rendered_config = self.render_node_config(&node.config, context)?;
+        let rendered_config = self.render_node_config(&node.config, context).await?;
 
         let query = rendered_config.get("query")
             .and_then(|v| v.as_str())
@@ -398,68 +1561,646 @@ impl<R: Runtime> WorkflowOrchestrator<R> {
     }
 
     /// Execute WebSocket node
+    ///
+    /// Opens a connection (from a saved `WebsocketRequest` or an inline `url`), sends each
+    /// scripted message in order, then collects server frames until `expect_count` frames
+    /// arrive, a frame matches the `until_match` regex, or `timeout_seconds` elapses.
     async fn execute_websocket(
         &self,
         node: &WorkflowNode,
         context: &ExecutionContext,
+        node_execution: &mut WorkflowNodeExecution,
     ) -> Result<JsonValue> {
-        let rendered_config = self.render_node_config(&node.config, context)?;
+        let rendered_config = self.render_node_config(&node.config, context).await?;
+        let request = self.build_websocket_request(node, &rendered_config)?;
+
+        let messages: Vec<String> = rendered_config
+            .get("messages")
+            .and_then(|v| v.as_array())
+            .map(|a| {
+                a.iter()
+                    .map(|m| match m {
+                        JsonValue::String(s) => s.clone(),
+                        m => m.to_string(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let expect_count = rendered_config.get("expect_count").and_then(|v| v.as_u64());
+        let until_match = match rendered_config.get("until_match").and_then(|v| v.as_str()) {
+            Some(p) if !p.is_empty() => Some(
+                Regex::new(p).map_err(|e| Error::GenericError(format!("Invalid 'until_match': {}", e)))?,
+            ),
+            _ => None,
+        };
+        let timeout_seconds =
+            rendered_config.get("timeout_seconds").and_then(|v| v.as_u64()).unwrap_or(30);
+        let cookie_jar_id = match rendered_config.get("cookie_jar_id").and_then(|v| v.as_str()) {
+            Some(id) if !id.is_empty() => Some(id.to_string()),
+            _ => self.app_handle.db().list_cookie_jars(&request.workspace_id)?.into_iter().next().map(|j| j.id),
+        };
 
-        let url = rendered_config.get("url")
-            .and_then(|v| v.as_str())
-            .unwrap_or("");
+        let ws_manager = self.app_handle.state::<Mutex<WebsocketManager>>();
+        let (events_tx, mut events_rx) = tokio::sync::mpsc::channel::<WebsocketEvent>(128);
+
+        let start = Instant::now();
+        let connection = connect_websocket(
+            &self.app_handle,
+            &mut *ws_manager.lock().await,
+            ConnectParams {
+                window_context: &PluginWindowContext::None,
+                update_source: &UpdateSource::Background,
+                unrendered_request: &request,
+                environment_id: context.environment_id.as_deref(),
+                cookie_jar_id: cookie_jar_id.as_deref(),
+                events_tx: Some(events_tx),
+            },
+        )
+        .await
+        .map_err(|e| Error::GenericError(e.to_string()))?;
+
+        node_execution.response_id = Some(connection.id.clone());
+        node_execution.response_model = Some("websocket_connection".to_string());
+        self.app_handle.db().upsert(&*node_execution, &UpdateSource::Background)?;
+
+        if matches!(connection.state, WebsocketConnectionState::Closed) {
+            return Err(Error::GenericError(format!(
+                "WebSocket connection failed: {}",
+                connection.error.unwrap_or_default()
+            )));
+        }
+
+        for message in messages {
+            send_websocket_message(
+                &self.app_handle,
+                &UpdateSource::Background,
+                &mut *ws_manager.lock().await,
+                &connection,
+                message,
+            )
+            .await
+                .map_err(|e| Error::GenericError(e.to_string()))?;
+        }
+
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(timeout_seconds);
+        let mut frames = Vec::new();
+        let mut matched = false;
+        let mut timed_out = false;
+        let mut closed = false;
+        let mut cancelled = false;
+
+        loop {
+            if expect_count.is_some_and(|c| frames.len() as u64 >= c) {
+                break;
+            }
+
+            let received = tokio::select! {
+                r = tokio::time::timeout_at(deadline, events_rx.recv()) => r,
+                _ = context.cancellation.cancelled() => {
+                    cancelled = true;
+                    break;
+                }
+            };
+            let event = match received {
+                Ok(Some(event)) => event,
+                Ok(None) => {
+                    closed = true;
+                    break;
+                }
+                Err(_) => {
+                    timed_out = true;
+                    break;
+                }
+            };
+
+            let message_type = match event.message_type {
+                WebsocketEventType::Text => "text",
+                WebsocketEventType::Binary => "binary",
+                WebsocketEventType::Close => {
+                    closed = true;
+                    break;
+                }
+                // Control frames are stored as events but aren't useful as output
+                _ => continue,
+            };
+
+            let text = String::from_utf8_lossy(&event.message).to_string();
+            let is_match = until_match.as_ref().is_some_and(|re| re.is_match(&text));
+            frames.push(json!({
+                "type": message_type,
+                "message": serde_json::from_str::<JsonValue>(&text).unwrap_or(JsonValue::String(text)),
+            }));
+
+            if is_match {
+                matched = true;
+                break;
+            }
+        }
+
+        if !closed {
+            if let Err(e) = ws_manager.lock().await.close(&connection.id).await {
+                warn!("Failed to close WebSocket connection: {e:?}");
+            }
+        }
+
+        if cancelled {
+            return Err(cancelled_error());
+        }
+
+        let waiting_for_count = expect_count.is_some_and(|c| (frames.len() as u64) < c);
+        let waiting_for_match = until_match.is_some() && !matched;
+        if (timed_out || closed) && (waiting_for_count || waiting_for_match) {
+            return Err(Error::GenericError(format!(
+                "WebSocket {} after receiving {} frames",
+                if timed_out {
+                    format!("timed out after {}s", timeout_seconds)
+                } else {
+                    "closed".to_string()
+                },
+                frames.len()
+            )));
+        }
 
-        // TODO: Integrate with existing WebSocket logic
         Ok(json!({
-            "url": url,
-            "messages": [],
-            "message": "WebSocket integration pending",
+            "connectionId": connection.id,
+            "url": connection.url,
+            "status": connection.status,
+            "frames": frames,
+            "count": frames.len(),
+            "matched": matched,
+            "timedOut": timed_out,
+            "elapsed": start.elapsed().as_millis() as i32,
         }))
     }
 
+    /// Build the request to connect with for a WebSocket node
+    fn build_websocket_request(&self, node: &WorkflowNode, config: &JsonValue) -> Result<WebsocketRequest> {
+        let db = self.app_handle.db();
+
+        let saved_id =
+            config.get("request_id").and_then(|v| v.as_str()).filter(|id| !id.is_empty());
+        let mut request = match saved_id {
+            Some(id) => db.get_websocket_request(id)?,
+            None => {
+                let workflow = db.get_workflow(&node.workflow_id)?;
+                WebsocketRequest {
+                    id: db
+                        .get_workflow_node_websocket_request(&node.id)
+                        .map(|r| r.id)
+                        .unwrap_or_default(),
+                    workspace_id: workflow.workspace_id,
+                    workflow_node_id: Some(node.id.clone()),
+                    name: node.name.clone(),
+                    ..Default::default()
+                }
+            }
+        };
+
+        if let Some(url) = config.get("url").and_then(|v| v.as_str()) {
+            // Ignore the placeholder scheme from the default config
+            if !url.is_empty() && url != "wss://" && url != "ws://" {
+                request.url = url.to_string();
+            }
+        }
+
+        if let Some(headers) = config.get("headers").and_then(|v| v.as_array()) {
+            if !headers.is_empty() {
+                request.headers = headers
+                    .iter()
+                    .filter_map(|h| serde_json::from_value::<HttpRequestHeader>(h.clone()).ok())
+                    .collect();
+            }
+        }
+
+        if request.url.is_empty() {
+            return Err(Error::GenericError("Missing 'url' in WebSocket config".to_string()));
+        }
+
+        if saved_id.is_none() {
+            request = db.upsert_websocket_request(&request, &UpdateSource::Background)?;
+        }
+
+        Ok(request)
+    }
+
     /// Execute parallel branches
     async fn execute_parallel(
         &self,
+        node_id: &str,
         node_ids: &[String],
         graph: &ExecutionGraph,
         context: &mut ExecutionContext,
     ) -> Result<()> {
-        // Spawn all branches concurrently
-        let mut handles = Vec::new();
+        let node = graph.nodes.get(node_id)
+            .ok_or_else(|| Error::GenericError(format!("Node not found: {}", node_id)))?;
+
+        let fail_fast = node.config.get("fail_fast").and_then(|v| v.as_bool()).unwrap_or(true);
+        let max_concurrency = node.config.get("max_concurrency")
+            .and_then(|v| v.as_u64())
+            .map(|n| n.max(1) as usize)
+            .unwrap_or(node_ids.len().max(1));
+
+        let start_time = Instant::now();
+        let semaphore = Arc::new(Semaphore::new(max_concurrency));
+        let mut branches = JoinSet::new();
 
-        for node_id in node_ids {
-            let node_id_str = node_id.clone();
+        for (index, branch_node_id) in node_ids.iter().enumerate() {
+            let branch_node_id = branch_node_id.clone();
             let graph = graph.clone();
             let mut branch_context = context.clone();
             let orchestrator = self.clone();
+            let semaphore = Arc::clone(&semaphore);
 
-            let handle = tauri::async_runtime::spawn(async move {
-                orchestrator.execute_node(&node_id_str, &graph, &mut branch_context).await
+            branches.spawn(async move {
+                // The semaphore is never closed, so acquiring can't fail
+                let _permit = semaphore.acquire_owned().await.expect("semaphore closed");
+                let result = orchestrator.execute_node(&branch_node_id, &graph, &mut branch_context).await;
+                (index, result.map(|_| branch_context))
             });
+        }
+
+        // Branches finish in any order, so results are slotted back by index to keep merging
+        // deterministic
+        let mut branch_contexts: Vec<Option<ExecutionContext>> = vec![None; node_ids.len()];
+        let mut errors = Vec::new();
+        while let Some(joined) = branches.join_next().await {
+            let (index, result) = match joined {
+                Ok(r) => r,
+                Err(e) => {
+                    errors.push(format!("Parallel branch panicked: {}", e));
+                    continue;
+                }
+            };
 
-            handles.push((node_id.clone(), handle));
+            match result {
+                Ok(branch_context) => branch_contexts[index] = Some(branch_context),
+                Err(e) => {
+                    errors.push(format!("Parallel branch {} failed: {}", node_ids[index], e));
+                    if fail_fast {
+                        branches.abort_all();
+                        // Wait for the aborts to land so nothing writes after we mark them
+                        while branches.join_next().await.is_some() {}
+                        self.skip_unfinished_nodes(
+                            &context.execution_id,
+                            Some(node_ids),
+                            "Cancelled because another parallel branch failed",
+                        )?;
+                        break;
+                    }
+                }
+            }
         }
 
-        // Wait for all to complete
+        // Merge in branch order, so later branches win when two change the same variable. Only
+        // values a branch actually changed are merged, so untouched copies can't clobber them.
+        let base_variables = context.variables.clone();
         let mut parallel_results = Vec::new();
-        for (node_id, handle) in handles {
-            match handle.await {
-                Ok(Ok(())) => {
-                    // Success - collect result if available
-                    if let Some(result) = context.node_results.get(&node_id) {
-                        parallel_results.push(result.output.clone());
+        for (branch_node_id, branch_context) in node_ids.iter().zip(branch_contexts) {
+            let Some(mut branch_context) = branch_context else {
+                continue;
+            };
+
+            for (name, value) in branch_context.variables {
+                if base_variables.get(&name) != Some(&value) {
+                    context.variables.insert(name, value);
+                }
+            }
+
+            if let Some(result) = branch_context.node_results.remove(branch_node_id) {
+                parallel_results.push(result.output.clone());
+                context.node_results.insert(branch_node_id.clone(), result);
+            }
+        }
+
+        context.node_results.insert(node.id.clone(), NodeResult {
+            node_id: node.id.clone(),
+            node_name: node.name.clone(),
+            output: json!(parallel_results),
+            elapsed: start_time.elapsed().as_millis() as i32,
+            loop_results: None,
+            parallel_results: Some(parallel_results),
+        });
+
+        // Branches that finished are merged above even if others failed, so their outputs and
+        // variables stay around when waiting for all branches
+        if !errors.is_empty() {
+            return Err(Error::GenericError(errors.join("; ")));
+        }
+
+        Ok(())
+    }
+
+    /// Mark node executions that were still running when their task was stopped as skipped,
+    /// limited to `node_ids` if given
+    fn skip_unfinished_nodes(
+        &self,
+        execution_id: &str,
+        node_ids: Option<&[String]>,
+        reason: &str,
+    ) -> Result<()> {
+        let db = self.app_handle.db();
+        for node_execution in db.get_workflow_node_executions(execution_id)? {
+            if node_execution.state != NodeExecutionState::Running
+                || node_ids.is_some_and(|ids| !ids.contains(&node_execution.workflow_node_id))
+            {
+                continue;
+            }
+            db.upsert(
+                &WorkflowNodeExecution {
+                    state: NodeExecutionState::Skipped,
+                    error: Some(reason.to_string()),
+                    ..node_execution
+                },
+                &UpdateSource::Background,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Close the response, connection or sub-workflow run a node execution left open when it was
+    /// stopped, the same way it would have been closed had it failed with `error`
+    fn close_in_flight<'a>(
+        &'a self,
+        node_execution: &'a WorkflowNodeExecution,
+        error: &'a str,
+    ) -> Pin<Box<dyn Future<Output = Result<()>> + Send + 'a>> {
+        Box::pin(async move {
+            let Some(id) = node_execution.response_id.as_deref() else {
+                return Ok(());
+            };
+
+            let source = &UpdateSource::Background;
+            match node_execution.response_model.as_deref() {
+                Some("http_response") => {
+                    let db = self.app_handle.db();
+                    let response = db.get_http_response(id)?;
+                    if !matches!(response.state, HttpResponseState::Closed) {
+                        db.upsert_http_response(
+                            &HttpResponse {
+                                state: HttpResponseState::Closed,
+                                error: Some(error.to_string()),
+                                ..response
+                            },
+                            source,
+                        )?;
                     }
                 }
-                Ok(Err(e)) => {
-                    return Err(Error::GenericError(format!("Parallel branch {} failed: {}", node_id, e)));
+                Some("grpc_connection") => {
+                    let db = self.app_handle.db();
+                    let connection = db.get_grpc_connection(id)?;
+                    if !matches!(connection.state, GrpcConnectionState::Closed) {
+                        db.upsert_grpc_connection(
+                            &GrpcConnection {
+                                state: GrpcConnectionState::Closed,
+                                error: Some(error.to_string()),
+                                ..connection
+                            },
+                            source,
+                        )?;
+                    }
                 }
-                Err(e) => {
-                    return Err(Error::GenericError(format!("Parallel branch {} panicked: {}", node_id, e)));
+                Some("websocket_connection") => {
+                    // The connection marks itself closed once the socket is gone
+                    let ws_manager = self.app_handle.state::<Mutex<WebsocketManager>>();
+                    if let Err(e) = ws_manager.lock().await.close(id).await {
+                        warn!("Failed to close WebSocket connection: {e:?}");
+                    }
+                }
+                Some("workflow_execution") => {
+                    let running: Vec<WorkflowNodeExecution> = self
+                        .app_handle
+                        .db()
+                        .get_workflow_node_executions(id)?
+                        .into_iter()
+                        .filter(|n| n.state == NodeExecutionState::Running)
+                        .collect();
+                    for child in &running {
+                        self.close_in_flight(child, error).await?;
+                    }
+                    self.skip_unfinished_nodes(id, None, error)?;
+                    self.update_execution_state(
+                        id,
+                        WorkflowExecutionState::Failed,
+                        Some(error.to_string()),
+                        None,
+                    )
+                    .await?;
+                }
+                _ => {}
+            }
+            Ok(())
+        })
+    }
+
+    /// Check the output of a previous node and record the outcomes on the execution
+    ///
+    /// An attempt that fails and will be retried isn't recorded, so the totals only count the
+    /// final attempt of each assertion node.
+    async fn execute_assertion(
+        &self,
+        node: &WorkflowNode,
+        graph: &ExecutionGraph,
+        context: &ExecutionContext,
+        node_execution: &mut WorkflowNodeExecution,
+        last_attempt: bool,
+    ) -> Result<JsonValue> {
+        let config = self.render_node_config(&node.config, context).await?;
+        let assertions: Vec<Assertion> = serde_json::from_value(
+            config.get("assertions").cloned().unwrap_or(json!([])),
+        )
+        .map_err(|e| Error::GenericError(format!("Invalid assertions: {}", e)))?;
+        let stop_on_failure = config.get("stop_on_failure").and_then(|v| v.as_bool()).unwrap_or(true);
+
+        let source = Self::source_result(node, &config, graph, context)
+            .ok_or_else(|| Error::GenericError("Assertion source node has not been executed".to_string()))?;
+
+        let outcomes = run_assertions(&assertions, &source.output, source.elapsed);
+        let output = summarize(&source.node_id, &outcomes);
+        let failed = outcomes.iter().filter(|o| !o.passed).count();
+        let fails_node = failed > 0 && stop_on_failure;
+
+        if !fails_node || last_attempt {
+            let db = self.app_handle.db();
+            let mut execution = db.get_workflow_execution(&context.execution_id)?;
+            execution.assertions_passed += (outcomes.len() - failed) as i32;
+            execution.assertions_failed += failed as i32;
+            db.upsert(&execution, &UpdateSource::Background)?;
+        }
+
+        if fails_node {
+            // Keep the individual outcomes on the failed node execution
+            node_execution.result = Some(output);
+            return Err(Error::GenericError(format!(
+                "{} of {} assertions failed",
+                failed,
+                outcomes.len()
+            )));
+        }
+
+        Ok(output)
+    }
+
+    /// Run another workflow of the workspace as its own execution and wait for it to finish
+    ///
+    /// Rendered `inputs` become variables of the child run, and are also the output of its
+    /// start trigger. The output is the result of each child node listed in `outputs` (by ID or
+    /// name), or of every child node if none are listed.
+    async fn execute_sub_workflow(
+        &self,
+        node: &WorkflowNode,
+        context: &ExecutionContext,
+        node_execution: &mut WorkflowNodeExecution,
+    ) -> Result<JsonValue> {
+        let config = self.render_node_config(&node.config, context).await?;
+        let workflow_id = config
+            .get("workflow_id")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .ok_or_else(|| Error::GenericError("Missing 'workflow_id' in sub-workflow node config".to_string()))?;
+        let inputs = config.get("inputs").cloned().unwrap_or(json!({}));
+
+        let graph = GraphBuilder::build(workflow_id, &self.app_handle.db())?;
+        let mut child = self.create_execution(
+            workflow_id,
+            context.environment_id.clone(),
+            ExecutionLinks {
+                caller_execution_id: Some(context.execution_id.clone()),
+                cancellation: Some(context.cancellation.clone()),
+                ..Default::default()
+            },
+        )?;
+        if let Some(inputs) = inputs.as_object() {
+            for (name, value) in inputs {
+                let value = match value {
+                    JsonValue::String(s) => s.clone(),
+                    v => v.to_string(),
+                };
+                child.variables.insert(name.clone(), value);
+            }
+        }
+        child.trigger_payload = Some(inputs);
+
+        // Link the child run so it can be opened from the node execution
+        node_execution.response_id = Some(child.execution_id.clone());
+        node_execution.response_model = Some("workflow_execution".to_string());
+        self.app_handle.db().upsert(&*node_execution, &UpdateSource::Background)?;
+
+        self.run_workflow(&graph, &mut child)
+            .await
+            .map_err(|e| Error::GenericError(format!("Sub-workflow failed: {}", e)))?;
+        let execution = self.app_handle.db().get_workflow_execution(&child.execution_id)?;
+        if execution.state != WorkflowExecutionState::Completed {
+            return Err(Error::GenericError(format!(
+                "Sub-workflow did not complete: {:?}",
+                execution.state
+            )));
+        }
+
+        let selected: Vec<String> = config
+            .get("outputs")
+            .and_then(|v| v.as_array())
+            .map(|a| a.iter().filter_map(|v| v.as_str()).map(|s| s.to_string()).collect())
+            .unwrap_or_default();
+
+        let mut output = serde_json::Map::new();
+        if selected.is_empty() {
+            for result in child.node_results.values() {
+                output.insert(result.node_name.clone(), result.output.clone());
+            }
+        } else {
+            for key in selected {
+                let result = child
+                    .node_results
+                    .get(&key)
+                    .or_else(|| child.node_results.values().find(|r| r.node_name == key))
+                    .ok_or_else(|| {
+                        Error::GenericError(format!("Sub-workflow node '{}' has no result", key))
+                    })?;
+                output.insert(key, result.output.clone());
+            }
+        }
+
+        Ok(JsonValue::Object(output))
+    }
+
+    /// Result of the node referenced by `source_node` (ID or name), or of the one feeding into
+    /// `node`
+    fn source_result<'a>(
+        node: &WorkflowNode,
+        config: &JsonValue,
+        graph: &ExecutionGraph,
+        context: &'a ExecutionContext,
+    ) -> Option<&'a NodeResult> {
+        match config.get("source_node").and_then(|v| v.as_str()).filter(|s| !s.is_empty()) {
+            Some(key) => context
+                .node_results
+                .get(key)
+                .or_else(|| context.node_results.values().find(|r| r.node_name == key)),
+            None => graph
+                .edges
+                .iter()
+                .find(|e| e.target_node_id == node.id)
+                .and_then(|e| context.node_results.get(&e.source_node_id)),
+        }
+    }
+
+    /// Capture values from the output of a previous node into run variables, so later nodes can
+    /// use them as `${[ name ]}`. With `environment_id` set they are also saved to that
+    /// environment, where manual requests pick them up after the run.
+    async fn execute_set_variable(
+        &self,
+        node: &WorkflowNode,
+        graph: &ExecutionGraph,
+        context: &mut ExecutionContext,
+    ) -> Result<JsonValue> {
+        let config = self.render_node_config(&node.config, context).await?;
+        let extractions: Vec<Extraction> = serde_json::from_value(
+            config.get("variables").cloned().unwrap_or(json!([])),
+        )
+        .map_err(|e| Error::GenericError(format!("Invalid variables: {}", e)))?;
+
+        let source = Self::source_result(node, &config, graph, context).ok_or_else(|| {
+            Error::GenericError("Set variable source node has not been executed".to_string())
+        })?;
+        let source_node_id = source.node_id.clone();
+        let values = extract_all(&extractions, &source.output).map_err(Error::GenericError)?;
+
+        let environment_id = config
+            .get("environment_id")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string());
+        if let Some(environment_id) = &environment_id {
+            let db = self.app_handle.db();
+            let mut environment = db.get_environment(environment_id)?;
+            for (name, value) in &values {
+                match environment.variables.iter_mut().find(|v| &v.name == name) {
+                    Some(v) => {
+                        v.value = value.clone();
+                        v.enabled = true;
+                    }
+                    None => environment.variables.push(EnvironmentVariable {
+                        enabled: true,
+                        name: name.clone(),
+                        value: value.clone(),
+                        id: None,
+                    }),
                 }
             }
+            db.upsert_environment(&environment, &UpdateSource::Background)?;
         }
 
-        Ok(())
+        let mut variables = serde_json::Map::new();
+        for (name, value) in values {
+            variables.insert(name.clone(), json!(value));
+            context.variables.insert(name, value);
+        }
+
+        Ok(json!({
+            "sourceNodeId": source_node_id,
+            "variables": variables,
+            "environmentId": environment_id,
+        }))
     }
 
     /// Execute conditional branch
@@ -479,8 +2220,29 @@ impl<R: Runtime> WorkflowOrchestrator<R> {
             .and_then(|v| v.as_str())
             .ok_or_else(|| Error::GenericError("Missing 'condition' in conditional node config".to_string()))?;
 
-        let rendered_condition = self.render_template(condition_expr, context)?;
-        let condition_result = self.evaluate_boolean(&rendered_condition)?;
+        let invalid = |e: String| {
+            Error::GenericError(format!("Invalid condition '{}': {}", condition_expr, e))
+        };
+        let condition = Condition::parse(condition_expr).map_err(invalid)?;
+        let condition_result = if condition.is_lone_tag() {
+            is_truthy_text(&self.render_template(condition_expr, context).await?)
+        } else {
+            let expression = Expression::parse(&condition.source).map_err(invalid)?;
+
+            // Tags are rendered on their own and bound as variables, never pasted into the source
+            let mut scope = Self::expression_scope(node_id, graph, context);
+            for (name, tag) in &condition.tags {
+                let value = bound_value(&self.render_template(tag, context).await?);
+                scope[name.as_str()] = value;
+            }
+
+            expression.evaluate_bool(&scope).map_err(|e| {
+                Error::GenericError(format!(
+                    "Failed to evaluate condition '{}': {}",
+                    condition_expr, e
+                ))
+            })?
+        };
 
         // 2. Execute appropriate branch
         let branch = if condition_result { true_branch } else { false_branch };
@@ -519,7 +2281,7 @@ impl<R: Runtime> WorkflowOrchestrator<R> {
                     .and_then(|v| v.as_str())
                     .ok_or_else(|| Error::GenericError("Missing 'array_variable' in array-based loop".to_string()))?;
 
-                let rendered = self.render_template(array_var, context)?;
+                let rendered = self.render_template(array_var, context).await?;
                 let array: Vec<JsonValue> = serde_json::from_str(&rendered)
                     .map_err(|e| Error::GenericError(format!("Failed to parse array: {}", e)))?;
 
@@ -534,6 +2296,10 @@ impl<R: Runtime> WorkflowOrchestrator<R> {
         let mut loop_results = Vec::new();
 
         for (index, item) in iterations {
+            if context.cancellation.is_cancelled() {
+                return Err(cancelled_error());
+            }
+
             // Push loop context
             context.loop_stack.push(LoopContext {
                 node_id: node.id.clone(),
@@ -559,6 +2325,7 @@ impl<R: Runtime> WorkflowOrchestrator<R> {
         // Store loop results
         context.node_results.insert(node.id.clone(), NodeResult {
             node_id: node.id.clone(),
+            node_name: node.name.clone(),
             output: json!(loop_results),
             elapsed: 0,
             loop_results: Some(loop_results),
@@ -569,56 +2336,136 @@ impl<R: Runtime> WorkflowOrchestrator<R> {
     }
 
     /// Render node config with template variables
-    fn render_node_config(&self, config: &JsonValue, _context: &ExecutionContext) -> Result<JsonValue> {
-        // TODO: Implement full template rendering with context
-        // For now, just return config as-is
-        Ok(config.clone())
+    async fn render_node_config(&self, config: &JsonValue, context: &ExecutionContext) -> Result<JsonValue> {
+        let vars = self.template_vars(context)?;
+        let workflow_ctx = Self::workflow_context(context);
+        let cb = PluginTemplateCallback::new(
+            &self.app_handle,
+            &PluginWindowContext::None,
+            RenderPurpose::Send,
+        );
+        let opt = RenderOptions {
+            error_behavior: RenderErrorBehavior::Throw,
+        };
+
+        render_json_value_raw_with_workflow(config.clone(), &vars, Some(&workflow_ctx), &cb, &opt)
+            .await
+            .map_err(|e| Error::GenericError(format!("Failed to render node config: {}", e)))
     }
 
     /// Render a template string
-    fn render_template(&self, template: &str, _context: &ExecutionContext) -> Result<String> {
-        // TODO: Implement template rendering with ExecutionContext
-        // This should integrate with yaak-templates and support:
-        // - step[N].response.* variables
-        // - loop.index, loop.item variables
-        // - env.* variables
-        Ok(template.to_string())
-    }
-
-    /// Evaluate a rendered condition as boolean
-    fn evaluate_boolean(&self, value: &str) -> Result<bool> {
-        // Simple boolean evaluation
-        match value.trim().to_lowercase().as_str() {
-            "true" | "1" | "yes" => Ok(true),
-            "false" | "0" | "no" | "" => Ok(false),
-            _ => {
-                // Try parsing as number (0 = false, non-zero = true)
-                if let Ok(num) = value.parse::<i64>() {
-                    Ok(num != 0)
-                } else {
-                    // Non-empty string = true
-                    Ok(!value.is_empty())
-                }
-            }
+    async fn render_template(&self, template: &str, context: &ExecutionContext) -> Result<String> {
+        let vars = self.template_vars(context)?;
+        let workflow_ctx = Self::workflow_context(context);
+        let cb = PluginTemplateCallback::new(
+            &self.app_handle,
+            &PluginWindowContext::None,
+            RenderPurpose::Send,
+        );
+        let opt = RenderOptions {
+            error_behavior: RenderErrorBehavior::Throw,
+        };
+
+        parse_and_render_with_workflow(template, &vars, Some(&workflow_ctx), &cb, &opt)
+            .await
+            .map_err(|e| Error::GenericError(format!("Failed to render template: {}", e)))
+    }
+
+    /// Build template variables from the environment chain, overlaid with runtime variables
+    fn template_vars(&self, context: &ExecutionContext) -> Result<HashMap<String, String>> {
+        let db = self.app_handle.db();
+        let workflow = db.get_workflow(&context.workflow_id)?;
+        let environment_chain = db.resolve_environments(
+            &workflow.workspace_id,
+            None,
+            context.environment_id.as_deref(),
+        )?;
+
+        let mut vars = make_vars_hashmap(environment_chain);
+        vars.extend(context.variables.clone());
+        Ok(vars)
+    }
+
+    /// Expose node results and the innermost loop to templates
+    fn workflow_context(context: &ExecutionContext) -> WorkflowContext {
+        let mut workflow_ctx = WorkflowContext::new();
+
+        for result in context.node_results.values() {
+            workflow_ctx.add_node_output(&result.node_id, &result.node_name, result.output.clone());
+        }
+
+        if let Some(l) = context.loop_stack.last() {
+            workflow_ctx.set_loop_context(TemplateLoopContext {
+                index: l.index,
+                total: l.total,
+                item: l.item.clone(),
+            });
         }
+
+        workflow_ctx
     }
 
-    /// Check if execution is cancelled
-    fn is_cancelled(&self, execution_id: &str) -> bool {
-        self.cancellation_tokens
-            .read()
-            .unwrap()
-            .get(execution_id)
-            .copied()
-            .unwrap_or(false)
+    /// Build the scope that condition expressions are evaluated against.
+    ///
+    /// Output fields of the node feeding into `node_id` are exposed at the top level (eg.
+    /// `status`, `body.id`), while `nodes`, `vars` and `loop` give access to everything else.
+    /// `step` lists request nodes left to right, for conditions from before expressions.
+    fn expression_scope(node_id: &str, graph: &ExecutionGraph, context: &ExecutionContext) -> JsonValue {
+        let previous_output = graph
+            .edges
+            .iter()
+            .find(|e| e.target_node_id == node_id)
+            .and_then(|e| context.node_results.get(&e.source_node_id))
+            .map(|r| r.output.clone());
+
+        let mut scope = match &previous_output {
+            Some(JsonValue::Object(o)) => o.clone(),
+            _ => serde_json::Map::new(),
+        };
+
+        let mut nodes = serde_json::Map::new();
+        for result in context.node_results.values() {
+            nodes.insert(result.node_name.clone(), result.output.clone());
+            nodes.insert(node_name_to_ident(&result.node_name), result.output.clone());
+        }
+        // IDs are inserted last so they always win over a clashing name
+        for result in context.node_results.values() {
+            nodes.insert(result.node_id.clone(), result.output.clone());
+        }
+
+        scope.insert("nodes".to_string(), JsonValue::Object(nodes));
+        scope.insert("vars".to_string(), json!(context.variables));
+        scope.insert(
+            "loop".to_string(),
+            match context.loop_stack.last() {
+                Some(l) => json!({ "index": l.index, "total": l.total, "item": l.item }),
+                None => JsonValue::Null,
+            },
+        );
+        scope.insert("previous".to_string(), previous_output.unwrap_or(JsonValue::Null));
+
+        // Workflows converted from steps have their request nodes laid out in step order
+        let mut requests: Vec<&WorkflowNode> = graph
+            .nodes
+            .values()
+            .filter(|n| matches!(n.node_subtype.as_str(), "http_request" | "grpc_request"))
+            .collect();
+        requests.sort_by(|a, b| a.position_x.total_cmp(&b.position_x));
+        let steps = requests
+            .iter()
+            .map(|n| match context.node_results.get(&n.id) {
+                Some(r) => json!({ "response": r.output }),
+                None => JsonValue::Null,
+            })
+            .collect();
+        scope.insert("step".to_string(), JsonValue::Array(steps));
+
+        JsonValue::Object(scope)
     }
 
-    /// Cancel an execution
+    /// Cancel a running execution or load test, along with everything it started
     pub fn cancel(&self, execution_id: &str) {
-        self.cancellation_tokens
-            .write()
-            .unwrap()
-            .insert(execution_id.to_string(), true);
+        cancellation::cancel(execution_id);
     }
 
     /// Update execution state
@@ -651,3 +2498,207 @@ impl<R: Runtime> WorkflowOrchestrator<R> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tauri::test::{MockRuntime, mock_builder, mock_context, noop_assets};
+    use yaak_models::models::{NodeType, WebsocketConnection, Workflow};
+
+    /// An app with nothing but a fresh database, removed again on drop
+    struct TestApp {
+        app: tauri::App<MockRuntime>,
+        db_path: PathBuf,
+    }
+
+    impl TestApp {
+        fn new() -> Self {
+            let db_path =
+                std::env::temp_dir().join(format!("{}.sqlite", generate_prefixed_id("yaak-test")));
+            let app = mock_builder()
+                .plugin(yaak_models::init_with_db_path(Some(db_path.clone())))
+                .build(mock_context(noop_assets()))
+                .expect("Failed to build test app");
+            TestApp { app, db_path }
+        }
+
+        fn orchestrator(&self) -> WorkflowOrchestrator<MockRuntime> {
+            WorkflowOrchestrator::new(self.app.handle().clone())
+        }
+
+        /// A node of `node_subtype` in a new workflow and workspace
+        fn create_node(&self, node_subtype: &str) -> WorkflowNode {
+            let db = self.app.db();
+            let source = &UpdateSource::Background;
+            let workspace = db
+                .upsert_workspace(
+                    &Workspace {
+                        name: "Test".into(),
+                        ..Default::default()
+                    },
+                    source,
+                )
+                .unwrap();
+            let workflow = db
+                .upsert(
+                    &Workflow {
+                        workspace_id: workspace.id.clone(),
+                        name: "Test".into(),
+                        ..Default::default()
+                    },
+                    source,
+                )
+                .unwrap();
+            db.upsert(
+                &WorkflowNode {
+                    workspace_id: workspace.id,
+                    workflow_id: workflow.id,
+                    node_type: NodeType::Action,
+                    node_subtype: node_subtype.into(),
+                    name: "Inline".into(),
+                    enabled: true,
+                    ..Default::default()
+                },
+                source,
+            )
+            .unwrap()
+        }
+    }
+
+    impl Drop for TestApp {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.db_path);
+        }
+    }
+
+    /// Build the inline request of a node. It's returned as JSON so every request type can be
+    /// checked the same way.
+    fn build_inline_request(
+        orchestrator: &WorkflowOrchestrator<MockRuntime>,
+        node: &WorkflowNode,
+        config: &JsonValue,
+    ) -> JsonValue {
+        match node.node_subtype.as_str() {
+            "http_request" => json!(orchestrator.build_http_request(node, config).unwrap()),
+            "grpc_request" => json!(orchestrator.build_grpc_request(node, config).unwrap()),
+            _ => json!(orchestrator.build_websocket_request(node, config).unwrap()),
+        }
+    }
+
+    /// Record what a run of the node saves under its request, which the database only accepts
+    /// for a saved request. Returns the ID of the response or connection.
+    fn record_run(db: &DbContext, node: &WorkflowNode, request: &JsonValue) -> String {
+        let source = &UpdateSource::Background;
+        let request_id = request["id"].as_str().unwrap().to_string();
+        let workspace_id = node.workspace_id.clone();
+        match node.node_subtype.as_str() {
+            "http_request" => {
+                let response = HttpResponse {
+                    request_id,
+                    workspace_id,
+                    ..Default::default()
+                };
+                db.upsert_http_response(&response, source).unwrap().id
+            }
+            "grpc_request" => {
+                let connection = GrpcConnection {
+                    request_id: request_id.clone(),
+                    workspace_id: workspace_id.clone(),
+                    ..Default::default()
+                };
+                let connection = db.upsert_grpc_connection(&connection, source).unwrap();
+                let event = GrpcEvent {
+                    request_id,
+                    workspace_id,
+                    connection_id: connection.id.clone(),
+                    ..Default::default()
+                };
+                db.upsert_grpc_event(&event, source).unwrap();
+                connection.id
+            }
+            _ => {
+                let connection = WebsocketConnection {
+                    request_id: request_id.clone(),
+                    workspace_id: workspace_id.clone(),
+                    ..Default::default()
+                };
+                let connection = db.upsert_websocket_connection(&connection, source).unwrap();
+                let event = WebsocketEvent {
+                    request_id,
+                    workspace_id,
+                    connection_id: connection.id.clone(),
+                    ..Default::default()
+                };
+                db.upsert_websocket_event(&event, source).unwrap();
+                connection.id
+            }
+        }
+    }
+
+    fn request_and_run_exist(
+        db: &DbContext,
+        node: &WorkflowNode,
+        request_id: &str,
+        run_id: &str,
+    ) -> bool {
+        match node.node_subtype.as_str() {
+            "http_request" => {
+                db.get_http_request(request_id).is_ok() || db.get_http_response(run_id).is_ok()
+            }
+            "grpc_request" => {
+                db.get_grpc_request(request_id).is_ok() || db.get_grpc_connection(run_id).is_ok()
+            }
+            _ => {
+                db.get_websocket_request(request_id).is_ok()
+                    || db.get_websocket_connection(run_id).is_ok()
+            }
+        }
+    }
+
+    fn listed_request_count(db: &DbContext, node: &WorkflowNode) -> usize {
+        match node.node_subtype.as_str() {
+            "http_request" => db.list_http_requests(&node.workspace_id).unwrap().len(),
+            "grpc_request" => db.list_grpc_requests(&node.workspace_id).unwrap().len(),
+            _ => db.list_websocket_requests(&node.workspace_id).unwrap().len(),
+        }
+    }
+
+    #[test]
+    fn test_inline_request_nodes() {
+        // Each node type, with a field that only the first config sets
+        let cases = [
+            ("http_request", "method"),
+            ("grpc_request", "message"),
+            ("websocket", "headers"),
+        ];
+        let first_config = json!({
+            "url": "wss://example.com/first",
+            "method": "POST",
+            "message": { "name": "Yaak" },
+            "headers": [{ "name": "X-Token", "value": "abc" }],
+        });
+        let second_config = json!({ "url": "wss://example.com/second" });
+
+        for (node_subtype, first_only) in cases {
+            let test = TestApp::new();
+            let orchestrator = test.orchestrator();
+            let node = test.create_node(node_subtype);
+            let db = test.app.db();
+
+            let request = build_inline_request(&orchestrator, &node, &first_config);
+            assert_eq!(request["workflowNodeId"], json!(node.id), "{node_subtype}");
+            let run_id = record_run(&db, &node, &request);
+
+            // Running again reuses the request, with nothing left over from the previous config
+            let again = build_inline_request(&orchestrator, &node, &second_config);
+            assert_eq!(again["id"], request["id"], "{node_subtype}");
+            assert_ne!(again[first_only], request[first_only], "{node_subtype}");
+
+            // It's hidden from the workspace, and goes away with the node
+            let request_id = request["id"].as_str().unwrap();
+            assert_eq!(listed_request_count(&db, &node), 0, "{node_subtype}");
+            db.delete_workflow_node(&node, &UpdateSource::Background).unwrap();
+            assert!(!request_and_run_exist(&db, &node, request_id, &run_id), "{node_subtype}");
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+use std::time::Duration;
+
+/// Retry, timeout and error handling for a single node, read from `config.policy`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct NodePolicy {
+    /// Additional attempts after the first one fails
+    pub max_retries: u32,
+    /// Delay before the first retry
+    pub retry_backoff_ms: u64,
+    /// Factor the delay grows by for each following retry
+    pub backoff_multiplier: f64,
+    /// Fail an attempt that runs longer than this
+    pub timeout_ms: Option<u64>,
+    /// Record the failure but let the workflow carry on
+    pub continue_on_error: bool,
+}
+
+impl Default for NodePolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 0,
+            retry_backoff_ms: 1000,
+            backoff_multiplier: 2.0,
+            timeout_ms: None,
+            continue_on_error: false,
+        }
+    }
+}
+
+/// Upper bound for a single backoff delay, so large multipliers can't stall a run for hours
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+impl NodePolicy {
+    pub fn from_config(config: &JsonValue) -> Self {
+        config
+            .get("policy")
+            .and_then(|p| serde_json::from_value(p.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout_ms.filter(|ms| *ms > 0).map(Duration::from_millis)
+    }
+
+    /// Delay to wait after `attempt` (1-based) failed
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = self.backoff_multiplier.max(1.0).powi(attempt.saturating_sub(1) as i32);
+        let ms = (self.retry_backoff_ms as f64 * factor).min(MAX_BACKOFF.as_millis() as f64);
+        Duration::from_millis(ms as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_defaults_without_policy() {
+        let policy = NodePolicy::from_config(&json!({ "url": "https://yaak.app" }));
+        assert_eq!(policy.max_retries, 0);
+        assert_eq!(policy.timeout(), None);
+        assert!(!policy.continue_on_error);
+    }
+
+    #[test]
+    fn test_backoff() {
+        let policy = NodePolicy::from_config(&json!({
+            "policy": { "max_retries": 5, "retry_backoff_ms": 100, "backoff_multiplier": 3 }
+        }));
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(300));
+        assert_eq!(policy.backoff(3), Duration::from_millis(900));
+        assert_eq!(policy.backoff(20), MAX_BACKOFF);
+    }
+
+    #[test]
+    fn test_timeout() {
+        let policy = NodePolicy::from_config(&json!({ "policy": { "timeout_ms": 0 } }));
+        assert_eq!(policy.timeout(), None);
+        let policy = NodePolicy::from_config(&json!({ "policy": { "timeout_ms": 2500 } }));
+        assert_eq!(policy.timeout(), Some(Duration::from_millis(2500)));
+    }
+}
@@ -0,0 +1,487 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Write;
+use yaak_models::db_context::DbContext;
+use yaak_models::error::Result;
+use yaak_models::models::{
+    NodeExecutionState, NodeType, Workflow, WorkflowExecution, WorkflowExecutionState,
+    WorkflowNode, WorkflowNodeExecution,
+};
+
+use super::assertion::AssertionOutcome;
+
+/// Format to export a finished execution in
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    Junit,
+    Json,
+}
+
+/// Test report for a single workflow execution
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionReport {
+    pub workflow_id: String,
+    pub workflow_name: String,
+    pub execution_id: String,
+    /// Execution that ran this one from a sub-workflow node
+    pub caller_execution_id: Option<String>,
+    pub environment_id: Option<String>,
+    pub state: WorkflowExecutionState,
+    pub error: Option<String>,
+    pub started_at: NaiveDateTime,
+    pub elapsed: Option<i32>,
+    pub summary: ReportSummary,
+    pub nodes: Vec<NodeReport>,
+}
+
+/// Test counts, using JUnit semantics: failed assertions are failures, failed nodes are errors
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportSummary {
+    pub tests: usize,
+    pub passed: usize,
+    pub failures: usize,
+    pub errors: usize,
+    pub skipped: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeReport {
+    pub node_id: String,
+    pub name: String,
+    pub node_type: NodeType,
+    pub node_subtype: String,
+    pub state: NodeExecutionState,
+    pub elapsed: Option<i32>,
+    /// Number of attempts, including retries
+    pub attempts: i32,
+    pub loop_iteration: Option<i32>,
+    /// Dataset row the node ran with, for dataset runs
+    pub dataset_row: Option<i32>,
+    pub error: Option<String>,
+    pub assertions: Vec<AssertionOutcome>,
+}
+
+impl ExecutionReport {
+    pub fn load(db: &DbContext, execution_id: &str) -> Result<Self> {
+        let execution = db.get_workflow_execution(execution_id)?;
+        let workflow = db.get_workflow(&execution.workflow_id)?;
+        let nodes = db.get_workflow_nodes(&workflow.id)?;
+        let node_executions = db.get_workflow_node_executions(execution_id)?;
+        let mut report = Self::new(&workflow, &execution, &nodes, node_executions);
+        report.nodes.extend(Self::sub_workflow_nodes(db, &execution)?);
+
+        // Dataset runs group one execution per row, so report the nodes of every row
+        let mut rows: Vec<WorkflowExecution> = db
+            .list_workflow_executions(&workflow.id)?
+            .into_iter()
+            .filter(|e| e.parent_execution_id.as_deref() == Some(execution_id))
+            .collect();
+        rows.sort_by_key(|e| e.dataset_row);
+        for row in rows {
+            let node_executions = db.get_workflow_node_executions(&row.id)?;
+            report.nodes.extend(Self::new(&workflow, &row, &nodes, node_executions).nodes);
+            report.nodes.extend(Self::sub_workflow_nodes(db, &row)?);
+        }
+
+        report.summary = report.summarize();
+        Ok(report)
+    }
+
+    /// Nodes of the runs that sub-workflow nodes of `execution` started, named after the
+    /// workflow they belong to
+    fn sub_workflow_nodes(db: &DbContext, execution: &WorkflowExecution) -> Result<Vec<NodeReport>> {
+        let mut reports = Vec::new();
+        for child in db.list_workflow_executions_by_caller(&execution.id)? {
+            let workflow = db.get_workflow(&child.workflow_id)?;
+            let nodes = db.get_workflow_nodes(&workflow.id)?;
+            let node_executions = db.get_workflow_node_executions(&child.id)?;
+            let mut child_reports = Self::new(&workflow, &child, &nodes, node_executions).nodes;
+            child_reports.extend(Self::sub_workflow_nodes(db, &child)?);
+            for report in &mut child_reports {
+                report.name = format!("{} › {}", workflow.name, report.name);
+                report.dataset_row = execution.dataset_row;
+            }
+            reports.extend(child_reports);
+        }
+        Ok(reports)
+    }
+
+    pub fn new(
+        workflow: &Workflow,
+        execution: &WorkflowExecution,
+        nodes: &[WorkflowNode],
+        node_executions: Vec<WorkflowNodeExecution>,
+    ) -> Self {
+        let nodes_by_id: HashMap<&str, &WorkflowNode> =
+            nodes.iter().map(|n| (n.id.as_str(), n)).collect();
+
+        // Retries create a row per attempt, so only the last attempt of each node (and loop
+        // iteration) is reported. Rows are sorted by creation time, so later rows win.
+        let mut reports: Vec<NodeReport> = Vec::new();
+        for node_execution in node_executions {
+            let existing = reports.iter().position(|r| {
+                r.node_id == node_execution.workflow_node_id
+                    && r.loop_iteration == node_execution.loop_iteration
+            });
+            let node = nodes_by_id.get(node_execution.workflow_node_id.as_str());
+            let report = NodeReport {
+                name: node.map(|n| n.name.clone()).unwrap_or(node_execution.workflow_node_id.clone()),
+                node_type: node.map(|n| n.node_type.clone()).unwrap_or(NodeType::Action),
+                node_subtype: node.map(|n| n.node_subtype.clone()).unwrap_or_default(),
+                assertions: node_execution
+                    .result
+                    .as_ref()
+                    .filter(|_| node.is_some_and(|n| n.node_subtype == "assertion"))
+                    .and_then(|r| r.get("results"))
+                    .and_then(|r| serde_json::from_value(r.clone()).ok())
+                    .unwrap_or_default(),
+                node_id: node_execution.workflow_node_id,
+                state: node_execution.state,
+                elapsed: node_execution.elapsed,
+                attempts: node_execution.attempt.max(1),
+                loop_iteration: node_execution.loop_iteration,
+                dataset_row: execution.dataset_row,
+                error: node_execution.error,
+            };
+            match existing {
+                Some(i) => reports[i] = report,
+                None => reports.push(report),
+            }
+        }
+
+        let mut report = Self {
+            workflow_id: workflow.id.clone(),
+            workflow_name: workflow.name.clone(),
+            execution_id: execution.id.clone(),
+            caller_execution_id: execution.caller_execution_id.clone(),
+            environment_id: execution.environment_id.clone(),
+            state: execution.state.clone(),
+            error: execution.error.clone(),
+            started_at: execution.created_at,
+            elapsed: execution.elapsed,
+            summary: ReportSummary::default(),
+            nodes: reports,
+        };
+        report.summary = report.summarize();
+        report
+    }
+
+    fn summarize(&self) -> ReportSummary {
+        let mut summary = ReportSummary::default();
+        for node in &self.nodes {
+            if !node.assertions.is_empty() {
+                summary.tests += node.assertions.len();
+                summary.failures += node.assertions.iter().filter(|a| !a.passed).count();
+                continue;
+            }
+            summary.tests += 1;
+            match node.state {
+                NodeExecutionState::Completed => {}
+                NodeExecutionState::Failed => summary.errors += 1,
+                _ => summary.skipped += 1,
+            }
+        }
+
+        if self.execution_error().is_some() {
+            summary.tests += 1;
+            summary.errors += 1;
+        }
+
+        summary.passed = summary.tests - summary.failures - summary.errors - summary.skipped;
+        summary
+    }
+
+    /// Error for a run that failed without any node to blame (eg. an invalid graph or
+    /// cancellation), so CI still sees the run as broken
+    fn execution_error(&self) -> Option<String> {
+        let node_failed = self.nodes.iter().any(|n| {
+            n.state == NodeExecutionState::Failed || n.assertions.iter().any(|a| !a.passed)
+        });
+        match self.state {
+            _ if node_failed => None,
+            WorkflowExecutionState::Failed => {
+                Some(self.error.clone().unwrap_or("Workflow execution failed".to_string()))
+            }
+            WorkflowExecutionState::Cancelled => Some("Workflow execution was cancelled".to_string()),
+            _ => None,
+        }
+    }
+
+    pub fn export(&self, format: ReportFormat) -> Result<String> {
+        match format {
+            ReportFormat::Json => Ok(serde_json::to_string_pretty(self)?),
+            ReportFormat::Junit => Ok(self.to_junit_xml()),
+        }
+    }
+
+    /// Render as JUnit XML: one suite for the workflow, one test case per node, and one per
+    /// assertion for assertion nodes
+    pub fn to_junit_xml(&self) -> String {
+        let suite = escape_xml(&self.workflow_name);
+        let time = format_seconds(self.elapsed);
+        let s = &self.summary;
+
+        let mut xml = String::new();
+        xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let _ = writeln!(
+            xml,
+            "<testsuites name=\"Yaak\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{time}\">",
+            s.tests, s.failures, s.errors, s.skipped
+        );
+        let _ = writeln!(
+            xml,
+            "  <testsuite name=\"{suite}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{time}\" timestamp=\"{}\">",
+            s.tests,
+            s.failures,
+            s.errors,
+            s.skipped,
+            self.started_at.format("%Y-%m-%dT%H:%M:%S"),
+        );
+
+        xml.push_str("    <properties>\n");
+        write_property(&mut xml, "workflowId", &self.workflow_id);
+        write_property(&mut xml, "executionId", &self.execution_id);
+        if let Some(caller_execution_id) = &self.caller_execution_id {
+            write_property(&mut xml, "callerExecutionId", caller_execution_id);
+        }
+        if let Some(environment_id) = &self.environment_id {
+            write_property(&mut xml, "environmentId", environment_id);
+        }
+        xml.push_str("    </properties>\n");
+
+        for node in &self.nodes {
+            let mut name = node.name.clone();
+            if let Some(i) = node.loop_iteration {
+                name.push_str(&format!(" [iteration {}]", i));
+            }
+            if let Some(row) = node.dataset_row {
+                name.push_str(&format!(" [row {}]", row + 1));
+            }
+
+            if node.assertions.is_empty() {
+                let body = match node.state {
+                    NodeExecutionState::Completed => None,
+                    NodeExecutionState::Failed => {
+                        let message = node.error.clone().unwrap_or("Node failed".to_string());
+                        Some(format!(
+                            "<error message=\"{}\" type=\"{}\">{}</error>",
+                            escape_xml(&message),
+                            escape_xml(&node.node_subtype),
+                            escape_xml(&attempts_detail(&message, node.attempts)),
+                        ))
+                    }
+                    _ => Some("<skipped/>".to_string()),
+                };
+                write_test_case(&mut xml, &suite, &name, node.elapsed, body);
+                continue;
+            }
+
+            // Spread the node's time over its assertions so the suite total still adds up
+            let elapsed = node.elapsed.map(|e| e / node.assertions.len() as i32);
+            let classname = format!("{suite}.{}", escape_xml(&name));
+            for assertion in &node.assertions {
+                let case_name = format!("{} {} {}", assertion.target, assertion.operator, assertion.expected);
+                let body = (!assertion.passed).then(|| {
+                    let message = assertion.message.clone().unwrap_or("Assertion failed".to_string());
+                    format!(
+                        "<failure message=\"{}\" type=\"{}\">Actual: {}</failure>",
+                        escape_xml(&message),
+                        escape_xml(&assertion.kind),
+                        escape_xml(&assertion.actual.to_string()),
+                    )
+                });
+                write_test_case(&mut xml, &classname, &case_name, elapsed, body);
+            }
+        }
+
+        if let Some(error) = self.execution_error() {
+            let body = format!("<error message=\"{}\">{}</error>", escape_xml(&error), escape_xml(&error));
+            write_test_case(&mut xml, &suite, &self.workflow_name, None, Some(body));
+        }
+
+        xml.push_str("  </testsuite>\n");
+        xml.push_str("</testsuites>\n");
+        xml
+    }
+}
+
+fn write_property(xml: &mut String, name: &str, value: &str) {
+    let _ = writeln!(xml, "      <property name=\"{name}\" value=\"{}\"/>", escape_xml(value));
+}
+
+/// Write a test case. `classname` must already be escaped
+fn write_test_case(
+    xml: &mut String,
+    classname: &str,
+    name: &str,
+    elapsed: Option<i32>,
+    body: Option<String>,
+) {
+    let open = format!(
+        "    <testcase classname=\"{classname}\" name=\"{}\" time=\"{}\"",
+        escape_xml(name),
+        format_seconds(elapsed)
+    );
+    let _ = match body {
+        None => writeln!(xml, "{open}/>"),
+        Some(body) => writeln!(xml, "{open}>\n      {body}\n    </testcase>"),
+    };
+}
+
+fn attempts_detail(message: &str, attempts: i32) -> String {
+    match attempts {
+        1 => message.to_string(),
+        n => format!("{message}\n\nFailed after {n} attempts"),
+    }
+}
+
+fn format_seconds(elapsed_ms: Option<i32>) -> String {
+    format!("{:.3}", elapsed_ms.unwrap_or(0) as f64 / 1000.0)
+}
+
+fn escape_xml(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // Control characters aren't allowed in XML 1.0, even when escaped
+            c if c.is_control() && !matches!(c, '\n' | '\r' | '\t') => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn node(id: &str, name: &str, subtype: &str) -> WorkflowNode {
+        WorkflowNode {
+            id: id.to_string(),
+            name: name.to_string(),
+            node_type: NodeType::Action,
+            node_subtype: subtype.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn node_execution(
+        node_id: &str,
+        state: NodeExecutionState,
+        attempt: i32,
+        error: Option<&str>,
+    ) -> WorkflowNodeExecution {
+        WorkflowNodeExecution {
+            workflow_node_id: node_id.to_string(),
+            state,
+            attempt,
+            elapsed: Some(200),
+            error: error.map(|e| e.to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn report(state: WorkflowExecutionState, node_executions: Vec<WorkflowNodeExecution>) -> ExecutionReport {
+        let workflow = Workflow {
+            id: "wf_1".to_string(),
+            name: "Checkout <smoke>".to_string(),
+            ..Default::default()
+        };
+        let execution = WorkflowExecution {
+            id: "we_1".to_string(),
+            workflow_id: "wf_1".to_string(),
+            state,
+            elapsed: Some(1500),
+            ..Default::default()
+        };
+        let nodes = vec![
+            node("n_http", "Create order", "http_request"),
+            node("n_assert", "Check order", "assertion"),
+            node("n_grpc", "Notify", "grpc_request"),
+        ];
+        ExecutionReport::new(&workflow, &execution, &nodes, node_executions)
+    }
+
+    #[test]
+    fn test_report_summary() {
+        let mut assertions = node_execution("n_assert", NodeExecutionState::Failed, 1, Some("1 of 2 assertions failed"));
+        assertions.result = Some(json!({
+            "results": [
+                { "type": "status", "target": "status", "operator": "equals", "passed": true, "actual": 201, "expected": 201, "message": null },
+                { "type": "header", "target": "x-id", "operator": "exists", "passed": false, "actual": null, "expected": null, "message": "Expected x-id exists null, got null" },
+            ]
+        }));
+
+        let report = report(
+            WorkflowExecutionState::Failed,
+            vec![
+                node_execution("n_http", NodeExecutionState::Failed, 1, Some("timeout")),
+                node_execution("n_http", NodeExecutionState::Completed, 2, None),
+                assertions,
+                node_execution("n_grpc", NodeExecutionState::Skipped, 1, None),
+            ],
+        );
+
+        assert_eq!(report.nodes.len(), 3);
+        assert_eq!(report.nodes[0].attempts, 2);
+        assert_eq!(report.nodes[0].state, NodeExecutionState::Completed);
+        assert_eq!(report.summary.tests, 4);
+        assert_eq!(report.summary.passed, 2);
+        assert_eq!(report.summary.failures, 1);
+        assert_eq!(report.summary.errors, 0);
+        assert_eq!(report.summary.skipped, 1);
+    }
+
+    #[test]
+    fn test_junit_xml() {
+        let report = report(
+            WorkflowExecutionState::Failed,
+            vec![node_execution("n_http", NodeExecutionState::Failed, 3, Some("status was \"500\""))],
+        );
+        let xml = report.to_junit_xml();
+
+        assert!(xml.contains("<testsuite name=\"Checkout &lt;smoke&gt;\" tests=\"1\" failures=\"0\" errors=\"1\""));
+        assert!(xml.contains("<testcase classname=\"Checkout &lt;smoke&gt;\" name=\"Create order\" time=\"0.200\">"));
+        assert!(xml.contains("<error message=\"status was &quot;500&quot;\" type=\"http_request\">"));
+        assert!(xml.contains("Failed after 3 attempts"));
+    }
+
+    #[test]
+    fn test_dataset_row_names() {
+        let workflow = Workflow { name: "Signup".to_string(), ..Default::default() };
+        let row = WorkflowExecution { dataset_row: Some(1), ..Default::default() };
+        let nodes = vec![node("n_http", "Create user", "http_request")];
+        let report = ExecutionReport::new(
+            &workflow,
+            &row,
+            &nodes,
+            vec![node_execution("n_http", NodeExecutionState::Completed, 1, None)],
+        );
+
+        assert_eq!(report.nodes[0].dataset_row, Some(1));
+        assert!(report.to_junit_xml().contains("name=\"Create user [row 2]\""));
+    }
+
+    #[test]
+    fn test_execution_error_without_nodes() {
+        let report = report(WorkflowExecutionState::Cancelled, Vec::new());
+        assert_eq!(report.summary.tests, 1);
+        assert_eq!(report.summary.errors, 1);
+        assert!(report.to_junit_xml().contains("Workflow execution was cancelled"));
+
+        let json: serde_json::Value = serde_json::from_str(&report.export(ReportFormat::Json).unwrap()).unwrap();
+        assert_eq!(json["summary"]["errors"], json!(1));
+        assert_eq!(json["workflowName"], json!("Checkout <smoke>"));
+    }
+}
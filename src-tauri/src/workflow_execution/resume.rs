@@ -0,0 +1,180 @@
+use serde_json::Value as JsonValue;
+use std::collections::{HashMap, HashSet, VecDeque};
+use yaak_models::error::{Error, Result};
+use yaak_models::models::{NodeExecutionState, WorkflowNodeExecution};
+
+use super::graph_builder::ExecutionGraph;
+
+/// What to reuse from an earlier execution when resuming it from a node
+#[derive(Debug, Default)]
+pub struct ResumePlan {
+    pub from_node_id: String,
+    /// Completed node executions to reuse, keyed by node ID and loop iteration
+    pub reused: HashMap<(String, Option<i32>), WorkflowNodeExecution>,
+    /// Output of the start trigger in the earlier run, so it sees the same payload
+    pub trigger_payload: Option<JsonValue>,
+}
+
+impl ResumePlan {
+    /// Plan resuming from `from_node_id`, or from the first node that failed or never finished.
+    ///
+    /// That node and everything downstream of it runs again. Every other node that completed
+    /// is reused, including ones in parallel branches next to the failure.
+    pub fn new(
+        graph: &ExecutionGraph,
+        node_executions: Vec<WorkflowNodeExecution>,
+        from_node_id: Option<String>,
+    ) -> Result<Self> {
+        let latest = last_attempts(node_executions);
+
+        let from_node_id = match from_node_id {
+            Some(id) => id,
+            None => latest
+                .iter()
+                .find(|e| e.state != NodeExecutionState::Completed)
+                .map(|e| e.workflow_node_id.clone())
+                .ok_or_else(|| {
+                    Error::GenericError("Execution has no failed node to resume from".to_string())
+                })?,
+        };
+
+        if !graph.nodes.contains_key(&from_node_id) {
+            return Err(Error::GenericError(format!(
+                "Node {} is no longer part of the workflow",
+                from_node_id
+            )));
+        }
+
+        let trigger_payload = latest
+            .iter()
+            .find(|e| e.workflow_node_id == graph.start_node_id)
+            .and_then(|e| e.result.clone());
+
+        let rerun = downstream_nodes(graph, &from_node_id);
+        let reused = latest
+            .into_iter()
+            .filter(|e| e.state == NodeExecutionState::Completed)
+            .filter(|e| !rerun.contains(&e.workflow_node_id))
+            .map(|e| ((e.workflow_node_id.clone(), e.loop_iteration), e))
+            .collect();
+
+        Ok(Self {
+            from_node_id,
+            reused,
+            trigger_payload,
+        })
+    }
+}
+
+/// Retries create a row per attempt, so keep only the last attempt of each node and loop
+/// iteration. Rows are sorted by creation time, so later rows win.
+fn last_attempts(node_executions: Vec<WorkflowNodeExecution>) -> Vec<WorkflowNodeExecution> {
+    let mut latest: Vec<WorkflowNodeExecution> = Vec::new();
+    for node_execution in node_executions {
+        let existing = latest.iter().position(|e| {
+            e.workflow_node_id == node_execution.workflow_node_id
+                && e.loop_iteration == node_execution.loop_iteration
+        });
+        match existing {
+            Some(i) => latest[i] = node_execution,
+            None => latest.push(node_execution),
+        }
+    }
+    latest
+}
+
+/// `node_id` and every node reachable from it
+fn downstream_nodes(graph: &ExecutionGraph, node_id: &str) -> HashSet<String> {
+    let mut seen = HashSet::from([node_id.to_string()]);
+    let mut queue = VecDeque::from([node_id.to_string()]);
+    while let Some(id) = queue.pop_front() {
+        for edge in graph.edges.iter().filter(|e| e.source_node_id == id && e.deleted_at.is_none()) {
+            if seen.insert(edge.target_node_id.clone()) {
+                queue.push_back(edge.target_node_id.clone());
+            }
+        }
+    }
+    seen
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use yaak_models::models::{WorkflowEdge, WorkflowNode};
+
+    /// trigger -> create -> [a, b] in parallel -> check
+    fn graph() -> ExecutionGraph {
+        let ids = ["trigger", "create", "a", "b", "check"];
+        let edges = [("trigger", "create"), ("create", "a"), ("create", "b"), ("a", "check"), ("b", "check")];
+        ExecutionGraph {
+            nodes: ids
+                .iter()
+                .map(|id| (id.to_string(), WorkflowNode { id: id.to_string(), ..Default::default() }))
+                .collect(),
+            edges: edges
+                .iter()
+                .map(|(source, target)| WorkflowEdge {
+                    source_node_id: source.to_string(),
+                    target_node_id: target.to_string(),
+                    ..Default::default()
+                })
+                .collect(),
+            start_node_id: "trigger".to_string(),
+            execution_order: Vec::new(),
+        }
+    }
+
+    fn node_execution(node_id: &str, state: NodeExecutionState, attempt: i32) -> WorkflowNodeExecution {
+        WorkflowNodeExecution {
+            workflow_node_id: node_id.to_string(),
+            state,
+            attempt,
+            result: Some(json!({ "node": node_id })),
+            ..Default::default()
+        }
+    }
+
+    fn run() -> Vec<WorkflowNodeExecution> {
+        vec![
+            node_execution("trigger", NodeExecutionState::Completed, 1),
+            node_execution("create", NodeExecutionState::Completed, 1),
+            node_execution("a", NodeExecutionState::Failed, 1),
+            node_execution("b", NodeExecutionState::Completed, 1),
+            node_execution("a", NodeExecutionState::Failed, 2),
+        ]
+    }
+
+    fn reused(plan: &ResumePlan) -> Vec<&str> {
+        let mut ids: Vec<&str> = plan.reused.keys().map(|(id, _)| id.as_str()).collect();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn test_resume_from_first_failed_node() {
+        let plan = ResumePlan::new(&graph(), run(), None).unwrap();
+        assert_eq!(plan.from_node_id, "a");
+        assert_eq!(reused(&plan), vec!["b", "create", "trigger"]);
+        assert_eq!(plan.trigger_payload, Some(json!({ "node": "trigger" })));
+    }
+
+    #[test]
+    fn test_resume_from_chosen_node() {
+        let plan = ResumePlan::new(&graph(), run(), Some("create".to_string())).unwrap();
+        assert_eq!(reused(&plan), vec!["trigger"]);
+
+        // A retry that eventually succeeded counts as completed
+        let mut executions = run();
+        executions.push(node_execution("a", NodeExecutionState::Completed, 3));
+        let plan = ResumePlan::new(&graph(), executions, Some("check".to_string())).unwrap();
+        assert_eq!(reused(&plan), vec!["a", "b", "create", "trigger"]);
+    }
+
+    #[test]
+    fn test_resume_errors() {
+        let completed = vec![node_execution("trigger", NodeExecutionState::Completed, 1)];
+        assert!(ResumePlan::new(&graph(), completed, None).is_err());
+        assert!(ResumePlan::new(&graph(), run(), Some("deleted".to_string())).is_err());
+    }
+}
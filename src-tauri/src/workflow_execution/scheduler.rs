@@ -0,0 +1,291 @@
+use chrono::{Duration, Local, NaiveDateTime, TimeZone, Utc};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value as JsonValue};
+use std::collections::{HashMap, HashSet};
+use tauri::{AppHandle, Runtime};
+use yaak_models::models::{WorkflowExecutionState, WorkflowNode};
+use yaak_models::query_manager::QueryManagerExt;
+use yaak_models::util::UpdateSource;
+
+use super::cron::CronSchedule;
+use super::orchestrator::WorkflowOrchestrator;
+
+/// Key/value namespace the schedule state of each timer trigger (keyed by node ID) is stored in
+pub const SCHEDULER_NAMESPACE: &str = "workflow_scheduler";
+
+/// How often to check for due triggers. Schedules have minute resolution, so this is plenty.
+const TICK: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// When a `timer_trigger` node should fire
+#[derive(Debug, Clone, PartialEq)]
+pub enum Schedule {
+    Interval(Duration),
+    /// Cron expressions are evaluated in the machine's local time zone
+    Cron { expression: String, cron: CronSchedule },
+}
+
+impl Schedule {
+    pub fn from_config(config: &JsonValue) -> Result<Self, String> {
+        match config.get("schedule_type").and_then(|v| v.as_str()).unwrap_or("interval") {
+            "interval" => {
+                let minutes = config.get("interval_minutes").and_then(|v| v.as_i64()).unwrap_or(0);
+                if minutes < 1 {
+                    return Err("Interval must be at least 1 minute".to_string());
+                }
+                Ok(Schedule::Interval(Duration::minutes(minutes)))
+            }
+            "cron" => {
+                let expression = config
+                    .get("cron_expression")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default()
+                    .trim()
+                    .to_string();
+                let cron = CronSchedule::parse(&expression)
+                    .map_err(|e| format!("Invalid cron expression '{}': {}", expression, e))?;
+                Ok(Schedule::Cron { expression, cron })
+            }
+            t => Err(format!("Unknown schedule type '{}'", t)),
+        }
+    }
+
+    /// Stored alongside the state, so editing a schedule restarts it
+    fn describe(&self) -> String {
+        match self {
+            Schedule::Interval(d) => format!("interval:{}", d.num_minutes()),
+            Schedule::Cron { expression, .. } => format!("cron:{}", expression),
+        }
+    }
+
+    /// Next fire time after `after`, both in UTC
+    pub fn next_after(&self, after: NaiveDateTime) -> Option<NaiveDateTime> {
+        match self {
+            Schedule::Interval(d) => Some(after + *d),
+            Schedule::Cron { cron, .. } => {
+                let mut local = Utc.from_utc_datetime(&after).with_timezone(&Local).naive_local();
+                // Times that don't exist locally (skipped by a DST change) are passed over
+                for _ in 0..4 {
+                    local = cron.next_after(local)?;
+                    if let Some(t) = Local.from_local_datetime(&local).earliest() {
+                        return Some(t.naive_utc());
+                    }
+                }
+                None
+            }
+        }
+    }
+}
+
+/// Persisted state of a timer trigger, so schedules survive restarts
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ScheduleState {
+    pub schedule: String,
+    pub last_fire_at: Option<NaiveDateTime>,
+    pub next_fire_at: Option<NaiveDateTime>,
+    pub last_execution_id: Option<String>,
+}
+
+impl ScheduleState {
+    /// Advance the state to `now`, returning whether the trigger is due. Fires missed while the
+    /// app was closed are coalesced into a single run.
+    fn advance(&mut self, schedule: &Schedule, now: NaiveDateTime) -> bool {
+        let description = schedule.describe();
+        if self.schedule != description || self.next_fire_at.is_none() {
+            self.schedule = description;
+            self.next_fire_at = schedule.next_after(now);
+            return false;
+        }
+
+        match self.next_fire_at {
+            Some(next) if next <= now => {
+                self.next_fire_at = schedule.next_after(now);
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Start the background scheduler that fires `timer_trigger` workflows
+pub fn start<R: Runtime>(app_handle: &AppHandle<R>) {
+    let mut scheduler = WorkflowScheduler::new(app_handle.clone());
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(TICK);
+        loop {
+            interval.tick().await;
+            scheduler.tick(Utc::now().naive_utc()).await;
+        }
+    });
+}
+
+struct WorkflowScheduler<R: Runtime> {
+    app_handle: AppHandle<R>,
+    orchestrator: WorkflowOrchestrator<R>,
+    /// Latest execution started by each trigger node
+    running: HashMap<String, String>,
+    /// Trigger nodes that came due while still running, with the `queue` overlap policy
+    queued: HashSet<String>,
+}
+
+impl<R: Runtime> WorkflowScheduler<R> {
+    fn new(app_handle: AppHandle<R>) -> Self {
+        Self {
+            orchestrator: WorkflowOrchestrator::new(app_handle.clone()),
+            app_handle,
+            running: HashMap::new(),
+            queued: HashSet::new(),
+        }
+    }
+
+    async fn tick(&mut self, now: NaiveDateTime) {
+        let triggers = match self.list_timer_triggers() {
+            Ok(t) => t,
+            Err(e) => {
+                warn!("Failed to list timer triggers: {}", e);
+                return;
+            }
+        };
+
+        // Forget about triggers that were deleted or disabled
+        let ids: HashSet<&str> = triggers.iter().map(|n| n.id.as_str()).collect();
+        self.running.retain(|id, _| ids.contains(id.as_str()));
+        self.queued.retain(|id| ids.contains(id.as_str()));
+
+        for node in &triggers {
+            let schedule = match Schedule::from_config(&node.config) {
+                Ok(s) => s,
+                Err(e) => {
+                    warn!("Skipping timer trigger {}: {}", node.id, e);
+                    continue;
+                }
+            };
+
+            let mut state = self.load_state(&node.id);
+            let prev_state = state.clone();
+            let is_due = state.advance(&schedule, now);
+            let is_queued = self.queued.contains(&node.id);
+
+            if (is_due || is_queued) && self.is_running(&node.id) {
+                if is_due && node.config.get("overlap_policy").and_then(|v| v.as_str()) == Some("queue") {
+                    info!("Queueing timer trigger {} until the current run finishes", node.id);
+                    self.queued.insert(node.id.clone());
+                } else if is_due {
+                    info!("Skipping timer trigger {}, previous run is still going", node.id);
+                }
+            } else if is_due || is_queued {
+                self.queued.remove(&node.id);
+                let payload = json!({
+                    "firedAt": now,
+                    "scheduledAt": prev_state.next_fire_at,
+                    "schedule": state.schedule,
+                });
+                match self
+                    .orchestrator
+                    .execute_with_trigger(node.workflow_id.clone(), None, Some(payload))
+                    .await
+                {
+                    Ok(execution_id) => {
+                        info!("Timer trigger {} started execution {}", node.id, execution_id);
+                        self.running.insert(node.id.clone(), execution_id.clone());
+                        state.last_fire_at = Some(now);
+                        state.last_execution_id = Some(execution_id);
+                    }
+                    Err(e) => warn!("Timer trigger {} failed to start workflow: {}", node.id, e),
+                }
+            }
+
+            if state != prev_state {
+                self.save_state(&node.id, &state);
+            }
+        }
+    }
+
+    fn list_timer_triggers(&self) -> yaak_models::error::Result<Vec<WorkflowNode>> {
+        let db = self.app_handle.db();
+        let mut triggers = Vec::new();
+        for workspace in db.list_workspaces()? {
+            triggers.extend(
+                db.list_workflow_nodes_by_workspace(&workspace.id)?
+                    .into_iter()
+                    .filter(|n| n.node_subtype == "timer_trigger")
+                    .filter(|n| n.enabled && n.deleted_at.is_none()),
+            );
+        }
+        Ok(triggers)
+    }
+
+    fn is_running(&self, node_id: &str) -> bool {
+        let Some(execution_id) = self.running.get(node_id) else {
+            return false;
+        };
+        match self.app_handle.db().get_workflow_execution(execution_id) {
+            Ok(e) => matches!(e.state, WorkflowExecutionState::Initialized | WorkflowExecutionState::Running),
+            Err(_) => false,
+        }
+    }
+
+    fn load_state(&self, node_id: &str) -> ScheduleState {
+        self.app_handle
+            .db()
+            .get_key_value_raw(SCHEDULER_NAMESPACE, node_id)
+            .and_then(|kv| serde_json::from_str(&kv.value).ok())
+            .unwrap_or_default()
+    }
+
+    fn save_state(&self, node_id: &str, state: &ScheduleState) {
+        let value = serde_json::to_string(state).unwrap_or_default();
+        self.app_handle.db().set_key_value_raw(
+            SCHEDULER_NAMESPACE,
+            node_id,
+            &value,
+            &UpdateSource::Background,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    #[test]
+    fn test_schedule_from_config() {
+        assert_eq!(
+            Schedule::from_config(&json!({ "schedule_type": "interval", "interval_minutes": 60 })),
+            Ok(Schedule::Interval(Duration::minutes(60)))
+        );
+        assert!(Schedule::from_config(&json!({ "schedule_type": "cron", "cron_expression": "0 * * * *" })).is_ok());
+        assert!(Schedule::from_config(&json!({ "schedule_type": "cron", "cron_expression": "nope" })).is_err());
+        assert!(Schedule::from_config(&json!({ "schedule_type": "interval", "interval_minutes": 0 })).is_err());
+        assert!(Schedule::from_config(&json!({ "schedule_type": "weekly" })).is_err());
+    }
+
+    #[test]
+    fn test_advance() {
+        let schedule = Schedule::Interval(Duration::minutes(60));
+        let mut state = ScheduleState::default();
+
+        // First sight of a schedule only plans the next run
+        assert!(!state.advance(&schedule, at("2025-01-01 10:00")));
+        assert_eq!(state.next_fire_at, Some(at("2025-01-01 11:00")));
+
+        assert!(!state.advance(&schedule, at("2025-01-01 10:59")));
+        assert!(state.advance(&schedule, at("2025-01-01 11:00")));
+        assert_eq!(state.next_fire_at, Some(at("2025-01-01 12:00")));
+
+        // Runs missed while the app was closed only fire once
+        assert!(state.advance(&schedule, at("2025-01-01 18:30")));
+        assert_eq!(state.next_fire_at, Some(at("2025-01-01 19:30")));
+        assert!(!state.advance(&schedule, at("2025-01-01 18:31")));
+
+        // Changing the schedule starts over
+        let schedule = Schedule::Interval(Duration::minutes(5));
+        assert!(!state.advance(&schedule, at("2025-01-01 20:00")));
+        assert_eq!(state.next_fire_at, Some(at("2025-01-01 20:05")));
+    }
+}
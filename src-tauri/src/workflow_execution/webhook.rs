@@ -0,0 +1,311 @@
+use http::{HeaderMap, Method, StatusCode};
+use http_body_util::{BodyExt, Full, Limited};
+use hyper::body::{Bytes, Incoming};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Request, Response};
+use hyper_util::rt::TokioIo;
+use log::{debug, info, warn};
+use serde_json::{json, Map, Value as JsonValue};
+use std::convert::Infallible;
+use std::sync::OnceLock;
+use tauri::{AppHandle, Runtime};
+use tokio::net::TcpListener;
+use yaak_models::models::WorkflowNode;
+use yaak_models::query_manager::QueryManagerExt;
+
+use super::orchestrator::WorkflowOrchestrator;
+
+/// Port the local webhook listener binds to, on the loopback interface only. It can be changed
+/// with the `YAAK_WEBHOOK_PORT` environment variable, eg. when something else uses it already.
+pub const WEBHOOK_PORT: u16 = 9431;
+
+/// Requests with larger bodies are rejected
+const MAX_BODY_SIZE: usize = 10 * 1024 * 1024;
+
+/// Base URL of the listener once it is bound, or why it couldn't be
+static BASE_URL: OnceLock<Result<String, String>> = OnceLock::new();
+
+/// URL the listener is reachable at, `None` while it's starting, or why it failed to start
+pub fn base_url() -> Result<Option<String>, String> {
+    BASE_URL.get().cloned().transpose()
+}
+
+fn port() -> u16 {
+    match std::env::var("YAAK_WEBHOOK_PORT") {
+        Ok(p) => p.parse().unwrap_or_else(|_| {
+            warn!("Invalid YAAK_WEBHOOK_PORT {}, using {}", p, WEBHOOK_PORT);
+            WEBHOOK_PORT
+        }),
+        Err(_) => WEBHOOK_PORT,
+    }
+}
+
+/// Start the local HTTP listener that starts `webhook_trigger` workflows. Every enabled trigger
+/// is served at `/webhooks/<node_id>`.
+pub fn start<R: Runtime>(app_handle: &AppHandle<R>) {
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        let port = port();
+        let listener = match TcpListener::bind(("127.0.0.1", port)).await {
+            Ok(l) => l,
+            Err(e) => {
+                // Shown where the webhook URL would be, so a port that's in use doesn't go unnoticed
+                let error = format!(
+                    "Failed to listen on port {}: {}. Set YAAK_WEBHOOK_PORT to use another port.",
+                    port, e
+                );
+                warn!("Failed to start webhook listener: {}", error);
+                let _ = BASE_URL.set(Err(error));
+                return;
+            }
+        };
+
+        let _ = BASE_URL.set(Ok(format!("http://127.0.0.1:{}", port)));
+        info!("Webhook listener running on port {}", port);
+
+        loop {
+            let stream = match listener.accept().await {
+                Ok((stream, _)) => stream,
+                Err(e) => {
+                    warn!("Failed to accept webhook connection: {}", e);
+                    continue;
+                }
+            };
+
+            let app_handle = app_handle.clone();
+            tauri::async_runtime::spawn(async move {
+                let service = service_fn(move |req| handle_request(app_handle.clone(), port, req));
+                if let Err(e) = http1::Builder::new().serve_connection(TokioIo::new(stream), service).await {
+                    debug!("Webhook connection error: {}", e);
+                }
+            });
+        }
+    });
+}
+
+async fn handle_request<R: Runtime>(
+    app_handle: AppHandle<R>,
+    port: u16,
+    req: Request<Incoming>,
+) -> Result<Response<Full<Bytes>>, Infallible> {
+    // Web pages can reach the listener through DNS rebinding, but never with a loopback Host
+    if !is_local_host(req.headers(), port) {
+        return Ok(respond(StatusCode::MISDIRECTED_REQUEST, json!({ "error": "Invalid Host header" })));
+    }
+
+    let node = match req.uri().path().strip_prefix("/webhooks/") {
+        Some(node_id) => find_trigger(&app_handle, node_id.trim_end_matches('/')),
+        None => None,
+    };
+    let Some(node) = node else {
+        return Ok(respond(StatusCode::NOT_FOUND, json!({ "error": "No webhook trigger at this path" })));
+    };
+
+    if let Err((status, message)) = authorize(&node.config, req.method(), req.headers()) {
+        let mut response = respond(status, json!({ "error": message }));
+        if status == StatusCode::METHOD_NOT_ALLOWED {
+            if let Ok(allow) = allowed_method(&node.config).as_str().parse() {
+                response.headers_mut().insert(http::header::ALLOW, allow);
+            }
+        }
+        return Ok(response);
+    }
+
+    let (parts, body) = req.into_parts();
+    let body = match Limited::new(body, MAX_BODY_SIZE).collect().await {
+        Ok(b) => b.to_bytes(),
+        Err(_) => {
+            return Ok(respond(StatusCode::PAYLOAD_TOO_LARGE, json!({ "error": "Body is too large" })));
+        }
+    };
+
+    let payload = build_payload(&parts.method, &parts.uri, &parts.headers, &body);
+    let orchestrator = WorkflowOrchestrator::new(app_handle);
+    match orchestrator.execute_with_trigger(node.workflow_id.clone(), None, Some(payload)).await {
+        Ok(execution_id) => {
+            info!("Webhook trigger {} started execution {}", node.id, execution_id);
+            Ok(respond(StatusCode::ACCEPTED, json!({ "executionId": execution_id })))
+        }
+        Err(e) => {
+            warn!("Webhook trigger {} failed to start workflow: {}", node.id, e);
+            Ok(respond(StatusCode::INTERNAL_SERVER_ERROR, json!({ "error": e.to_string() })))
+        }
+    }
+}
+
+fn find_trigger<R: Runtime>(app_handle: &AppHandle<R>, node_id: &str) -> Option<WorkflowNode> {
+    app_handle
+        .db()
+        .get_workflow_node(node_id)
+        .ok()
+        .filter(|n| n.node_subtype == "webhook_trigger" && n.enabled && n.deleted_at.is_none())
+}
+
+fn is_local_host(headers: &HeaderMap, port: u16) -> bool {
+    let Some(host) = headers.get(http::header::HOST).and_then(|v| v.to_str().ok()) else {
+        return false;
+    };
+    ["127.0.0.1", "localhost"]
+        .iter()
+        .any(|name| host.eq_ignore_ascii_case(&format!("{}:{}", name, port)))
+}
+
+fn allowed_method(config: &JsonValue) -> String {
+    config.get("method").and_then(|m| m.as_str()).unwrap_or("POST").to_uppercase()
+}
+
+/// Check the request method and the trigger's `auth_token`, which can be sent either as a
+/// bearer token or in the `X-Webhook-Token` header. Triggers without a token reject every
+/// request unless `allow_unauthenticated` is set.
+fn authorize(
+    config: &JsonValue,
+    method: &Method,
+    headers: &HeaderMap,
+) -> Result<(), (StatusCode, String)> {
+    let allowed = allowed_method(config);
+    if method.as_str() != allowed {
+        return Err((StatusCode::METHOD_NOT_ALLOWED, format!("Expected {} request", allowed)));
+    }
+
+    let expected = config.get("auth_token").and_then(|t| t.as_str()).unwrap_or_default();
+    if expected.is_empty() {
+        if config.get("allow_unauthenticated").and_then(|v| v.as_bool()).unwrap_or(false) {
+            return Ok(());
+        }
+        return Err((StatusCode::UNAUTHORIZED, "Webhook trigger has no auth token set".to_string()));
+    }
+
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+    let provided = header("authorization")
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .or_else(|| header("x-webhook-token"))
+        .unwrap_or_default();
+
+    if constant_time_eq(provided.trim().as_bytes(), expected.as_bytes()) {
+        Ok(())
+    } else {
+        Err((StatusCode::UNAUTHORIZED, "Invalid or missing auth token".to_string()))
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Trigger node output for an incoming request. JSON bodies are parsed, anything else is
+/// passed through as text.
+fn build_payload(method: &Method, uri: &http::Uri, headers: &HeaderMap, body: &[u8]) -> JsonValue {
+    let mut header_map = Map::new();
+    for name in headers.keys() {
+        let values: Vec<&str> = headers.get_all(name).iter().filter_map(|v| v.to_str().ok()).collect();
+        header_map.insert(name.as_str().to_string(), json!(values.join(", ")));
+    }
+
+    // Repeated query params become arrays
+    let mut query = Map::new();
+    let url = reqwest::Url::parse(&format!("http://localhost{}", uri)).ok();
+    for (k, v) in url.iter().flat_map(|u| u.query_pairs()) {
+        let v = JsonValue::String(v.to_string());
+        match query.get_mut(k.as_ref()) {
+            Some(JsonValue::Array(values)) => values.push(v),
+            Some(existing) => *existing = json!([existing.clone(), v]),
+            None => {
+                query.insert(k.to_string(), v);
+            }
+        }
+    }
+
+    let text = String::from_utf8_lossy(body).to_string();
+    let body = match serde_json::from_slice::<JsonValue>(body) {
+        Ok(v) => v,
+        Err(_) if text.is_empty() => JsonValue::Null,
+        Err(_) => JsonValue::String(text.clone()),
+    };
+
+    json!({
+        "method": method.as_str(),
+        "path": uri.path(),
+        "headers": header_map,
+        "query": query,
+        "body": body,
+        "rawBody": text,
+        "receivedAt": chrono::Utc::now().naive_utc(),
+    })
+}
+
+fn respond(status: StatusCode, body: JsonValue) -> Response<Full<Bytes>> {
+    let mut response = Response::new(Full::new(Bytes::from(body.to_string())));
+    *response.status_mut() = status;
+    response.headers_mut().insert(
+        http::header::CONTENT_TYPE,
+        http::HeaderValue::from_static("application/json"),
+    );
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::HeaderValue;
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (k, v) in pairs {
+            headers.append(*k, HeaderValue::from_static(v));
+        }
+        headers
+    }
+
+    #[test]
+    fn test_authorize() {
+        let config = json!({ "method": "POST", "auth_token": "s3cret" });
+
+        let ok = headers(&[("authorization", "Bearer s3cret")]);
+        assert!(authorize(&config, &Method::POST, &ok).is_ok());
+        let ok = headers(&[("x-webhook-token", "s3cret")]);
+        assert!(authorize(&config, &Method::POST, &ok).is_ok());
+
+        let err = authorize(&config, &Method::GET, &ok).unwrap_err();
+        assert_eq!(err.0, StatusCode::METHOD_NOT_ALLOWED);
+        let err = authorize(&config, &Method::POST, &headers(&[("authorization", "Bearer nope")])).unwrap_err();
+        assert_eq!(err.0, StatusCode::UNAUTHORIZED);
+        let err = authorize(&config, &Method::POST, &HeaderMap::new()).unwrap_err();
+        assert_eq!(err.0, StatusCode::UNAUTHORIZED);
+
+        // No token configured rejects everything, unless that's explicitly allowed
+        let config = json!({ "method": "put", "auth_token": "" });
+        let err = authorize(&config, &Method::PUT, &HeaderMap::new()).unwrap_err();
+        assert_eq!(err.0, StatusCode::UNAUTHORIZED);
+        let config = json!({ "method": "put", "auth_token": "", "allow_unauthenticated": true });
+        assert!(authorize(&config, &Method::PUT, &HeaderMap::new()).is_ok());
+    }
+
+    #[test]
+    fn test_is_local_host() {
+        assert!(is_local_host(&headers(&[("host", "127.0.0.1:9431")]), 9431));
+        assert!(is_local_host(&headers(&[("host", "LOCALHOST:9431")]), 9431));
+        assert!(!is_local_host(&headers(&[("host", "localhost:80")]), 9431));
+        assert!(!is_local_host(&headers(&[("host", "attacker.example:9431")]), 9431));
+        assert!(!is_local_host(&HeaderMap::new(), 9431));
+    }
+
+    #[test]
+    fn test_build_payload() {
+        let uri: http::Uri = "/webhooks/wn_1?event=paid&tag=a&tag=b%20c".parse().unwrap();
+        let headers = headers(&[("content-type", "application/json"), ("x-sig", "1"), ("x-sig", "2")]);
+        let payload = build_payload(&Method::POST, &uri, &headers, br#"{"amount": 42}"#);
+
+        assert_eq!(payload["method"], json!("POST"));
+        assert_eq!(payload["path"], json!("/webhooks/wn_1"));
+        assert_eq!(payload["headers"]["x-sig"], json!("1, 2"));
+        assert_eq!(payload["query"]["event"], json!("paid"));
+        assert_eq!(payload["query"]["tag"], json!(["a", "b c"]));
+        assert_eq!(payload["body"]["amount"], json!(42));
+
+        let payload = build_payload(&Method::POST, &uri, &HeaderMap::new(), b"a=1&b=2");
+        assert_eq!(payload["body"], json!("a=1&b=2"));
+        let payload = build_payload(&Method::GET, &uri, &HeaderMap::new(), b"");
+        assert_eq!(payload["body"], JsonValue::Null);
+    }
+}
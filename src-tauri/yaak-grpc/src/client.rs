@@ -1,5 +1,5 @@
 use crate::manager::decorate_req;
-use crate::transport::get_transport;
+use crate::transport::{GrpcResolver, get_transport};
 use async_recursion::async_recursion;
 use hyper_rustls::HttpsConnector;
 use hyper_util::client::legacy::Client;
@@ -18,28 +18,34 @@ use tonic_reflection::pb::v1::{
 };
 use tonic_reflection::pb::v1::{ExtensionRequest, FileDescriptorResponse};
 use tonic_reflection::pb::{v1, v1alpha};
+use yaak_http::dns::DnsResolver;
+use yaak_http::tls::TlsOptions;
 
-pub struct AutoReflectionClient<T = Client<HttpsConnector<HttpConnector>, BoxBody>> {
+pub struct AutoReflectionClient<T = Client<HttpsConnector<HttpConnector<GrpcResolver>>, BoxBody>> {
     use_v1alpha: bool,
     client_v1: v1::server_reflection_client::ServerReflectionClient<T>,
     client_v1alpha: v1alpha::server_reflection_client::ServerReflectionClient<T>,
 }
 
 impl AutoReflectionClient {
-    pub fn new(uri: &Uri, validate_certificates: bool) -> Self {
+    pub fn new(
+        uri: &Uri,
+        tls_options: &TlsOptions,
+        dns_resolver: &DnsResolver,
+    ) -> Result<Self, String> {
         let client_v1 = v1::server_reflection_client::ServerReflectionClient::with_origin(
-            get_transport(validate_certificates),
+            get_transport(uri, tls_options, dns_resolver)?,
             uri.clone(),
         );
         let client_v1alpha = v1alpha::server_reflection_client::ServerReflectionClient::with_origin(
-            get_transport(validate_certificates),
+            get_transport(uri, tls_options, dns_resolver)?,
             uri.clone(),
         );
-        AutoReflectionClient {
+        Ok(AutoReflectionClient {
             use_v1alpha: false,
             client_v1,
             client_v1alpha,
-        }
+        })
     }
 
     #[async_recursion]
@@ -17,13 +17,15 @@ use tonic::{IntoRequest, IntoStreamingRequest, Request, Response, Status, Stream
 
 use crate::codec::DynamicCodec;
 use crate::reflection::{fill_pool_from_files, fill_pool_from_reflection, method_desc_to_path};
-use crate::transport::get_transport;
+use crate::transport::{GrpcResolver, get_transport};
 use crate::{json_schema, MethodDefinition, ServiceDefinition};
+use yaak_http::dns::DnsResolver;
+use yaak_http::tls::TlsOptions;
 
 #[derive(Clone)]
 pub struct GrpcConnection {
     pool: DescriptorPool,
-    conn: Client<HttpsConnector<HttpConnector>, BoxBody>,
+    conn: Client<HttpsConnector<HttpConnector<GrpcResolver>>, BoxBody>,
     pub uri: Uri,
 }
 
@@ -181,11 +183,12 @@ impl GrpcHandle {
         uri: &str,
         proto_files: &Vec<PathBuf>,
         metadata: &BTreeMap<String, String>,
-        validate_certificates: bool,
+        tls_options: &TlsOptions,
+        dns_resolver: &DnsResolver,
     ) -> Result<(), String> {
         let pool = if proto_files.is_empty() {
             let full_uri = uri_from_str(uri)?;
-            fill_pool_from_reflection(&full_uri, metadata, validate_certificates).await
+            fill_pool_from_reflection(&full_uri, metadata, tls_options, dns_resolver).await
         } else {
             fill_pool_from_files(&self.app_handle, proto_files).await
         }?;
@@ -200,10 +203,11 @@ impl GrpcHandle {
         uri: &str,
         proto_files: &Vec<PathBuf>,
         metadata: &BTreeMap<String, String>,
-        validate_certificates: bool,
+        tls_options: &TlsOptions,
+        dns_resolver: &DnsResolver,
     ) -> Result<Vec<ServiceDefinition>, String> {
         // Ensure reflection is up-to-date
-        self.reflect(id, uri, proto_files, metadata, validate_certificates).await?;
+        self.reflect(id, uri, proto_files, metadata, tls_options, dns_resolver).await?;
 
         let pool = self.get_pool(id, uri, proto_files).ok_or("Failed to get pool".to_string())?;
         Ok(self.services_from_pool(&pool))
@@ -240,13 +244,14 @@ impl GrpcHandle {
         uri: &str,
         proto_files: &Vec<PathBuf>,
         metadata: &BTreeMap<String, String>,
-        validate_certificates: bool,
+        tls_options: &TlsOptions,
+        dns_resolver: &DnsResolver,
     ) -> Result<GrpcConnection, String> {
-        self.reflect(id, uri, proto_files, metadata, validate_certificates).await?;
+        self.reflect(id, uri, proto_files, metadata, tls_options, dns_resolver).await?;
         let pool = self.get_pool(id, uri, proto_files).ok_or("Failed to get pool")?;
 
         let uri = uri_from_str(uri)?;
-        let conn = get_transport(validate_certificates);
+        let conn = get_transport(&uri, tls_options, dns_resolver)?;
         let connection = GrpcConnection {
             pool: pool.clone(),
             conn,
@@ -19,6 +19,8 @@ use tonic::codegen::http::uri::PathAndQuery;
 use tonic::transport::Uri;
 use tonic_reflection::pb::v1::server_reflection_request::MessageRequest;
 use tonic_reflection::pb::v1::server_reflection_response::MessageResponse;
+use yaak_http::dns::DnsResolver;
+use yaak_http::tls::TlsOptions;
 
 pub async fn fill_pool_from_files(
     app_handle: &AppHandle,
@@ -120,10 +122,11 @@ pub async fn fill_pool_from_files(
 pub async fn fill_pool_from_reflection(
     uri: &Uri,
     metadata: &BTreeMap<String, String>,
-    validate_certificates: bool,
+    tls_options: &TlsOptions,
+    dns_resolver: &DnsResolver,
 ) -> Result<DescriptorPool, String> {
     let mut pool = DescriptorPool::new();
-    let mut client = AutoReflectionClient::new(uri, validate_certificates);
+    let mut client = AutoReflectionClient::new(uri, tls_options, dns_resolver)?;
 
     for service in list_services(&mut client, metadata).await? {
         if service == "grpc.reflection.v1alpha.ServerReflection" {
@@ -1,25 +1,67 @@
 use hyper_rustls::{HttpsConnector, HttpsConnectorBuilder};
+use hyper_util::client::legacy::connect::dns::Name;
 use hyper_util::client::legacy::connect::HttpConnector;
 use hyper_util::client::legacy::Client;
 use hyper_util::rt::TokioExecutor;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::task::{Context, Poll};
 use tonic::body::BoxBody;
+use tonic::transport::Uri;
+use tower_service::Service;
+use yaak_http::dns::DnsResolver;
+use yaak_http::tls::{TlsOptions, default_port};
 
 // I think ALPN breaks this because we're specifying http2_only
 const WITH_ALPN: bool = false;
 
-pub(crate) fn get_transport(validate_certificates: bool) -> Client<HttpsConnector<HttpConnector>, BoxBody> {
-    let tls_config = yaak_http::tls::get_config(validate_certificates, WITH_ALPN);
+pub(crate) fn get_transport(
+    uri: &Uri,
+    tls_options: &TlsOptions,
+    dns_resolver: &DnsResolver,
+) -> Result<Client<HttpsConnector<HttpConnector<GrpcResolver>>, BoxBody>, String> {
+    let host = uri.host().unwrap_or_default();
+    let port = uri.port_u16().or_else(|| uri.scheme_str().and_then(default_port));
+    let tls_config = yaak_http::tls::get_config(tls_options, host, port, WITH_ALPN)
+        .map_err(|e| e.to_string())?;
 
-    let mut http = HttpConnector::new();
+    let mut http = HttpConnector::new_with_resolver(GrpcResolver(dns_resolver.clone()));
     http.enforce_http(false);
 
-    let connector =
-        HttpsConnectorBuilder::new().with_tls_config(tls_config).https_or_http().enable_http2().build();
+    let connector = HttpsConnectorBuilder::new()
+        .with_tls_config(tls_config)
+        .https_or_http()
+        .enable_http2()
+        .wrap_connector(http);
 
     let client = Client::builder(TokioExecutor::new())
         .pool_max_idle_per_host(0)
         .http2_only(true)
         .build(connector);
 
-    client
+    Ok(client)
+}
+
+/// Resolves hosts for the gRPC connector with the workspace's DNS settings
+#[derive(Clone)]
+pub(crate) struct GrpcResolver(DnsResolver);
+
+impl Service<Name> for GrpcResolver {
+    type Response = std::vec::IntoIter<SocketAddr>;
+    type Error = yaak_http::error::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, name: Name) -> Self::Future {
+        let resolver = self.0.clone();
+        Box::pin(async move {
+            // The connector fills in the port
+            let addrs = resolver.lookup(name.as_str()).await?;
+            Ok(addrs.into_iter().map(|ip| SocketAddr::new(ip, 0)).collect::<Vec<_>>().into_iter())
+        })
+    }
 }
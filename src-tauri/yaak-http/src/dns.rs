@@ -0,0 +1,331 @@
+use crate::error::Error::DnsError;
+use crate::error::Result;
+use crate::tls::{TlsOptions, get_config, host_matches};
+use reqwest::header::{ACCEPT, CONTENT_TYPE};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::Duration;
+use tokio::net::{UdpSocket, lookup_host};
+use tokio::time::timeout;
+use yaak_models::models::{DnsOverride, DnsResolverSetting, Workspace};
+
+const QUERY_TIMEOUT: Duration = Duration::from_secs(5);
+const DNS_MESSAGE: &str = "application/dns-message";
+const TYPE_A: u16 = 1;
+const TYPE_AAAA: u16 = 28;
+
+/// Resolves hosts for HTTP, gRPC and WebSocket connections, following the workspace's overrides
+/// and resolver setting
+#[derive(Debug, Clone, Default)]
+pub struct DnsResolver {
+    pub overrides: Vec<DnsOverride>,
+    pub setting: DnsResolverSetting,
+}
+
+impl From<&Workspace> for DnsResolver {
+    fn from(workspace: &Workspace) -> Self {
+        DnsResolver {
+            overrides: workspace.setting_dns_overrides.clone(),
+            setting: workspace.setting_dns_resolver.clone(),
+        }
+    }
+}
+
+enum Transport {
+    Udp(SocketAddr),
+    Https(reqwest::Client, String),
+}
+
+impl DnsResolver {
+    /// Look up the addresses of a host, IPv4 first
+    pub async fn lookup(&self, host: &str) -> Result<Vec<IpAddr>> {
+        let host = host.trim_start_matches('[').trim_end_matches(']').to_lowercase();
+        if let Ok(ip) = host.parse::<IpAddr>() {
+            return Ok(vec![ip]);
+        }
+
+        if let Some(addrs) = self.overridden(&host)? {
+            return Ok(addrs);
+        }
+
+        if host == "localhost" || host.ends_with(".localhost") {
+            return Ok(vec![
+                IpAddr::V4(Ipv4Addr::LOCALHOST),
+                IpAddr::V6(Ipv6Addr::LOCALHOST),
+            ]);
+        }
+
+        let transport = match &self.setting {
+            DnsResolverSetting::System => {
+                let addrs = lookup_host((host.as_str(), 0))
+                    .await
+                    .map_err(|e| DnsError(format!("Failed to resolve {host}: {e}")))?;
+                return Ok(addrs.map(|a| a.ip()).collect());
+            }
+            DnsResolverSetting::Nameserver { address } => Transport::Udp(nameserver_addr(address)?),
+            DnsResolverSetting::Https { url } => Transport::Https(https_client()?, url.clone()),
+        };
+
+        let (v4, v6) =
+            tokio::join!(query(&transport, &host, TYPE_A), query(&transport, &host, TYPE_AAAA));
+        let addrs: Vec<IpAddr> = match (v4, v6) {
+            (Err(e), Err(_)) => return Err(e),
+            (v4, v6) => v4.unwrap_or_default().into_iter().chain(v6.unwrap_or_default()).collect(),
+        };
+
+        if addrs.is_empty() {
+            return Err(DnsError(format!("No addresses found for {host}")));
+        }
+        Ok(addrs)
+    }
+
+    /// Addresses of the enabled overrides that match a host, if any
+    fn overridden(&self, host: &str) -> Result<Option<Vec<IpAddr>>> {
+        let addrs = self
+            .overrides
+            .iter()
+            .filter(|o| o.enabled && host_matches(&o.hostname, host, None))
+            .map(|o| {
+                o.ip.trim().parse::<IpAddr>().map_err(|_| {
+                    DnsError(format!("Invalid override address \"{}\" for {}", o.ip, o.hostname))
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(if addrs.is_empty() { None } else { Some(addrs) })
+    }
+}
+
+fn nameserver_addr(address: &str) -> Result<SocketAddr> {
+    let address = address.trim();
+    address
+        .parse::<SocketAddr>()
+        .or_else(|_| address.parse::<IpAddr>().map(|ip| SocketAddr::new(ip, 53)))
+        .map_err(|_| DnsError(format!("Invalid nameserver address \"{address}\"")))
+}
+
+fn https_client() -> Result<reqwest::Client> {
+    let options = TlsOptions {
+        validate_certificates: true,
+        ..Default::default()
+    };
+    reqwest::Client::builder()
+        .use_preconfigured_tls(get_config(&options, "", None, true)?)
+        .timeout(QUERY_TIMEOUT)
+        .build()
+        .map_err(|e| DnsError(e.to_string()))
+}
+
+async fn query(transport: &Transport, host: &str, record_type: u16) -> Result<Vec<IpAddr>> {
+    match transport {
+        Transport::Udp(server) => {
+            // The ID is all that ties a response to its query, so it shouldn't be guessable
+            let id = RandomState::new().build_hasher().finish() as u16;
+            let response = exchange_udp(*server, &build_query(id, host, record_type)?).await?;
+            parse_response(id, &response)
+        }
+        Transport::Https(client, url) => {
+            // RFC 8484 asks for an ID of 0 so responses can be cached
+            let response = client
+                .post(url)
+                .header(CONTENT_TYPE, DNS_MESSAGE)
+                .header(ACCEPT, DNS_MESSAGE)
+                .body(build_query(0, host, record_type)?)
+                .send()
+                .await
+                .and_then(|r| r.error_for_status())
+                .map_err(|e| DnsError(format!("DNS-over-HTTPS query to {url} failed: {e}")))?;
+            let bytes = response.bytes().await.map_err(|e| DnsError(e.to_string()))?;
+            parse_response(0, &bytes)
+        }
+    }
+}
+
+async fn exchange_udp(server: SocketAddr, query: &[u8]) -> Result<Vec<u8>> {
+    let io_err = |e: std::io::Error| DnsError(format!("DNS query to {server} failed: {e}"));
+    let local: SocketAddr = match server {
+        SocketAddr::V4(_) => (Ipv4Addr::UNSPECIFIED, 0).into(),
+        SocketAddr::V6(_) => (Ipv6Addr::UNSPECIFIED, 0).into(),
+    };
+
+    let socket = UdpSocket::bind(local).await.map_err(io_err)?;
+    socket.connect(server).await.map_err(io_err)?;
+    socket.send(query).await.map_err(io_err)?;
+
+    let mut buf = vec![0; 4096];
+    let len = timeout(QUERY_TIMEOUT, socket.recv(&mut buf))
+        .await
+        .map_err(|_| DnsError(format!("DNS query to {server} timed out")))?
+        .map_err(io_err)?;
+    buf.truncate(len);
+    Ok(buf)
+}
+
+/// A DNS message with a single recursive question for a host
+fn build_query(id: u16, host: &str, record_type: u16) -> Result<Vec<u8>> {
+    let mut msg = Vec::with_capacity(host.len() + 18);
+    msg.extend_from_slice(&id.to_be_bytes());
+    msg.extend_from_slice(&[0x01, 0x00]); // Recursion desired
+    msg.extend_from_slice(&[0, 1, 0, 0, 0, 0, 0, 0]); // One question, no other records
+
+    for label in host.trim_end_matches('.').split('.') {
+        if label.is_empty() || label.len() > 63 {
+            return Err(DnsError(format!("Invalid host name \"{host}\"")));
+        }
+        msg.push(label.len() as u8);
+        msg.extend_from_slice(label.as_bytes());
+    }
+    msg.push(0);
+
+    msg.extend_from_slice(&record_type.to_be_bytes());
+    msg.extend_from_slice(&[0, 1]); // Class IN
+    Ok(msg)
+}
+
+/// The A and AAAA addresses in the answers of a DNS response
+fn parse_response(id: u16, msg: &[u8]) -> Result<Vec<IpAddr>> {
+    let invalid = || DnsError("Invalid DNS response".to_string());
+    let u16_at =
+        |i: usize| msg.get(i..i + 2).map(|b| u16::from_be_bytes([b[0], b[1]])).ok_or_else(invalid);
+
+    if u16_at(0)? != id {
+        return Err(DnsError("DNS response doesn't match the query".to_string()));
+    }
+
+    match u16_at(2)? & 0x000f {
+        0 => {}
+        3 => return Ok(Vec::new()), // The name doesn't exist
+        code => return Err(DnsError(format!("DNS server responded with error code {code}"))),
+    }
+
+    let questions = u16_at(4)?;
+    let answers = u16_at(6)?;
+
+    let mut i = 12;
+    for _ in 0..questions {
+        i = skip_name(msg, i).ok_or_else(invalid)? + 4;
+    }
+
+    let mut addrs = Vec::new();
+    for _ in 0..answers {
+        i = skip_name(msg, i).ok_or_else(invalid)?;
+        let record_type = u16_at(i)?;
+        let len = u16_at(i + 8)? as usize;
+        let data = msg.get(i + 10..i + 10 + len).ok_or_else(invalid)?;
+        match record_type {
+            TYPE_A => addrs.extend(<[u8; 4]>::try_from(data).ok().map(IpAddr::from)),
+            TYPE_AAAA => addrs.extend(<[u8; 16]>::try_from(data).ok().map(IpAddr::from)),
+            // CNAMEs and such, which the server already followed for us
+            _ => {}
+        }
+        i += 10 + len;
+    }
+
+    Ok(addrs)
+}
+
+/// Index right after the (possibly compressed) name that starts at `i`
+fn skip_name(msg: &[u8], mut i: usize) -> Option<usize> {
+    loop {
+        match *msg.get(i)? {
+            0 => return Some(i + 1),
+            len if len & 0xc0 == 0xc0 => return Some(i + 2),
+            len => i += 1 + len as usize,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dns_override(hostname: &str, ip: &str) -> DnsOverride {
+        DnsOverride {
+            enabled: true,
+            hostname: hostname.to_string(),
+            ip: ip.to_string(),
+        }
+    }
+
+    #[test]
+    fn overrides() {
+        let resolver = DnsResolver {
+            overrides: vec![
+                dns_override("api.example.com", "10.0.0.1"),
+                dns_override("*.staging.example.com", "::1"),
+                DnsOverride {
+                    enabled: false,
+                    ..dns_override("www.example.com", "10.0.0.2")
+                },
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            resolver.overridden("api.example.com").unwrap(),
+            Some(vec!["10.0.0.1".parse().unwrap()])
+        );
+        assert_eq!(
+            resolver.overridden("a.staging.example.com").unwrap(),
+            Some(vec!["::1".parse().unwrap()])
+        );
+        assert_eq!(resolver.overridden("www.example.com").unwrap(), None);
+        assert_eq!(resolver.overridden("example.com").unwrap(), None);
+    }
+
+    #[test]
+    fn invalid_override() {
+        let resolver = DnsResolver {
+            overrides: vec![dns_override("api.example.com", "not-an-ip")],
+            ..Default::default()
+        };
+        assert!(resolver.overridden("api.example.com").is_err());
+        assert!(resolver.overridden("other.example.com").is_ok());
+    }
+
+    #[test]
+    fn nameserver_addresses() {
+        assert_eq!(nameserver_addr("1.1.1.1").unwrap(), "1.1.1.1:53".parse().unwrap());
+        assert_eq!(nameserver_addr(" 10.0.0.2:5353 ").unwrap(), "10.0.0.2:5353".parse().unwrap());
+        assert_eq!(nameserver_addr("::1").unwrap(), "[::1]:53".parse().unwrap());
+        assert!(nameserver_addr("dns.example.com").is_err());
+    }
+
+    #[test]
+    fn query_message() {
+        let msg = build_query(0xabcd, "api.example.com", TYPE_AAAA).unwrap();
+        let mut expected = vec![0xab, 0xcd, 0x01, 0x00, 0, 1, 0, 0, 0, 0, 0, 0];
+        expected.extend_from_slice(b"\x03api\x07example\x03com\x00");
+        expected.extend_from_slice(&[0, 28, 0, 1]);
+        assert_eq!(msg, expected);
+
+        assert!(build_query(0, "api..example.com", TYPE_A).is_err());
+    }
+
+    #[test]
+    fn response_message() {
+        let mut msg = build_query(7, "example.com", TYPE_A).unwrap();
+        msg[2] = 0x81; // A response
+        msg[3] = 0x80;
+        msg[7] = 3; // Three answers
+
+        // A CNAME pointing back at the question's name, then two addresses
+        msg.extend_from_slice(&[0xc0, 12, 0, 5, 0, 1, 0, 0, 0, 60, 0, 2, 0xc0, 12]);
+        msg.extend_from_slice(&[0xc0, 12, 0, 1, 0, 1, 0, 0, 0, 60, 0, 4, 93, 184, 216, 34]);
+        msg.extend_from_slice(&[0xc0, 12, 0, 28, 0, 1, 0, 0, 0, 60, 0, 16]);
+        msg.extend_from_slice(&Ipv6Addr::LOCALHOST.octets());
+
+        assert_eq!(
+            parse_response(7, &msg).unwrap(),
+            vec![
+                "93.184.216.34".parse::<IpAddr>().unwrap(),
+                "::1".parse().unwrap()
+            ]
+        );
+        assert!(parse_response(8, &msg).is_err());
+        assert!(parse_response(7, &msg[..msg.len() - 1]).is_err());
+
+        msg[3] = 0x83; // The name doesn't exist
+        assert_eq!(parse_response(7, &msg).unwrap(), vec![]);
+    }
+}
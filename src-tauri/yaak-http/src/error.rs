@@ -0,0 +1,15 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("TLS error: {0}")]
+    TlsError(#[from] rustls::Error),
+
+    #[error("Certificate error: {0}")]
+    CertificateError(String),
+
+    #[error("DNS error: {0}")]
+    DnsError(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
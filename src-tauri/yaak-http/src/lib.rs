@@ -1,3 +1,5 @@
+pub mod dns;
+pub mod error;
 pub mod tls;
 
 use yaak_models::models::HttpUrlParameter;
@@ -1,30 +1,307 @@
+use crate::error::Error::CertificateError;
+use crate::error::Result;
+use p12_keystore::KeyStore;
+use regex::Regex;
 use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::client::{
+    ClientSessionMemoryCache, ClientSessionStore, Resumption, Tls12ClientSessionValue,
+    Tls13ClientSessionValue,
+};
 use rustls::crypto::ring;
-use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
-use rustls::{ClientConfig, DigitallySignedStruct, SignatureScheme};
-use rustls_platform_verifier::BuilderVerifierExt;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer, ServerName, UnixTime};
+use rustls::{
+    CipherSuite, ClientConfig, DigitallySignedStruct, DistinguishedName, NamedGroup,
+    ProtocolVersion, SignatureScheme,
+};
+use rustls_platform_verifier::Verifier;
+use std::fmt::Debug;
+use std::fs;
 use std::sync::Arc;
+use yaak_models::models::{CaCertificate, ClientCertificate, Workspace};
 
-pub fn get_config(validate_certificates: bool, with_alpn: bool) -> ClientConfig {
+/// Everything that decides how a TLS connection to a host is set up
+#[derive(Debug, Clone, Default)]
+pub struct TlsOptions {
+    pub validate_certificates: bool,
+    pub client_certificates: Vec<ClientCertificate>,
+    pub ca_certificates: Vec<CaCertificate>,
+    pub observer: Option<Arc<dyn TlsObserver>>,
+}
+
+impl From<&Workspace> for TlsOptions {
+    fn from(workspace: &Workspace) -> Self {
+        TlsOptions {
+            validate_certificates: workspace.setting_validate_certificates,
+            client_certificates: workspace.setting_client_certificates.clone(),
+            ca_certificates: workspace.setting_ca_certificates.clone(),
+            observer: None,
+        }
+    }
+}
+
+/// Told about the TLS handshakes made with a config, so they can be shown on responses
+pub trait TlsObserver: Debug + Send + Sync {
+    /// Called right before the ClientHello is sent, so right after the TCP connection is up
+    fn handshake_started(&self);
+    fn protocol_version(&self, version: ProtocolVersion);
+    /// Only known for TLS 1.3, once the server sends a session ticket
+    fn cipher_suite(&self, suite: CipherSuite);
+    fn server_certificates(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+    );
+}
+
+/// Build the TLS config for connecting to `host`, using the client certificate and extra CAs
+/// configured for it. `port` should be the effective port, so use [`default_port`] when the URL
+/// doesn't have one.
+pub fn get_config(
+    options: &TlsOptions,
+    host: &str,
+    port: Option<u16>,
+    with_alpn: bool,
+) -> Result<ClientConfig> {
     let arc_crypto_provider = Arc::new(ring::default_provider());
-    let config_builder = ClientConfig::builder_with_provider(arc_crypto_provider)
-        .with_safe_default_protocol_versions()
-        .unwrap();
-    let mut client = if validate_certificates {
+    let config_builder = ClientConfig::builder_with_provider(arc_crypto_provider.clone())
+        .with_safe_default_protocol_versions()?;
+
+    let mut extra_roots = Vec::new();
+    for ca in
+        options.ca_certificates.iter().filter(|c| c.enabled && host_matches(&c.host, host, port))
+    {
+        extra_roots.extend(read_certificates(&ca.file)?);
+    }
+
+    let mut verifier: Arc<dyn ServerCertVerifier> = if !options.validate_certificates {
+        Arc::new(NoVerifier)
+    } else if extra_roots.is_empty() {
         // Use platform-native verifier to validate certificates
-        config_builder.with_platform_verifier().unwrap().with_no_client_auth()
+        Arc::new(Verifier::new(arc_crypto_provider)?)
     } else {
-        config_builder
-            .dangerous()
-            .with_custom_certificate_verifier(Arc::new(NoVerifier))
-            .with_no_client_auth()
+        Arc::new(Verifier::new_with_extra_roots(extra_roots, arc_crypto_provider)?)
+    };
+    if let Some(observer) = &options.observer {
+        verifier = Arc::new(ObservingVerifier {
+            inner: verifier,
+            observer: observer.clone(),
+        });
+    }
+    let config_builder = config_builder.dangerous().with_custom_certificate_verifier(verifier);
+
+    let client_certificate =
+        options.client_certificates.iter().find(|c| c.enabled && host_matches(&c.host, host, port));
+    let mut client = match client_certificate {
+        None => config_builder.with_no_client_auth(),
+        Some(c) => {
+            let (cert_chain, key) = read_client_certificate(c)?;
+            config_builder.with_client_auth_cert(cert_chain, key)?
+        }
     };
 
     if with_alpn {
         client.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
     }
 
-    client
+    if let Some(observer) = &options.observer {
+        client.resumption = Resumption::store(Arc::new(ObservingSessionStore {
+            inner: ClientSessionMemoryCache::new(256),
+            observer: observer.clone(),
+        }));
+    }
+
+    Ok(client)
+}
+
+/// The port used for a URL scheme when none is given
+pub fn default_port(scheme: &str) -> Option<u16> {
+    match scheme {
+        "https" | "wss" | "grpcs" => Some(443),
+        "http" | "ws" | "grpc" => Some(80),
+        _ => None,
+    }
+}
+
+/// Whether a certificate's host pattern applies to a host. `*` matches anything, and a
+/// pattern with a port only matches that port.
+pub(crate) fn host_matches(pattern: &str, host: &str, port: Option<u16>) -> bool {
+    let pattern = pattern.trim().to_lowercase();
+    if pattern.is_empty() {
+        return false;
+    }
+
+    // Split off the port, taking care not to split a bare IPv6 address
+    let (pattern_host, pattern_port) = match pattern.rsplit_once(':') {
+        Some((h, p)) if !h.contains(':') || h.ends_with(']') => match p.parse::<u16>() {
+            Ok(p) => (h, Some(p)),
+            Err(_) => (pattern.as_str(), None),
+        },
+        _ => (pattern.as_str(), None),
+    };
+
+    if pattern_port.is_some() && pattern_port != port {
+        return false;
+    }
+
+    let re = pattern_host.split('*').map(regex::escape).collect::<Vec<_>>().join(".*");
+    match Regex::new(&format!("^{re}$")) {
+        Ok(re) => re.is_match(&host.to_lowercase()),
+        Err(_) => false,
+    }
+}
+
+fn read_file(path: &str) -> Result<Vec<u8>> {
+    fs::read(path).map_err(|e| CertificateError(format!("Failed to read {path}: {e}")))
+}
+
+/// Read every certificate from a PEM file, or a single certificate from a DER file
+fn read_certificates(path: &str) -> Result<Vec<CertificateDer<'static>>> {
+    let bytes = read_file(path)?;
+    let certs = CertificateDer::pem_slice_iter(&bytes)
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|e| CertificateError(format!("Invalid certificate in {path}: {e}")))?;
+
+    if certs.is_empty() {
+        return Ok(vec![CertificateDer::from(bytes)]);
+    }
+
+    Ok(certs)
+}
+
+fn read_client_certificate(
+    cert: &ClientCertificate,
+) -> Result<(Vec<CertificateDer<'static>>, PrivateKeyDer<'static>)> {
+    if let Some(pfx_file) = cert.pfx_file.as_deref().filter(|f| !f.is_empty()) {
+        let bytes = read_file(pfx_file)?;
+        let passphrase = cert.passphrase.as_deref().unwrap_or_default();
+        let key_store = KeyStore::from_pkcs12(&bytes, passphrase)
+            .map_err(|e| CertificateError(format!("Failed to open {pfx_file}: {e}")))?;
+        let (_, chain) = key_store
+            .private_key_chain()
+            .ok_or_else(|| CertificateError(format!("No private key found in {pfx_file}")))?;
+        let cert_chain =
+            chain.chain().iter().map(|c| CertificateDer::from(c.as_der().to_vec())).collect();
+        let key = PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(chain.key().to_vec()));
+        return Ok((cert_chain, key));
+    }
+
+    let (Some(crt_file), Some(key_file)) = (
+        cert.crt_file.as_deref().filter(|f| !f.is_empty()),
+        cert.key_file.as_deref().filter(|f| !f.is_empty()),
+    ) else {
+        return Err(CertificateError(format!(
+            "Client certificate for {} needs a PKCS#12 file or both a certificate and key file",
+            cert.host
+        )));
+    };
+
+    let cert_chain = read_certificates(crt_file)?;
+    let key = PrivateKeyDer::from_pem_slice(&read_file(key_file)?)
+        .map_err(|e| CertificateError(format!("Invalid private key in {key_file}: {e}")))?;
+
+    Ok((cert_chain, key))
+}
+
+/// Passes the server's certificates and the protocol version on to a [`TlsObserver`]
+#[derive(Debug)]
+struct ObservingVerifier {
+    inner: Arc<dyn ServerCertVerifier>,
+    observer: Arc<dyn TlsObserver>,
+}
+
+impl ServerCertVerifier for ObservingVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer,
+        intermediates: &[CertificateDer],
+        server_name: &ServerName,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> std::result::Result<ServerCertVerified, rustls::Error> {
+        self.observer.server_certificates(end_entity, intermediates);
+        self.inner.verify_server_cert(end_entity, intermediates, server_name, ocsp_response, now)
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        self.observer.protocol_version(ProtocolVersion::TLSv1_2);
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        self.observer.protocol_version(ProtocolVersion::TLSv1_3);
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+
+    fn requires_raw_public_keys(&self) -> bool {
+        self.inner.requires_raw_public_keys()
+    }
+
+    fn root_hint_subjects(&self) -> Option<&[DistinguishedName]> {
+        self.inner.root_hint_subjects()
+    }
+}
+
+/// rustls doesn't expose handshake progress, but it asks the session store for a key exchange
+/// hint when it builds the ClientHello and hands it the ticket (and its cipher suite) after a
+/// TLS 1.3 handshake, which is close enough to hook into.
+#[derive(Debug)]
+struct ObservingSessionStore {
+    inner: ClientSessionMemoryCache,
+    observer: Arc<dyn TlsObserver>,
+}
+
+impl ClientSessionStore for ObservingSessionStore {
+    fn set_kx_hint(&self, server_name: ServerName<'static>, group: NamedGroup) {
+        self.inner.set_kx_hint(server_name, group)
+    }
+
+    fn kx_hint(&self, server_name: &ServerName<'_>) -> Option<NamedGroup> {
+        self.observer.handshake_started();
+        self.inner.kx_hint(server_name)
+    }
+
+    fn set_tls12_session(&self, server_name: ServerName<'static>, value: Tls12ClientSessionValue) {
+        self.inner.set_tls12_session(server_name, value)
+    }
+
+    fn tls12_session(&self, server_name: &ServerName<'_>) -> Option<Tls12ClientSessionValue> {
+        self.inner.tls12_session(server_name)
+    }
+
+    fn remove_tls12_session(&self, server_name: &ServerName<'static>) {
+        self.inner.remove_tls12_session(server_name)
+    }
+
+    fn insert_tls13_ticket(
+        &self,
+        server_name: ServerName<'static>,
+        value: Tls13ClientSessionValue,
+    ) {
+        self.observer.cipher_suite(value.suite().common.suite);
+        self.inner.insert_tls13_ticket(server_name, value)
+    }
+
+    fn take_tls13_ticket(
+        &self,
+        server_name: &ServerName<'static>,
+    ) -> Option<Tls13ClientSessionValue> {
+        self.inner.take_tls13_ticket(server_name)
+    }
 }
 
 // Copied from reqwest: https://github.com/seanmonstar/reqwest/blob/595c80b1fbcdab73ac2ae93e4edc3406f453df25/src/tls.rs#L608
@@ -39,7 +316,7 @@ impl ServerCertVerifier for NoVerifier {
         _server_name: &ServerName,
         _ocsp_response: &[u8],
         _now: UnixTime,
-    ) -> Result<ServerCertVerified, rustls::Error> {
+    ) -> std::result::Result<ServerCertVerified, rustls::Error> {
         Ok(ServerCertVerified::assertion())
     }
 
@@ -48,7 +325,7 @@ impl ServerCertVerifier for NoVerifier {
         _message: &[u8],
         _cert: &CertificateDer,
         _dss: &DigitallySignedStruct,
-    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
         Ok(HandshakeSignatureValid::assertion())
     }
 
@@ -57,7 +334,7 @@ impl ServerCertVerifier for NoVerifier {
         _message: &[u8],
         _cert: &CertificateDer,
         _dss: &DigitallySignedStruct,
-    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
         Ok(HandshakeSignatureValid::assertion())
     }
 
@@ -79,3 +356,51 @@ impl ServerCertVerifier for NoVerifier {
         ]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn host_matches_exact() {
+        assert!(host_matches("api.example.com", "api.example.com", Some(443)));
+        assert!(host_matches(" API.example.com ", "api.EXAMPLE.com", Some(443)));
+        assert!(!host_matches("api.example.com", "example.com", Some(443)));
+        assert!(!host_matches("", "example.com", Some(443)));
+    }
+
+    #[test]
+    fn host_matches_wildcard() {
+        assert!(host_matches("*", "anything.local", None));
+        assert!(host_matches("*.example.com", "a.b.example.com", Some(443)));
+        assert!(!host_matches("*.example.com", "example.com", Some(443)));
+        assert!(!host_matches("*.example.com", "example.com.evil.io", Some(443)));
+    }
+
+    #[test]
+    fn host_matches_port() {
+        assert!(host_matches("localhost:8443", "localhost", Some(8443)));
+        assert!(!host_matches("localhost:8443", "localhost", Some(443)));
+        assert!(!host_matches("localhost:8443", "localhost", None));
+        assert!(host_matches("[::1]:8443", "[::1]", Some(8443)));
+        assert!(host_matches("[::1]", "[::1]", Some(8443)));
+    }
+
+    #[test]
+    fn missing_client_certificate_files() {
+        let options = TlsOptions {
+            validate_certificates: false,
+            client_certificates: vec![ClientCertificate {
+                enabled: true,
+                host: "*.internal".to_string(),
+                crt_file: Some("/does/not/exist.crt".to_string()),
+                key_file: None,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        assert!(get_config(&options, "api.example.com", Some(443), false).is_ok());
+        assert!(get_config(&options, "api.internal", Some(443), false).is_err());
+    }
+}
@@ -1,7 +1,42 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { JsonValue } from "./serde_json/JsonValue";
 
-export type AnyModel = CookieJar | Environment | Folder | GraphQlIntrospection | GrpcConnection | GrpcEvent | GrpcRequest | HttpRequest | HttpResponse | KeyValue | Plugin | Settings | SyncState | WebsocketConnection | WebsocketEvent | WebsocketRequest | Workflow | WorkflowStep | WorkflowExecution | WorkflowStepExecution | WorkflowNode | WorkflowEdge | WorkflowViewport | WorkflowNodeExecution | Workspace | WorkspaceMeta;
+export type AnyModel = CookieJar | Environment | Folder | GraphQlIntrospection | GrpcConnection | GrpcEvent | GrpcRequest | HttpRequest | HttpResponse | KeyValue | Plugin | Settings | SyncState | WebsocketConnection | WebsocketEvent | WebsocketRequest | Workflow | WorkflowExecution | WorkflowNode | WorkflowEdge | WorkflowViewport | WorkflowNodeExecution | WorkflowLoadTest | Workspace | WorkspaceMeta;
+
+/**
+ * Extra certificate authorities trusted on top of the platform ones
+ */
+export type CaCertificate = { enabled?: boolean, 
+/**
+ * Host to trust the certificates for, with the same syntax as `ClientCertificate::host`
+ */
+host: string, 
+/**
+ * PEM (or DER) file with one or more CA certificates
+ */
+file: string, };
+
+/**
+ * A certificate (and key) presented to servers that ask for mutual TLS
+ */
+export type ClientCertificate = { enabled?: boolean, 
+/**
+ * Host to use the certificate for. Supports `*` wildcards and an optional port, like
+ * `*.internal.example.com` or `localhost:8443`
+ */
+host: string, 
+/**
+ * PEM file with the certificate chain, used together with `key_file`
+ */
+crtFile: string | null, 
+/**
+ * PEM file with the private key for `crt_file`
+ */
+keyFile: string | null, 
+/**
+ * PKCS#12 bundle with both the certificate chain and key, instead of `crt_file`/`key_file`
+ */
+pfxFile: string | null, passphrase: string | null, };
 
 export type Cookie = { raw_cookie: string, domain: CookieDomain, expires: CookieExpires, path: [string, boolean], };
 
@@ -11,6 +46,25 @@ export type CookieExpires = { "AtUtc": string } | "SessionEnd";
 
 export type CookieJar = { model: "cookie_jar", id: string, createdAt: string, updatedAt: string, workspaceId: string, cookies: Array<Cookie>, name: string, };
 
+/**
+ * Resolves a host to a fixed address, like curl's `--resolve`. The URL is left alone, so the
+ * request keeps its Host header and TLS server name.
+ */
+export type DnsOverride = { enabled?: boolean, 
+/**
+ * Host to override. Supports `*` wildcards, like `*.staging.example.com`
+ */
+hostname: string, 
+/**
+ * IPv4 or IPv6 address to connect to instead
+ */
+ip: string, };
+
+/**
+ * Where hosts without an override are looked up
+ */
+export type DnsResolverSetting = { "type": "system" } | { "type": "nameserver", address: string, } | { "type": "https", url: string, };
+
 export type EdgeType = "sequential" | "conditional" | "parallel" | "loop";
 
 export type EditorKeymap = "default" | "vim" | "vscode" | "emacs";
@@ -33,18 +87,63 @@ export type GrpcEvent = { model: "grpc_event", id: string, createdAt: string, up
 
 export type GrpcEventType = "info" | "error" | "client_message" | "server_message" | "connection_start" | "connection_end";
 
-export type GrpcRequest = { model: "grpc_request", id: string, createdAt: string, updatedAt: string, workspaceId: string, folderId: string | null, authenticationType: string | null, authentication: Record<string, any>, description: string, message: string, metadata: Array<HttpRequestHeader>, method: string | null, name: string, service: string | null, sortPriority: number, url: string, };
+export type GrpcRequest = { model: "grpc_request", id: string, createdAt: string, updatedAt: string, workspaceId: string, folderId: string | null, authenticationType: string | null, authentication: Record<string, any>, description: string, message: string, metadata: Array<HttpRequestHeader>, method: string | null, name: string, service: string | null, sortPriority: number, url: string, 
+/**
+ * Set on the hidden request an inline workflow gRPC node sends through
+ */
+workflowNodeId: string | null, };
 
-export type HttpRequest = { model: "http_request", id: string, createdAt: string, updatedAt: string, workspaceId: string, folderId: string | null, authentication: Record<string, any>, authenticationType: string | null, body: Record<string, any>, bodyType: string | null, description: string, headers: Array<HttpRequestHeader>, method: string, name: string, sortPriority: number, url: string, urlParameters: Array<HttpUrlParameter>, };
+export type HttpRequest = { model: "http_request", id: string, createdAt: string, updatedAt: string, workspaceId: string, folderId: string | null, authentication: Record<string, any>, authenticationType: string | null, body: Record<string, any>, bodyType: string | null, description: string, headers: Array<HttpRequestHeader>, method: string, name: string, sortPriority: number, url: string, urlParameters: Array<HttpUrlParameter>, 
+/**
+ * Set on the hidden request an inline workflow HTTP node sends through
+ */
+workflowNodeId: string | null, };
 
 export type HttpRequestHeader = { enabled?: boolean, name: string, value: string, id?: string, };
 
-export type HttpResponse = { model: "http_response", id: string, createdAt: string, updatedAt: string, workspaceId: string, requestId: string, bodyPath: string | null, contentLength: number | null, elapsed: number, elapsedHeaders: number, error: string | null, headers: Array<HttpResponseHeader>, remoteAddr: string | null, status: number, statusReason: string | null, state: HttpResponseState, url: string, version: string | null, };
+export type HttpResponse = { model: "http_response", id: string, createdAt: string, updatedAt: string, workspaceId: string, requestId: string, bodyPath: string | null, contentLength: number | null, elapsed: number, elapsedHeaders: number, error: string | null, headers: Array<HttpResponseHeader>, redirects: Array<HttpResponseRedirect>, remoteAddr: string | null, request: HttpResponseRequest | null, status: number, statusReason: string | null, state: HttpResponseState, timings: HttpResponseTimings, tls: HttpResponseTls | null, url: string, version: string | null, };
 
 export type HttpResponseHeader = { name: string, value: string, };
 
+/**
+ * A redirect that was followed on the way to the final response
+ */
+export type HttpResponseRedirect = { status: number, method: string, url: string, location: string, headers: Array<HttpResponseHeader>, elapsed: number, };
+
+/**
+ * The request as it was handed to the HTTP client, after rendering and authentication
+ */
+export type HttpResponseRequest = { method: string, url: string, headers: Array<HttpResponseHeader>, 
+/**
+ * The body, cut off after the first few megabytes
+ */
+bodyPath: string | null, 
+/**
+ * Size of the whole body, even when the saved one was cut off
+ */
+contentLength: number | null, };
+
 export type HttpResponseState = "initialized" | "connected" | "closed";
 
+/**
+ * How long each phase of a request took, in milliseconds. The connection phases are zero when
+ * an existing connection was reused.
+ */
+export type HttpResponseTimings = { dns: number, connect: number, tls: number, 
+/**
+ * From the request being sent (or the connection being ready) to the response headers
+ */
+ttfb: number, download: number, };
+
+/**
+ * The TLS connection a response was received over
+ */
+export type HttpResponseTls = { version: string | null, cipher: string | null, 
+/**
+ * PEM encoded certificates sent by the server, starting with its own
+ */
+peerCertificates: Array<string>, };
+
 export type HttpUrlParameter = { enabled?: boolean, name: string, value: string, id?: string, };
 
 export type KeyValue = { model: "key_value", id: string, createdAt: string, updatedAt: string, key: string, namespace: string, value: string, };
@@ -101,28 +200,76 @@ export type WebsocketEventType = "binary" | "close" | "frame" | "open" | "ping"
 
 export type WebsocketMessageType = "text" | "binary";
 
-export type WebsocketRequest = { model: "websocket_request", id: string, createdAt: string, updatedAt: string, workspaceId: string, folderId: string | null, authentication: Record<string, any>, authenticationType: string | null, description: string, headers: Array<HttpRequestHeader>, message: string, name: string, sortPriority: number, url: string, urlParameters: Array<HttpUrlParameter>, };
+export type WebsocketRequest = { model: "websocket_request", id: string, createdAt: string, updatedAt: string, workspaceId: string, folderId: string | null, authentication: Record<string, any>, authenticationType: string | null, description: string, headers: Array<HttpRequestHeader>, message: string, name: string, sortPriority: number, url: string, urlParameters: Array<HttpUrlParameter>, 
+/**
+ * Set on the hidden request an inline workflow WebSocket node connects through
+ */
+workflowNodeId: string | null, };
 
-export type Workflow = { model: "workflow", id: string, createdAt: string, updatedAt: string, deletedAt: string | null, workspaceId: string, name: string, description: string | null, environmentId: string | null, sortPriority: number, };
+export type Workflow = { model: "workflow", id: string, createdAt: string, updatedAt: string, deletedAt: string | null, workspaceId: string, name: string, description: string | null, environmentId: string | null, sortPriority: number, 
+/**
+ * CSV or JSON file to run the workflow once per row of, with the row's fields as variables
+ */
+datasetPath: string | null, };
 
-export type WorkflowEdge = { model: "workflow_edge", id: string, createdAt: string, updatedAt: string, deletedAt: string | null, workflowId: string, sourceNodeId: string, targetNodeId: string, sourceAnchor: string, targetAnchor: string, edgeType: EdgeType, };
+export type WorkflowEdge = { model: "workflow_edge", id: string, createdAt: string, updatedAt: string, deletedAt: string | null, workspaceId: string, workflowId: string, sourceNodeId: string, targetNodeId: string, sourceAnchor: string, targetAnchor: string, edgeType: EdgeType, };
 
-export type WorkflowExecution = { model: "workflow_execution", id: string, createdAt: string, updatedAt: string, deletedAt: string | null, workflowId: string, workspaceId: string, environmentId: string | null, elapsed: number | null, state: WorkflowExecutionState, error: string | null, };
+export type WorkflowExecution = { model: "workflow_execution", id: string, createdAt: string, updatedAt: string, deletedAt: string | null, workflowId: string, workspaceId: string, environmentId: string | null, elapsed: number | null, state: WorkflowExecutionState, error: string | null, assertionsPassed: number, assertionsFailed: number, 
+/**
+ * Earlier execution this one was resumed from, reusing its completed node results
+ */
+resumedFromId: string | null, 
+/**
+ * Execution grouping the runs of a dataset, one per row
+ */
+parentExecutionId: string | null, 
+/**
+ * Index of the dataset row this execution ran with
+ */
+datasetRow: number | null, 
+/**
+ * Load test this execution ran as one iteration of
+ */
+loadTestId: string | null, 
+/**
+ * Execution whose sub-workflow node started this one
+ */
+callerExecutionId: string | null, };
 
 export type WorkflowExecutionState = "initialized" | "running" | "completed" | "failed" | "cancelled";
 
-export type WorkflowNode = { model: "workflow_node", id: string, createdAt: string, updatedAt: string, deletedAt: string | null, workflowId: string, nodeType: NodeType, nodeSubtype: string, positionX: number, positionY: number, width: number, height: number, name: string, description: string | null, config: JsonValue, enabled: boolean, legacyStepId: string | null, };
-
-export type WorkflowNodeExecution = { model: "workflow_node_execution", id: string, createdAt: string, updatedAt: string, deletedAt: string | null, workflowExecutionId: string, workflowNodeId: string, elapsed: number | null, state: NodeExecutionState, error: string | null, result: JsonValue | null, loopIteration: number | null, };
-
-export type WorkflowStep = { model: "workflow_step", id: string, createdAt: string, updatedAt: string, deletedAt: string | null, workflowId: string, requestId: string, requestModel: string, name: string, enabled: boolean, sortPriority: number, };
+export type WorkflowLoadTest = { model: "workflow_load_test", id: string, createdAt: string, updatedAt: string, deletedAt: string | null, workflowId: string, workspaceId: string, environmentId: string | null, state: WorkflowExecutionState, 
+/**
+ * Total number of workflow runs
+ */
+iterations: number, 
+/**
+ * Number of runs in flight at once
+ */
+concurrency: number, 
+/**
+ * Time over which the concurrent workers are started
+ */
+rampUpMs: number, completed: number, failed: number, elapsed: number | null, error: string | null, 
+/**
+ * Per-node latency percentiles, error rates and throughput
+ */
+summary: JsonValue | null, };
 
-export type WorkflowStepExecution = { model: "workflow_step_execution", id: string, createdAt: string, updatedAt: string, deletedAt: string | null, workflowExecutionId: string, workflowStepId: string, requestId: string, responseId: string | null, responseModel: string | null, elapsed: number | null, state: WorkflowStepExecutionState, error: string | null, };
+export type WorkflowNode = { model: "workflow_node", id: string, createdAt: string, updatedAt: string, deletedAt: string | null, workspaceId: string, workflowId: string, nodeType: NodeType, nodeSubtype: string, positionX: number, positionY: number, width: number, height: number, name: string, description: string | null, config: JsonValue, enabled: boolean, 
+/**
+ * Step of a legacy linear workflow that this node was converted from
+ */
+legacyStepId: string | null, };
 
-export type WorkflowStepExecutionState = "pending" | "running" | "completed" | "failed" | "skipped";
+export type WorkflowNodeExecution = { model: "workflow_node_execution", id: string, createdAt: string, updatedAt: string, deletedAt: string | null, workflowExecutionId: string, workflowNodeId: string, elapsed: number | null, state: NodeExecutionState, error: string | null, result: JsonValue | null, loopIteration: number | null, responseId: string | null, responseModel: string | null, 
+/**
+ * 1-based attempt number, incremented for every retry of the node
+ */
+attempt: number, };
 
 export type WorkflowViewport = { model: "workflow_viewport", id: string, createdAt: string, updatedAt: string, workflowId: string, panX: number, panY: number, zoom: number, };
 
-export type Workspace = { model: "workspace", id: string, createdAt: string, updatedAt: string, authentication: Record<string, any>, authenticationType: string | null, description: string, headers: Array<HttpRequestHeader>, name: string, encryptionKeyChallenge: string | null, settingValidateCertificates: boolean, settingFollowRedirects: boolean, settingRequestTimeout: number, };
+export type Workspace = { model: "workspace", id: string, createdAt: string, updatedAt: string, authentication: Record<string, any>, authenticationType: string | null, description: string, headers: Array<HttpRequestHeader>, name: string, encryptionKeyChallenge: string | null, settingValidateCertificates: boolean, settingFollowRedirects: boolean, settingMaxRedirects: number, settingRequestTimeout: number, settingClientCertificates: Array<ClientCertificate>, settingCaCertificates: Array<CaCertificate>, settingDnsOverrides: Array<DnsOverride>, settingDnsResolver: DnsResolverSetting, };
 
 export type WorkspaceMeta = { model: "workspace_meta", id: string, workspaceId: string, createdAt: string, updatedAt: string, encryptionKey: EncryptedKey | null, settingSyncDir: string | null, };
@@ -26,9 +26,7 @@ export const websocketConnectionsAtom = createOrderedModelAtom(
   'desc',
 );
 export const workflowsAtom = createOrderedModelAtom('workflow', 'sortPriority', 'asc');
-export const workflowStepsAtom = createOrderedModelAtom('workflow_step', 'sortPriority', 'asc');
 export const workflowExecutionsAtom = createOrderedModelAtom('workflow_execution', 'createdAt', 'desc');
-export const workflowStepExecutionsAtom = createOrderedModelAtom('workflow_step_execution', 'createdAt', 'asc');
 export const workspaceMetasAtom = createModelAtom('workspace_meta');
 export const workspacesAtom = createOrderedModelAtom('workspace', 'name', 'asc');
 
@@ -37,6 +35,7 @@ export const canvasNodesAtom = createModelAtom('workflow_node');
 export const canvasEdgesAtom = createModelAtom('workflow_edge');
 export const canvasViewportsAtom = createModelAtom('workflow_viewport');
 export const canvasNodeExecutionsAtom = createOrderedModelAtom('workflow_node_execution', 'createdAt', 'asc');
+export const workflowLoadTestsAtom = createOrderedModelAtom('workflow_load_test', 'createdAt', 'desc');
 
 // UI state atoms
 export const selectedNodeIdAtom = atom<string | null>(null);
@@ -204,6 +204,11 @@ export function replaceModelsInStore<
 }
 
 function shouldIgnoreModel({ model, updateSource }: ModelPayload) {
+  // Requests of inline workflow nodes are hidden, they only exist for their responses
+  if ('workflowNodeId' in model && model.workflowNodeId != null) {
+    return true;
+  }
+
   // Never ignore updates from non-user sources
   if (updateSource.type !== 'window') {
     return false;
@@ -20,12 +20,11 @@ export function newStoreData(): ModelStoreData {
     websocket_request: {},
     workflow: {},
     workflow_execution: {},
-    workflow_step: {},
-    workflow_step_execution: {},
     workflow_node: {},
     workflow_edge: {},
     workflow_viewport: {},
     workflow_node_execution: {},
+    workflow_load_test: {},
     workspace: {},
     workspace_meta: {},
   };
@@ -0,0 +1,3 @@
+-- Link canvas node executions to the response they produced
+ALTER TABLE workflow_node_executions ADD COLUMN response_id TEXT;     -- soft reference to http_responses, grpc_connections, etc.
+ALTER TABLE workflow_node_executions ADD COLUMN response_model TEXT;  -- 'http_response' | 'grpc_connection' | 'websocket_connection'
@@ -0,0 +1,3 @@
+-- Pass/fail summary of assertion nodes for each workflow run
+ALTER TABLE workflow_executions ADD COLUMN assertions_passed INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE workflow_executions ADD COLUMN assertions_failed INTEGER NOT NULL DEFAULT 0;
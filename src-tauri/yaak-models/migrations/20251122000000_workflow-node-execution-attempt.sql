@@ -0,0 +1,2 @@
+-- Each retry of a canvas node is stored as its own execution row
+ALTER TABLE workflow_node_executions ADD COLUMN attempt INTEGER NOT NULL DEFAULT 1;
@@ -0,0 +1,15 @@
+-- Nodes and edges carry their workspace so they can be synced to the filesystem on their own
+ALTER TABLE workflow_nodes ADD COLUMN workspace_id TEXT NOT NULL DEFAULT '';
+ALTER TABLE workflow_edges ADD COLUMN workspace_id TEXT NOT NULL DEFAULT '';
+
+UPDATE workflow_nodes
+SET workspace_id = COALESCE(
+    (SELECT workflows.workspace_id FROM workflows WHERE workflows.id = workflow_nodes.workflow_id), ''
+);
+UPDATE workflow_edges
+SET workspace_id = COALESCE(
+    (SELECT workflows.workspace_id FROM workflows WHERE workflows.id = workflow_edges.workflow_id), ''
+);
+
+CREATE INDEX idx_workflow_nodes_workspace_id ON workflow_nodes(workspace_id);
+CREATE INDEX idx_workflow_edges_workspace_id ON workflow_edges(workspace_id);
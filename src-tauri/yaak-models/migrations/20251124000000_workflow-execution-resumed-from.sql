@@ -0,0 +1,2 @@
+-- Runs resumed from a node of an earlier run point back to that run
+ALTER TABLE workflow_executions ADD COLUMN resumed_from_id TEXT;
@@ -0,0 +1,8 @@
+-- Workflows can run once per row of a CSV or JSON dataset file
+ALTER TABLE workflows ADD COLUMN dataset_path TEXT;
+
+-- Each row runs as its own execution, grouped under a parent execution
+ALTER TABLE workflow_executions ADD COLUMN parent_execution_id TEXT;
+ALTER TABLE workflow_executions ADD COLUMN dataset_row INTEGER;
+
+CREATE INDEX idx_workflow_executions_parent_execution_id ON workflow_executions(parent_execution_id);
@@ -0,0 +1,25 @@
+-- Load tests run a workflow many times and keep only an aggregated summary, so they don't
+-- leave an execution row behind for every iteration
+CREATE TABLE workflow_load_tests (
+    id TEXT PRIMARY KEY,
+    model TEXT NOT NULL DEFAULT 'workflow_load_test',
+    created_at INTEGER NOT NULL,
+    updated_at INTEGER NOT NULL,
+    deleted_at INTEGER,
+    workflow_id TEXT NOT NULL,
+    workspace_id TEXT NOT NULL,
+    environment_id TEXT,  -- soft reference
+    state TEXT NOT NULL,  -- 'initialized', 'running', 'completed', 'failed', 'cancelled'
+    iterations INTEGER NOT NULL,
+    concurrency INTEGER NOT NULL,
+    ramp_up_ms INTEGER NOT NULL DEFAULT 0,
+    completed INTEGER NOT NULL DEFAULT 0,
+    failed INTEGER NOT NULL DEFAULT 0,
+    elapsed INTEGER,
+    error TEXT,
+    summary TEXT,  -- JSON latency percentiles, error rates and throughput
+    FOREIGN KEY (workflow_id) REFERENCES workflows(id) ON DELETE CASCADE,
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_workflow_load_tests_workflow_id ON workflow_load_tests(workflow_id);
@@ -0,0 +1,160 @@
+-- Legacy workflows were a linear list of steps, run by an engine of their own. Turn every step
+-- into a canvas node and move the runs over to node executions, so the canvas engine and its
+-- history are the only ones left.
+
+-- Steps become request nodes chained after the start trigger of their workflow, and workflows that
+-- only have steps get a manual trigger to start from. Workflows that already have canvas nodes only
+-- get nodes for the steps that are missing one, chained after their own start trigger so they run.
+CREATE TEMP TABLE legacy_workflows AS
+SELECT DISTINCT s.workflow_id AS id
+FROM workflow_steps s
+WHERE NOT EXISTS (SELECT 1 FROM workflow_nodes n WHERE n.workflow_id = s.workflow_id);
+
+CREATE TEMP TABLE unconverted_steps AS
+SELECT s.id, s.workflow_id
+FROM workflow_steps s
+WHERE NOT EXISTS (SELECT 1 FROM workflow_nodes n WHERE n.legacy_step_id = s.id);
+
+-- Triggers that runs start from, the same way the graph builder finds them
+CREATE TEMP VIEW start_triggers AS
+SELECT t.id, t.workflow_id, t.created_at
+FROM workflow_nodes t
+WHERE t.node_type = 'trigger'
+  AND t.deleted_at IS NULL
+  AND NOT EXISTS (SELECT 1 FROM workflow_edges e WHERE e.target_node_id = t.id AND e.deleted_at IS NULL);
+
+-- 1. Start trigger, for workflows that don't have one yet
+INSERT INTO workflow_nodes (id, model, created_at, updated_at, workspace_id, workflow_id, node_type, node_subtype,
+                            position_x, position_y, width, height, name, config, enabled)
+SELECT (
+           -- This is the best way to generate a random string in SQLite, apparently
+           'wn_' || SUBSTR('abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23457789', (ABS(RANDOM()) % 57) + 1, 1) ||
+           SUBSTR('abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23457789', (ABS(RANDOM()) % 57) + 1, 1) ||
+           SUBSTR('abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23457789', (ABS(RANDOM()) % 57) + 1, 1) ||
+           SUBSTR('abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23457789', (ABS(RANDOM()) % 57) + 1, 1) ||
+           SUBSTR('abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23457789', (ABS(RANDOM()) % 57) + 1, 1) ||
+           SUBSTR('abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23457789', (ABS(RANDOM()) % 57) + 1, 1) ||
+           SUBSTR('abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23457789', (ABS(RANDOM()) % 57) + 1, 1) ||
+           SUBSTR('abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23457789', (ABS(RANDOM()) % 57) + 1, 1) ||
+           SUBSTR('abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23457789', (ABS(RANDOM()) % 57) + 1, 1) ||
+           SUBSTR('abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23457789', (ABS(RANDOM()) % 57) + 1, 1)
+           ),
+       'workflow_node',
+       w.created_at,
+       w.updated_at,
+       w.workspace_id,
+       w.id,
+       'trigger',
+       'manual_trigger',
+       100,
+       CASE WHEN w.id IN (SELECT id FROM legacy_workflows) THEN 200 ELSE 500 END,
+       200,
+       100,
+       'Start',
+       '{}',
+       1
+FROM workflows w
+WHERE w.id IN (SELECT workflow_id FROM unconverted_steps)
+  AND w.id NOT IN (SELECT workflow_id FROM start_triggers);
+
+-- 2. One request node per step, left to right in step order
+INSERT INTO workflow_nodes (id, model, created_at, updated_at, workspace_id, workflow_id, node_type, node_subtype,
+                            position_x, position_y, width, height, name, config, enabled, legacy_step_id)
+SELECT (
+           'wn_' || SUBSTR('abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23457789', (ABS(RANDOM()) % 57) + 1, 1) ||
+           SUBSTR('abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23457789', (ABS(RANDOM()) % 57) + 1, 1) ||
+           SUBSTR('abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23457789', (ABS(RANDOM()) % 57) + 1, 1) ||
+           SUBSTR('abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23457789', (ABS(RANDOM()) % 57) + 1, 1) ||
+           SUBSTR('abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23457789', (ABS(RANDOM()) % 57) + 1, 1) ||
+           SUBSTR('abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23457789', (ABS(RANDOM()) % 57) + 1, 1) ||
+           SUBSTR('abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23457789', (ABS(RANDOM()) % 57) + 1, 1) ||
+           SUBSTR('abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23457789', (ABS(RANDOM()) % 57) + 1, 1) ||
+           SUBSTR('abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23457789', (ABS(RANDOM()) % 57) + 1, 1) ||
+           SUBSTR('abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23457789', (ABS(RANDOM()) % 57) + 1, 1)
+           ),
+       'workflow_node',
+       s.created_at,
+       s.updated_at,
+       w.workspace_id,
+       s.workflow_id,
+       'action',
+       CASE WHEN s.request_model = 'grpc_request' THEN 'grpc_request' ELSE 'http_request' END,
+       100 + 300 * ROW_NUMBER() OVER (PARTITION BY s.workflow_id ORDER BY s.sort_priority, s.created_at, s.id),
+       -- Below the existing canvas, so they don't end up on top of nodes that are already there
+       CASE WHEN s.workflow_id IN (SELECT id FROM legacy_workflows) THEN 200 ELSE 500 END,
+       200,
+       100,
+       s.name,
+       json_object('request_id', s.request_id),
+       s.enabled,
+       s.id
+FROM workflow_steps s
+         JOIN workflows w ON w.id = s.workflow_id
+WHERE s.id IN (SELECT id FROM unconverted_steps);
+
+-- 3. Sequential edges from the start trigger through every new step node
+INSERT INTO workflow_edges (id, model, created_at, updated_at, workspace_id, workflow_id, source_node_id,
+                            target_node_id, source_anchor, target_anchor, edge_type)
+SELECT (
+           'we_' || SUBSTR('abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23457789', (ABS(RANDOM()) % 57) + 1, 1) ||
+           SUBSTR('abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23457789', (ABS(RANDOM()) % 57) + 1, 1) ||
+           SUBSTR('abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23457789', (ABS(RANDOM()) % 57) + 1, 1) ||
+           SUBSTR('abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23457789', (ABS(RANDOM()) % 57) + 1, 1) ||
+           SUBSTR('abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23457789', (ABS(RANDOM()) % 57) + 1, 1) ||
+           SUBSTR('abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23457789', (ABS(RANDOM()) % 57) + 1, 1) ||
+           SUBSTR('abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23457789', (ABS(RANDOM()) % 57) + 1, 1) ||
+           SUBSTR('abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23457789', (ABS(RANDOM()) % 57) + 1, 1) ||
+           SUBSTR('abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23457789', (ABS(RANDOM()) % 57) + 1, 1) ||
+           SUBSTR('abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23457789', (ABS(RANDOM()) % 57) + 1, 1)
+           ),
+       'workflow_edge',
+       n.created_at,
+       n.updated_at,
+       n.workspace_id,
+       n.workflow_id,
+       COALESCE(
+           LAG(n.id) OVER (PARTITION BY n.workflow_id ORDER BY n.position_x),
+           (SELECT t.id FROM start_triggers t WHERE t.workflow_id = n.workflow_id ORDER BY t.created_at, t.id LIMIT 1)
+       ),
+       n.id,
+       'output',
+       'input',
+       'sequential'
+FROM workflow_nodes n
+WHERE n.legacy_step_id IN (SELECT id FROM unconverted_steps);
+
+-- 4. Step executions become executions of the node their step turned into
+INSERT INTO workflow_node_executions (id, model, created_at, updated_at, deleted_at, workflow_execution_id,
+                                      workflow_node_id, elapsed, state, error, response_id, response_model, attempt)
+SELECT (
+           'wne_' || SUBSTR('abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23457789', (ABS(RANDOM()) % 57) + 1, 1) ||
+           SUBSTR('abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23457789', (ABS(RANDOM()) % 57) + 1, 1) ||
+           SUBSTR('abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23457789', (ABS(RANDOM()) % 57) + 1, 1) ||
+           SUBSTR('abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23457789', (ABS(RANDOM()) % 57) + 1, 1) ||
+           SUBSTR('abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23457789', (ABS(RANDOM()) % 57) + 1, 1) ||
+           SUBSTR('abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23457789', (ABS(RANDOM()) % 57) + 1, 1) ||
+           SUBSTR('abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23457789', (ABS(RANDOM()) % 57) + 1, 1) ||
+           SUBSTR('abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23457789', (ABS(RANDOM()) % 57) + 1, 1) ||
+           SUBSTR('abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23457789', (ABS(RANDOM()) % 57) + 1, 1) ||
+           SUBSTR('abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23457789', (ABS(RANDOM()) % 57) + 1, 1)
+           ),
+       'workflow_node_execution',
+       se.created_at,
+       se.updated_at,
+       se.deleted_at,
+       se.workflow_execution_id,
+       n.id,
+       se.elapsed,
+       se.state,
+       se.error,
+       se.response_id,
+       se.response_model,
+       1
+FROM workflow_step_executions se
+         JOIN workflow_nodes n ON n.legacy_step_id = se.workflow_step_id;
+
+DROP VIEW start_triggers;
+DROP TABLE unconverted_steps;
+DROP TABLE legacy_workflows;
+DROP TABLE workflow_step_executions;
+DROP TABLE workflow_steps;
@@ -0,0 +1,6 @@
+-- Redirects are followed by us instead of reqwest, so every hop can be recorded on the response
+ALTER TABLE workspaces
+    ADD COLUMN setting_max_redirects INTEGER DEFAULT 10 NOT NULL;
+
+ALTER TABLE http_responses
+    ADD COLUMN redirects TEXT DEFAULT '[]' NOT NULL;
@@ -0,0 +1,5 @@
+ALTER TABLE http_responses
+    ADD COLUMN timings TEXT DEFAULT '{}' NOT NULL;
+
+ALTER TABLE http_responses
+    ADD COLUMN tls TEXT NULL;
@@ -0,0 +1,5 @@
+ALTER TABLE workspaces
+    ADD COLUMN setting_dns_overrides TEXT DEFAULT '[]' NOT NULL;
+
+ALTER TABLE workspaces
+    ADD COLUMN setting_dns_resolver TEXT DEFAULT '{"type":"system"}' NOT NULL;
@@ -0,0 +1,2 @@
+ALTER TABLE http_responses
+    ADD COLUMN request TEXT NULL;
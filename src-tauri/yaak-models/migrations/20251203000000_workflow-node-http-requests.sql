@@ -0,0 +1,3 @@
+-- Inline HTTP request nodes send through a hidden request owned by the node, so their responses
+-- have a request to reference. It goes away along with the node.
+ALTER TABLE http_requests ADD COLUMN workflow_node_id TEXT REFERENCES workflow_nodes (id) ON DELETE CASCADE;
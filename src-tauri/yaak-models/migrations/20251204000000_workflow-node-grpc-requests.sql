@@ -0,0 +1,3 @@
+-- Like HTTP, inline gRPC request nodes connect through a hidden request owned by the node, so their
+-- connections and events have a request to reference.
+ALTER TABLE grpc_requests ADD COLUMN workflow_node_id TEXT REFERENCES workflow_nodes (id) ON DELETE CASCADE;
@@ -0,0 +1,9 @@
+-- Runs of a load test belong to the load test rather than to a parent execution, which is how
+-- dataset rows are grouped. Only a failed sample run is kept, and it goes away with the load test.
+ALTER TABLE workflow_executions ADD COLUMN load_test_id TEXT REFERENCES workflow_load_tests (id) ON DELETE CASCADE;
+
+UPDATE workflow_executions
+SET load_test_id = parent_execution_id, parent_execution_id = NULL
+WHERE parent_execution_id IN (SELECT id FROM workflow_load_tests);
+
+CREATE INDEX idx_workflow_executions_load_test_id ON workflow_executions(load_test_id);
@@ -0,0 +1,5 @@
+-- Runs started by a sub-workflow node link back to the execution that ran the node, so they can be
+-- found from either side. They are part of that run, and go away along with it.
+ALTER TABLE workflow_executions ADD COLUMN caller_execution_id TEXT REFERENCES workflow_executions (id) ON DELETE CASCADE;
+
+CREATE INDEX idx_workflow_executions_caller_execution_id ON workflow_executions(caller_execution_id);
@@ -21,7 +21,6 @@ pub(crate) fn upsert<R: Runtime>(window: WebviewWindow<R>, model: AnyModel) -> R
         AnyModel::Settings(m) => db.upsert_settings(&m, source)?.id,
         AnyModel::WebsocketRequest(m) => db.upsert_websocket_request(&m, source)?.id,
         AnyModel::Workflow(m) => db.upsert(&m, source)?.id,
-        AnyModel::WorkflowStep(m) => db.upsert(&m, source)?.id,
         AnyModel::Workspace(m) => db.upsert_workspace(&m, source)?.id,
         AnyModel::WorkspaceMeta(m) => db.upsert_workspace_meta(&m, source)?.id,
         a => return Err(GenericError(format!("Cannot upsert AnyModel {a:?})"))),
@@ -47,7 +46,7 @@ pub(crate) fn delete<R: Runtime>(window: WebviewWindow<R>, model: AnyModel) -> R
             AnyModel::WebsocketConnection(m) => tx.delete_websocket_connection(&m, source)?.id,
             AnyModel::WebsocketRequest(m) => tx.delete_websocket_request(&m, source)?.id,
             AnyModel::Workflow(m) => tx.delete_workflow(&m, source)?.id,
-            AnyModel::WorkflowStep(m) => tx.delete_workflow_step(&m, source)?.id,
+            AnyModel::WorkflowLoadTest(m) => tx.delete_workflow_load_test(&m, source)?.id,
             AnyModel::Workspace(m) => tx.delete_workspace(&m, source)?.id,
             a => return Err(GenericError(format!("Cannot delete AnyModel {a:?})"))),
         };
@@ -146,6 +145,7 @@ pub(crate) fn workspace_models<R: Runtime>(
         l.append(&mut db.list_workflow_nodes_by_workspace(wid)?.into_iter().map(Into::into).collect());
         l.append(&mut db.list_workflow_edges_by_workspace(wid)?.into_iter().map(Into::into).collect());
         l.append(&mut db.list_workflow_viewports_by_workspace(wid)?.into_iter().map(Into::into).collect());
+        l.append(&mut db.list_workflow_load_tests_by_workspace(wid)?.into_iter().map(Into::into).collect());
     }
 
     let j = serde_json::to_string(&l)?;
@@ -6,6 +6,7 @@ use log::error;
 use r2d2::Pool;
 use r2d2_sqlite::SqliteConnectionManager;
 use std::fs::create_dir_all;
+use std::path::PathBuf;
 use std::sync::mpsc;
 use std::time::Duration;
 use tauri::async_runtime::Mutex;
@@ -34,6 +35,12 @@ impl SqliteConnection {
 }
 
 pub fn init<R: Runtime>() -> TauriPlugin<R> {
+    init_with_db_path(None)
+}
+
+/// Same as [`init`], but with an explicit database file instead of the one in the app data dir.
+/// Used by the headless workflow runner, which has no UI to show migration errors in.
+pub fn init_with_db_path<R: Runtime>(db_file_path: Option<PathBuf>) -> TauriPlugin<R> {
     tauri::plugin::Builder::new("yaak-models")
         .invoke_handler(generate_handler![
             delete,
@@ -46,11 +53,16 @@ pub fn init<R: Runtime>() -> TauriPlugin<R> {
             websocket_events,
             workspace_models,
         ])
-        .setup(|app_handle, _api| {
-            let app_path = app_handle.path().app_data_dir().unwrap();
-            create_dir_all(app_path.clone()).expect("Problem creating App directory!");
-
-            let db_file_path = app_path.join("db.sqlite");
+        .setup(move |app_handle, _api| {
+            let is_headless = db_file_path.is_some();
+            let db_file_path = match db_file_path {
+                Some(p) => p,
+                None => {
+                    let app_path = app_handle.path().app_data_dir().unwrap();
+                    create_dir_all(app_path.clone()).expect("Problem creating App directory!");
+                    app_path.join("db.sqlite")
+                }
+            };
 
             let manager = SqliteConnectionManager::file(db_file_path);
             let pool = Pool::builder()
@@ -61,6 +73,9 @@ pub fn init<R: Runtime>() -> TauriPlugin<R> {
 
             if let Err(e) = migrate_db(&pool) {
                 error!("Failed to run database migration {e:?}");
+                if is_headless {
+                    return Err(Box::from(e.to_string()));
+                }
                 app_handle
                     .dialog()
                     .message(e.to_string())
@@ -316,4 +331,177 @@ mod migration_tests {
 
         println!("✅ Migration works correctly with existing data!");
     }
+
+    #[test]
+    fn test_legacy_workflow_steps_migration() {
+        let manager = SqliteConnectionManager::memory();
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(manager)
+            .expect("Failed to create connection pool");
+
+        // Hold back the conversion until there are legacy steps to convert
+        let legacy_version = "20251127000000";
+        {
+            let conn = pool.get().expect("Failed to get connection");
+            conn.execute_batch(
+                "CREATE TABLE _sqlx_migrations (
+                    version        BIGINT PRIMARY KEY,
+                    description    TEXT NOT NULL,
+                    installed_on   TIMESTAMP default CURRENT_TIMESTAMP NOT NULL,
+                    success        BOOLEAN                             NOT NULL,
+                    checksum       BLOB                                NOT NULL,
+                    execution_time BIGINT                              NOT NULL
+                )",
+            )
+            .expect("Failed to create migrations table");
+            conn.execute(
+                "INSERT INTO _sqlx_migrations (version, description, success, checksum, execution_time)
+                 VALUES (?, 'workflow-legacy-steps', 1, '', 0)",
+                [legacy_version],
+            )
+            .expect("Failed to hold back migration");
+        }
+        migrate_db(&pool).expect("Migration failed");
+
+        {
+            let conn = pool.get().expect("Failed to get connection");
+            conn.execute_batch(
+                "INSERT INTO workspaces (id, model, created_at, updated_at, name, description)
+                 VALUES ('wk1', 'workspace', 1, 1, 'Test Workspace', '');
+                 INSERT INTO workflows (id, model, created_at, updated_at, workspace_id, name, sort_priority)
+                 VALUES ('wf1', 'workflow', 1, 1, 'wk1', 'Legacy', 0),
+                        ('wf2', 'workflow', 1, 1, 'wk1', 'Mixed', 1);
+                 INSERT INTO workflow_steps
+                 (id, model, created_at, updated_at, workflow_id, request_id, request_model, name, enabled, sort_priority)
+                 VALUES ('ws1', 'workflow_step', 1, 1, 'wf1', 'rq1', 'http_request', 'Login', 1, 2),
+                        ('ws2', 'workflow_step', 1, 1, 'wf1', 'gr1', 'grpc_request', 'Lookup', 0, 1),
+                        ('ws3', 'workflow_step', 1, 1, 'wf2', 'rq2', 'http_request', 'Logout', 1, 0);
+                 INSERT INTO workflow_nodes
+                 (id, model, created_at, updated_at, workspace_id, workflow_id, node_type, node_subtype, name, config)
+                 VALUES ('tn1', 'workflow_node', 1, 1, 'wk1', 'wf2', 'trigger', 'manual_trigger', 'Start', '{}'),
+                        ('hn1', 'workflow_node', 1, 1, 'wk1', 'wf2', 'action', 'http_request', 'Canvas', '{}');
+                 INSERT INTO workflow_edges
+                 (id, model, created_at, updated_at, workspace_id, workflow_id, source_node_id, target_node_id)
+                 VALUES ('we_canvas', 'workflow_edge', 1, 1, 'wk1', 'wf2', 'tn1', 'hn1');
+                 INSERT INTO workflow_executions (id, model, created_at, updated_at, workflow_id, workspace_id, state)
+                 VALUES ('we1', 'workflow_execution', 1, 1, 'wf1', 'wk1', 'failed');
+                 INSERT INTO workflow_step_executions
+                 (id, model, created_at, updated_at, workflow_execution_id, workflow_step_id, request_id, response_id,
+                  response_model, elapsed, state, error)
+                 VALUES ('se1', 'workflow_step_execution', 1, 1, 'we1', 'ws1', 'rq1', 'rs1', 'http_response', 12,
+                         'failed', 'Boom');",
+            )
+            .expect("Failed to insert legacy workflow");
+            conn.execute("DELETE FROM _sqlx_migrations WHERE version = ?", [legacy_version])
+                .expect("Failed to release migration");
+        }
+        migrate_db(&pool).expect("Legacy migration failed");
+
+        let conn = pool.get().expect("Failed to get connection");
+
+        // The trigger comes first, followed by the steps in step order
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, node_subtype, name, config, enabled, legacy_step_id FROM workflow_nodes
+                 WHERE workflow_id = 'wf1' ORDER BY position_x",
+            )
+            .expect("Query failed");
+        let nodes: Vec<(String, String, String, String, bool, Option<String>)> = stmt
+            .query_map([], |r| {
+                Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?, r.get(4)?, r.get(5)?))
+            })
+            .expect("Query failed")
+            .collect::<rusqlite::Result<_>>()
+            .expect("Query failed");
+        let summary: Vec<_> = nodes
+            .iter()
+            .map(|(_, subtype, name, config, enabled, step)| {
+                (subtype.as_str(), name.as_str(), config.as_str(), *enabled, step.as_deref())
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("manual_trigger", "Start", "{}", true, None),
+                ("grpc_request", "Lookup", r#"{"request_id":"gr1"}"#, false, Some("ws2")),
+                ("http_request", "Login", r#"{"request_id":"rq1"}"#, true, Some("ws1")),
+            ]
+        );
+
+        let mut stmt = conn
+            .prepare("SELECT source_node_id, target_node_id FROM workflow_edges WHERE workflow_id = 'wf1'")
+            .expect("Query failed");
+        let mut edges: Vec<(String, String)> = stmt
+            .query_map([], |r| Ok((r.get(0)?, r.get(1)?)))
+            .expect("Query failed")
+            .collect::<rusqlite::Result<_>>()
+            .expect("Query failed");
+        edges.sort_by_key(|(source, _)| nodes.iter().position(|n| &n.0 == source));
+        assert_eq!(
+            edges,
+            vec![
+                (nodes[0].0.clone(), nodes[1].0.clone()),
+                (nodes[1].0.clone(), nodes[2].0.clone())
+            ]
+        );
+
+        // Past runs are kept as executions of the node the step became
+        let (node_id, state, error, response_id, attempt): (String, String, String, String, i32) = conn
+            .query_row(
+                "SELECT workflow_node_id, state, error, response_id, attempt FROM workflow_node_executions
+                 WHERE workflow_execution_id = 'we1'",
+                [],
+                |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?, r.get(4)?)),
+            )
+            .expect("Query failed");
+        assert_eq!(
+            (node_id, state.as_str(), error.as_str(), response_id.as_str(), attempt),
+            (nodes[2].0.clone(), "failed", "Boom", "rs1", 1)
+        );
+
+        let legacy_tables: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name LIKE 'workflow_step%'",
+                [],
+                |r| r.get(0),
+            )
+            .expect("Query failed");
+        assert_eq!(legacy_tables, 0, "Legacy step tables should be dropped");
+
+        // Steps of a workflow that already has a canvas hang off its existing trigger
+        let mut stmt = conn
+            .prepare(
+                "SELECT n.id, n.node_subtype, n.legacy_step_id FROM workflow_nodes n
+                 WHERE n.workflow_id = 'wf2' AND n.id NOT IN ('tn1', 'hn1')",
+            )
+            .expect("Query failed");
+        let new_nodes: Vec<(String, String, Option<String>)> = stmt
+            .query_map([], |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)))
+            .expect("Query failed")
+            .collect::<rusqlite::Result<_>>()
+            .expect("Query failed");
+        assert_eq!(new_nodes.len(), 1, "Only the step should be added, not another trigger");
+        let (step_node_id, subtype, step) = &new_nodes[0];
+        assert_eq!((subtype.as_str(), step.as_deref()), ("http_request", Some("ws3")));
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT source_node_id, target_node_id FROM workflow_edges
+                 WHERE workflow_id = 'wf2' ORDER BY source_node_id, target_node_id = 'hn1'",
+            )
+            .expect("Query failed");
+        let edges: Vec<(String, String)> = stmt
+            .query_map([], |r| Ok((r.get(0)?, r.get(1)?)))
+            .expect("Query failed")
+            .collect::<rusqlite::Result<_>>()
+            .expect("Query failed");
+        assert_eq!(
+            edges,
+            vec![
+                ("tn1".to_string(), step_node_id.clone()),
+                ("tn1".to_string(), "hn1".to_string())
+            ]
+        );
+    }
 }
@@ -264,7 +264,75 @@ pub struct Workspace {
     pub setting_validate_certificates: bool,
     #[serde(default = "default_true")]
     pub setting_follow_redirects: bool,
+    #[serde(default = "default_max_redirects")]
+    pub setting_max_redirects: i32,
     pub setting_request_timeout: i32,
+    pub setting_client_certificates: Vec<ClientCertificate>,
+    pub setting_ca_certificates: Vec<CaCertificate>,
+    pub setting_dns_overrides: Vec<DnsOverride>,
+    pub setting_dns_resolver: DnsResolverSetting,
+}
+
+/// A certificate (and key) presented to servers that ask for mutual TLS
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct ClientCertificate {
+    #[serde(default = "default_true")]
+    #[ts(optional, as = "Option<bool>")]
+    pub enabled: bool,
+    /// Host to use the certificate for. Supports `*` wildcards and an optional port, like
+    /// `*.internal.example.com` or `localhost:8443`
+    pub host: String,
+    /// PEM file with the certificate chain, used together with `key_file`
+    pub crt_file: Option<String>,
+    /// PEM file with the private key for `crt_file`
+    pub key_file: Option<String>,
+    /// PKCS#12 bundle with both the certificate chain and key, instead of `crt_file`/`key_file`
+    pub pfx_file: Option<String>,
+    pub passphrase: Option<String>,
+}
+
+/// Extra certificate authorities trusted on top of the platform ones
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct CaCertificate {
+    #[serde(default = "default_true")]
+    #[ts(optional, as = "Option<bool>")]
+    pub enabled: bool,
+    /// Host to trust the certificates for, with the same syntax as `ClientCertificate::host`
+    pub host: String,
+    /// PEM (or DER) file with one or more CA certificates
+    pub file: String,
+}
+
+/// Resolves a host to a fixed address, like curl's `--resolve`. The URL is left alone, so the
+/// request keeps its Host header and TLS server name.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct DnsOverride {
+    #[serde(default = "default_true")]
+    #[ts(optional, as = "Option<bool>")]
+    pub enabled: bool,
+    /// Host to override. Supports `*` wildcards, like `*.staging.example.com`
+    pub hostname: String,
+    /// IPv4 or IPv6 address to connect to instead
+    pub ip: String,
+}
+
+/// Where hosts without an override are looked up
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, TS)]
+#[serde(rename_all = "camelCase", tag = "type")]
+#[ts(export, export_to = "gen_models.ts")]
+pub enum DnsResolverSetting {
+    #[default]
+    System,
+    /// A plain DNS server, like `1.1.1.1` or `10.0.0.2:5353`
+    Nameserver { address: String },
+    /// A DNS-over-HTTPS endpoint, like `https://cloudflare-dns.com/dns-query`
+    Https { url: String },
 }
 
 impl UpsertModelInfo for Workspace {
@@ -303,8 +371,16 @@ impl UpsertModelInfo for Workspace {
             (Description, self.description.into()),
             (EncryptionKeyChallenge, self.encryption_key_challenge.into()),
             (SettingFollowRedirects, self.setting_follow_redirects.into()),
+            (SettingMaxRedirects, self.setting_max_redirects.into()),
             (SettingRequestTimeout, self.setting_request_timeout.into()),
             (SettingValidateCertificates, self.setting_validate_certificates.into()),
+            (
+                SettingClientCertificates,
+                serde_json::to_string(&self.setting_client_certificates)?.into(),
+            ),
+            (SettingCaCertificates, serde_json::to_string(&self.setting_ca_certificates)?.into()),
+            (SettingDnsOverrides, serde_json::to_string(&self.setting_dns_overrides)?.into()),
+            (SettingDnsResolver, serde_json::to_string(&self.setting_dns_resolver)?.into()),
         ])
     }
 
@@ -319,8 +395,13 @@ impl UpsertModelInfo for Workspace {
             WorkspaceIden::EncryptionKeyChallenge,
             WorkspaceIden::SettingRequestTimeout,
             WorkspaceIden::SettingFollowRedirects,
+            WorkspaceIden::SettingMaxRedirects,
             WorkspaceIden::SettingRequestTimeout,
             WorkspaceIden::SettingValidateCertificates,
+            WorkspaceIden::SettingClientCertificates,
+            WorkspaceIden::SettingCaCertificates,
+            WorkspaceIden::SettingDnsOverrides,
+            WorkspaceIden::SettingDnsResolver,
         ]
     }
 
@@ -330,6 +411,10 @@ impl UpsertModelInfo for Workspace {
     {
         let headers: String = row.get("headers")?;
         let authentication: String = row.get("authentication")?;
+        let client_certificates: String = row.get("setting_client_certificates")?;
+        let ca_certificates: String = row.get("setting_ca_certificates")?;
+        let dns_overrides: String = row.get("setting_dns_overrides")?;
+        let dns_resolver: String = row.get("setting_dns_resolver")?;
         Ok(Self {
             id: row.get("id")?,
             model: row.get("model")?,
@@ -342,8 +427,14 @@ impl UpsertModelInfo for Workspace {
             authentication: serde_json::from_str(&authentication).unwrap_or_default(),
             authentication_type: row.get("authentication_type")?,
             setting_follow_redirects: row.get("setting_follow_redirects")?,
+            setting_max_redirects: row.get("setting_max_redirects")?,
             setting_request_timeout: row.get("setting_request_timeout")?,
             setting_validate_certificates: row.get("setting_validate_certificates")?,
+            setting_client_certificates: serde_json::from_str(&client_certificates)
+                .unwrap_or_default(),
+            setting_ca_certificates: serde_json::from_str(&ca_certificates).unwrap_or_default(),
+            setting_dns_overrides: serde_json::from_str(&dns_overrides).unwrap_or_default(),
+            setting_dns_resolver: serde_json::from_str(&dns_resolver).unwrap_or_default(),
         })
     }
 }
@@ -819,6 +910,8 @@ pub struct HttpRequest {
     pub sort_priority: f64,
     pub url: String,
     pub url_parameters: Vec<HttpUrlParameter>,
+    /// Set on the hidden request an inline workflow HTTP node sends through
+    pub workflow_node_id: Option<String>,
 }
 
 impl UpsertModelInfo for HttpRequest {
@@ -862,6 +955,7 @@ impl UpsertModelInfo for HttpRequest {
             (AuthenticationType, self.authentication_type.into()),
             (Headers, serde_json::to_string(&self.headers)?.into()),
             (SortPriority, self.sort_priority.into()),
+            (WorkflowNodeId, self.workflow_node_id.into()),
         ])
     }
 
@@ -881,6 +975,7 @@ impl UpsertModelInfo for HttpRequest {
             Url,
             UrlParameters,
             SortPriority,
+            WorkflowNodeId,
         ]
     }
 
@@ -907,6 +1002,7 @@ impl UpsertModelInfo for HttpRequest {
             sort_priority: row.get("sort_priority")?,
             url: row.get("url")?,
             url_parameters: serde_json::from_str(url_parameters.as_str()).unwrap_or_default(),
+            workflow_node_id: row.get("workflow_node_id")?,
         })
     }
 }
@@ -1060,6 +1156,8 @@ pub struct WebsocketRequest {
     pub sort_priority: f64,
     pub url: String,
     pub url_parameters: Vec<HttpUrlParameter>,
+    /// Set on the hidden request an inline workflow WebSocket node connects through
+    pub workflow_node_id: Option<String>,
 }
 
 impl UpsertModelInfo for WebsocketRequest {
@@ -1102,6 +1200,7 @@ impl UpsertModelInfo for WebsocketRequest {
             (SortPriority, self.sort_priority.into()),
             (Url, self.url.into()),
             (UrlParameters, serde_json::to_string(&self.url_parameters)?.into()),
+            (WorkflowNodeId, self.workflow_node_id.into()),
         ])
     }
 
@@ -1119,6 +1218,7 @@ impl UpsertModelInfo for WebsocketRequest {
             WebsocketRequestIden::SortPriority,
             WebsocketRequestIden::Url,
             WebsocketRequestIden::UrlParameters,
+            WebsocketRequestIden::WorkflowNodeId,
         ]
     }
 
@@ -1145,6 +1245,7 @@ impl UpsertModelInfo for WebsocketRequest {
             headers: serde_json::from_str(headers.as_str()).unwrap_or_default(),
             folder_id: row.get("folder_id")?,
             name: row.get("name")?,
+            workflow_node_id: row.get("workflow_node_id")?,
         })
     }
 }
@@ -1262,6 +1363,58 @@ pub struct HttpResponseHeader {
     pub value: String,
 }
 
+/// A redirect that was followed on the way to the final response
+#[derive(Debug, Clone, Serialize, Deserialize, Default, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct HttpResponseRedirect {
+    pub status: i32,
+    pub method: String,
+    pub url: String,
+    pub location: String,
+    pub headers: Vec<HttpResponseHeader>,
+    pub elapsed: i32,
+}
+
+/// How long each phase of a request took, in milliseconds. The connection phases are zero when
+/// an existing connection was reused.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct HttpResponseTimings {
+    pub dns: i32,
+    pub connect: i32,
+    pub tls: i32,
+    /// From the request being sent (or the connection being ready) to the response headers
+    pub ttfb: i32,
+    pub download: i32,
+}
+
+/// The TLS connection a response was received over
+#[derive(Debug, Clone, Serialize, Deserialize, Default, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct HttpResponseTls {
+    pub version: Option<String>,
+    pub cipher: Option<String>,
+    /// PEM encoded certificates sent by the server, starting with its own
+    pub peer_certificates: Vec<String>,
+}
+
+/// The request as it was handed to the HTTP client, after rendering and authentication
+#[derive(Debug, Clone, Serialize, Deserialize, Default, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct HttpResponseRequest {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<HttpResponseHeader>,
+    /// The body, cut off after the first few megabytes
+    pub body_path: Option<String>,
+    /// Size of the whole body, even when the saved one was cut off
+    pub content_length: Option<i32>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[serde(rename_all = "snake_case")]
 #[ts(export, export_to = "gen_models.ts")]
@@ -1296,10 +1449,14 @@ pub struct HttpResponse {
     pub elapsed_headers: i32,
     pub error: Option<String>,
     pub headers: Vec<HttpResponseHeader>,
+    pub redirects: Vec<HttpResponseRedirect>,
     pub remote_addr: Option<String>,
+    pub request: Option<HttpResponseRequest>,
     pub status: i32,
     pub status_reason: Option<String>,
     pub state: HttpResponseState,
+    pub timings: HttpResponseTimings,
+    pub tls: Option<HttpResponseTls>,
     pub url: String,
     pub version: Option<String>,
 }
@@ -1341,10 +1498,14 @@ impl UpsertModelInfo for HttpResponse {
             (ElapsedHeaders, self.elapsed_headers.into()),
             (Error, self.error.into()),
             (Headers, serde_json::to_string(&self.headers)?.into()),
+            (Redirects, serde_json::to_string(&self.redirects)?.into()),
             (RemoteAddr, self.remote_addr.into()),
+            (Request, self.request.map(|r| serde_json::to_string(&r)).transpose()?.into()),
             (State, serde_json::to_value(self.state)?.as_str().into()),
             (Status, self.status.into()),
             (StatusReason, self.status_reason.into()),
+            (Timings, serde_json::to_string(&self.timings)?.into()),
+            (Tls, self.tls.map(|t| serde_json::to_string(&t)).transpose()?.into()),
             (Url, self.url.into()),
             (Version, self.version.into()),
         ])
@@ -1359,10 +1520,14 @@ impl UpsertModelInfo for HttpResponse {
             HttpResponseIden::ElapsedHeaders,
             HttpResponseIden::Error,
             HttpResponseIden::Headers,
+            HttpResponseIden::Redirects,
             HttpResponseIden::RemoteAddr,
+            HttpResponseIden::Request,
             HttpResponseIden::State,
             HttpResponseIden::Status,
             HttpResponseIden::StatusReason,
+            HttpResponseIden::Timings,
+            HttpResponseIden::Tls,
             HttpResponseIden::Url,
             HttpResponseIden::Version,
         ]
@@ -1373,6 +1538,10 @@ impl UpsertModelInfo for HttpResponse {
         Self: Sized,
     {
         let headers: String = r.get("headers")?;
+        let redirects: String = r.get("redirects")?;
+        let timings: String = r.get("timings")?;
+        let tls: Option<String> = r.get("tls")?;
+        let request: Option<String> = r.get("request")?;
         let state: String = r.get("state")?;
         Ok(Self {
             id: r.get("id")?,
@@ -1393,6 +1562,10 @@ impl UpsertModelInfo for HttpResponse {
             state: serde_json::from_str(format!(r#""{state}""#).as_str()).unwrap(),
             body_path: r.get("body_path")?,
             headers: serde_json::from_str(headers.as_str()).unwrap_or_default(),
+            redirects: serde_json::from_str(redirects.as_str()).unwrap_or_default(),
+            timings: serde_json::from_str(timings.as_str()).unwrap_or_default(),
+            tls: tls.and_then(|t| serde_json::from_str(t.as_str()).ok()),
+            request: request.and_then(|r| serde_json::from_str(r.as_str()).ok()),
         })
     }
 }
@@ -1494,6 +1667,8 @@ pub struct GrpcRequest {
     pub service: Option<String>,
     pub sort_priority: f64,
     pub url: String,
+    /// Set on the hidden request an inline workflow gRPC node sends through
+    pub workflow_node_id: Option<String>,
 }
 
 impl UpsertModelInfo for GrpcRequest {
@@ -1537,6 +1712,7 @@ impl UpsertModelInfo for GrpcRequest {
             (AuthenticationType, self.authentication_type.into()),
             (Authentication, serde_json::to_string(&self.authentication)?.into()),
             (Metadata, serde_json::to_string(&self.metadata)?.into()),
+            (WorkflowNodeId, self.workflow_node_id.into()),
         ])
     }
 
@@ -1555,6 +1731,7 @@ impl UpsertModelInfo for GrpcRequest {
             GrpcRequestIden::AuthenticationType,
             GrpcRequestIden::Authentication,
             GrpcRequestIden::Metadata,
+            GrpcRequestIden::WorkflowNodeId,
         ]
     }
 
@@ -1581,6 +1758,7 @@ impl UpsertModelInfo for GrpcRequest {
             url: row.get("url")?,
             sort_priority: row.get("sort_priority")?,
             metadata: serde_json::from_str(metadata.as_str()).unwrap_or_default(),
+            workflow_node_id: row.get("workflow_node_id")?,
         })
     }
 }
@@ -2088,6 +2266,10 @@ fn default_true() -> bool {
     true
 }
 
+fn default_max_redirects() -> i32 {
+    10
+}
+
 fn default_http_method() -> String {
     "GET".to_string()
 }
@@ -2155,13 +2337,12 @@ define_any_model! {
     WebsocketEvent,
     WebsocketRequest,
     Workflow,
-    WorkflowStep,
     WorkflowExecution,
-    WorkflowStepExecution,
     WorkflowNode,
     WorkflowEdge,
     WorkflowViewport,
     WorkflowNodeExecution,
+    WorkflowLoadTest,
     Workspace,
     WorkspaceMeta,
 }
@@ -2196,13 +2377,12 @@ impl<'de> Deserialize<'de> for AnyModel {
             Some(m) if m == "websocket_event" => AnyModel::WebsocketEvent(fv(value).unwrap()),
             Some(m) if m == "websocket_request" => AnyModel::WebsocketRequest(fv(value).unwrap()),
             Some(m) if m == "workflow" => AnyModel::Workflow(fv(value).unwrap()),
-            Some(m) if m == "workflow_step" => AnyModel::WorkflowStep(fv(value).unwrap()),
             Some(m) if m == "workflow_execution" => AnyModel::WorkflowExecution(fv(value).unwrap()),
-            Some(m) if m == "workflow_step_execution" => AnyModel::WorkflowStepExecution(fv(value).unwrap()),
             Some(m) if m == "workflow_node" => AnyModel::WorkflowNode(fv(value).unwrap()),
             Some(m) if m == "workflow_edge" => AnyModel::WorkflowEdge(fv(value).unwrap()),
             Some(m) if m == "workflow_viewport" => AnyModel::WorkflowViewport(fv(value).unwrap()),
             Some(m) if m == "workflow_node_execution" => AnyModel::WorkflowNodeExecution(fv(value).unwrap()),
+            Some(m) if m == "workflow_load_test" => AnyModel::WorkflowLoadTest(fv(value).unwrap()),
             Some(m) if m == "workspace" => AnyModel::Workspace(fv(value).unwrap()),
             Some(m) if m == "workspace_meta" => AnyModel::WorkspaceMeta(fv(value).unwrap()),
             Some(m) => {
@@ -2238,7 +2418,6 @@ impl AnyModel {
             AnyModel::HttpRequest(v) => compute_name(&v.name, &v.url, "HTTP Request"),
             AnyModel::WebsocketRequest(v) => compute_name(&v.name, &v.url, "WebSocket Request"),
             AnyModel::Workflow(v) => v.name,
-            AnyModel::WorkflowStep(v) => v.name,
             AnyModel::WorkflowNode(v) => v.name,
             AnyModel::Workspace(v) => v.name,
             _ => "No Name".to_string(),
@@ -2285,7 +2464,7 @@ fn upsert_date(update_source: &UpdateSource, dt: NaiveDateTime) -> SimpleExpr {
 // ============================================================================
 
 // Workflow: Container for test sequences
-#[derive(Debug, Clone, Serialize, Deserialize, Default, TS)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, TS)]
 #[serde(default, rename_all = "camelCase")]
 #[ts(export, export_to = "gen_models.ts")]
 #[enum_def(table_name = "workflows")]
@@ -2301,6 +2480,8 @@ pub struct Workflow {
     pub description: Option<String>,
     pub environment_id: Option<String>,
     pub sort_priority: f64,
+    /// CSV or JSON file to run the workflow once per row of, with the row's fields as variables
+    pub dataset_path: Option<String>,
 }
 
 impl UpsertModelInfo for Workflow {
@@ -2338,12 +2519,13 @@ impl UpsertModelInfo for Workflow {
             (Description, self.description.into()),
             (EnvironmentId, self.environment_id.into()),
             (SortPriority, self.sort_priority.into()),
+            (DatasetPath, self.dataset_path.into()),
         ])
     }
 
     fn update_columns() -> Vec<impl IntoIden> {
         use WorkflowIden::*;
-        vec![UpdatedAt, DeletedAt, Name, Description, EnvironmentId, SortPriority]
+        vec![UpdatedAt, DeletedAt, Name, Description, EnvironmentId, SortPriority, DatasetPath]
     }
 
     fn from_row(row: &Row) -> rusqlite::Result<Self> {
@@ -2358,87 +2540,7 @@ impl UpsertModelInfo for Workflow {
             description: row.get("description")?,
             environment_id: row.get("environment_id")?,
             sort_priority: row.get("sort_priority")?,
-        })
-    }
-}
-
-// WorkflowStep: Individual request in execution order
-#[derive(Debug, Clone, Serialize, Deserialize, Default, TS)]
-#[serde(default, rename_all = "camelCase")]
-#[ts(export, export_to = "gen_models.ts")]
-#[enum_def(table_name = "workflow_steps")]
-pub struct WorkflowStep {
-    #[ts(type = "\"workflow_step\"")]
-    pub model: String,
-    pub id: String,
-    pub created_at: NaiveDateTime,
-    pub updated_at: NaiveDateTime,
-    pub deleted_at: Option<NaiveDateTime>,
-    pub workflow_id: String,
-    pub request_id: String,
-    pub request_model: String,  // "http_request" | "grpc_request"
-    pub name: String,
-    pub enabled: bool,
-    pub sort_priority: f64,
-}
-
-impl UpsertModelInfo for WorkflowStep {
-    fn table_name() -> impl IntoTableRef + IntoIden {
-        WorkflowStepIden::Table
-    }
-
-    fn id_column() -> impl IntoIden + Eq + Clone {
-        WorkflowStepIden::Id
-    }
-
-    fn generate_id() -> String {
-        generate_prefixed_id("ws")
-    }
-
-    fn order_by() -> (impl IntoColumnRef, Order) {
-        (WorkflowStepIden::SortPriority, Order::Asc)
-    }
-
-    fn get_id(&self) -> String {
-        self.id.clone()
-    }
-
-    fn insert_values(
-        self,
-        source: &UpdateSource,
-    ) -> Result<Vec<(impl IntoIden + Eq, impl Into<SimpleExpr>)>> {
-        use WorkflowStepIden::*;
-        Ok(vec![
-            (CreatedAt, upsert_date(source, self.created_at)),
-            (UpdatedAt, upsert_date(source, self.updated_at)),
-            (DeletedAt, self.deleted_at.map(|d| d.and_utc().timestamp()).into()),
-            (WorkflowId, self.workflow_id.into()),
-            (RequestId, self.request_id.into()),
-            (RequestModel, self.request_model.into()),
-            (Name, self.name.trim().into()),
-            (Enabled, self.enabled.into()),
-            (SortPriority, self.sort_priority.into()),
-        ])
-    }
-
-    fn update_columns() -> Vec<impl IntoIden> {
-        use WorkflowStepIden::*;
-        vec![UpdatedAt, DeletedAt, Name, Enabled, SortPriority]
-    }
-
-    fn from_row(row: &Row) -> rusqlite::Result<Self> {
-        Ok(Self {
-            model: row.get("model")?,
-            id: row.get("id")?,
-            created_at: row.get("created_at")?,
-            updated_at: row.get("updated_at")?,
-            deleted_at: row.get("deleted_at")?,
-            workflow_id: row.get("workflow_id")?,
-            request_id: row.get("request_id")?,
-            request_model: row.get("request_model")?,
-            name: row.get("name")?,
-            enabled: row.get("enabled")?,
-            sort_priority: row.get("sort_priority")?,
+            dataset_path: row.get("dataset_path")?,
         })
     }
 }
@@ -2461,6 +2563,18 @@ pub struct WorkflowExecution {
     pub elapsed: Option<i32>,
     pub state: WorkflowExecutionState,
     pub error: Option<String>,
+    pub assertions_passed: i32,
+    pub assertions_failed: i32,
+    /// Earlier execution this one was resumed from, reusing its completed node results
+    pub resumed_from_id: Option<String>,
+    /// Execution grouping the runs of a dataset, one per row
+    pub parent_execution_id: Option<String>,
+    /// Index of the dataset row this execution ran with
+    pub dataset_row: Option<i32>,
+    /// Load test this execution ran as one iteration of
+    pub load_test_id: Option<String>,
+    /// Execution whose sub-workflow node started this one
+    pub caller_execution_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS, PartialEq, Default)]
@@ -2539,12 +2653,19 @@ impl UpsertModelInfo for WorkflowExecution {
             (Elapsed, self.elapsed.into()),
             (State, self.state.to_string().into()),
             (Error, self.error.into()),
+            (AssertionsPassed, self.assertions_passed.into()),
+            (AssertionsFailed, self.assertions_failed.into()),
+            (ResumedFromId, self.resumed_from_id.into()),
+            (ParentExecutionId, self.parent_execution_id.into()),
+            (DatasetRow, self.dataset_row.into()),
+            (LoadTestId, self.load_test_id.into()),
+            (CallerExecutionId, self.caller_execution_id.into()),
         ])
     }
 
     fn update_columns() -> Vec<impl IntoIden> {
         use WorkflowExecutionIden::*;
-        vec![UpdatedAt, DeletedAt, Elapsed, State, Error]
+        vec![UpdatedAt, DeletedAt, Elapsed, State, Error, AssertionsPassed, AssertionsFailed]
     }
 
     fn from_row(row: &Row) -> rusqlite::Result<Self> {
@@ -2564,137 +2685,13 @@ impl UpsertModelInfo for WorkflowExecution {
             elapsed: row.get("elapsed")?,
             state,
             error: row.get("error")?,
-        })
-    }
-}
-
-// WorkflowStepExecution: Results of individual step execution
-#[derive(Debug, Clone, Serialize, Deserialize, Default, TS)]
-#[serde(default, rename_all = "camelCase")]
-#[ts(export, export_to = "gen_models.ts")]
-#[enum_def(table_name = "workflow_step_executions")]
-pub struct WorkflowStepExecution {
-    #[ts(type = "\"workflow_step_execution\"")]
-    pub model: String,
-    pub id: String,
-    pub created_at: NaiveDateTime,
-    pub updated_at: NaiveDateTime,
-    pub deleted_at: Option<NaiveDateTime>,
-    pub workflow_execution_id: String,
-    pub workflow_step_id: String,
-    pub request_id: String,
-    pub response_id: Option<String>,
-    pub response_model: Option<String>,  // "http_response" | "grpc_connection"
-    pub elapsed: Option<i32>,
-    pub state: WorkflowStepExecutionState,
-    pub error: Option<String>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize, TS, PartialEq, Default)]
-#[serde(rename_all = "lowercase")]
-#[ts(export, export_to = "gen_models.ts")]
-pub enum WorkflowStepExecutionState {
-    #[default]
-    Pending,
-    Running,
-    Completed,
-    Failed,
-    Skipped,
-}
-
-impl FromStr for WorkflowStepExecutionState {
-    type Err = crate::error::Error;
-
-    fn from_str(s: &str) -> Result<Self> {
-        match s {
-            "pending" => Ok(Self::Pending),
-            "running" => Ok(Self::Running),
-            "completed" => Ok(Self::Completed),
-            "failed" => Ok(Self::Failed),
-            "skipped" => Ok(Self::Skipped),
-            _ => Err(crate::error::Error::GenericError(format!("Invalid WorkflowStepExecutionState: {}", s))),
-        }
-    }
-}
-
-impl Display for WorkflowStepExecutionState {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let str = match self {
-            WorkflowStepExecutionState::Pending => "pending",
-            WorkflowStepExecutionState::Running => "running",
-            WorkflowStepExecutionState::Completed => "completed",
-            WorkflowStepExecutionState::Failed => "failed",
-            WorkflowStepExecutionState::Skipped => "skipped",
-        };
-        write!(f, "{}", str)
-    }
-}
-
-impl UpsertModelInfo for WorkflowStepExecution {
-    fn table_name() -> impl IntoTableRef + IntoIden {
-        WorkflowStepExecutionIden::Table
-    }
-
-    fn id_column() -> impl IntoIden + Eq + Clone {
-        WorkflowStepExecutionIden::Id
-    }
-
-    fn generate_id() -> String {
-        generate_prefixed_id("se")
-    }
-
-    fn order_by() -> (impl IntoColumnRef, Order) {
-        (WorkflowStepExecutionIden::CreatedAt, Order::Asc)
-    }
-
-    fn get_id(&self) -> String {
-        self.id.clone()
-    }
-
-    fn insert_values(
-        self,
-        source: &UpdateSource,
-    ) -> Result<Vec<(impl IntoIden + Eq, impl Into<SimpleExpr>)>> {
-        use WorkflowStepExecutionIden::*;
-        Ok(vec![
-            (CreatedAt, upsert_date(source, self.created_at)),
-            (UpdatedAt, upsert_date(source, self.updated_at)),
-            (DeletedAt, self.deleted_at.map(|d| d.and_utc().timestamp()).into()),
-            (WorkflowExecutionId, self.workflow_execution_id.into()),
-            (WorkflowStepId, self.workflow_step_id.into()),
-            (RequestId, self.request_id.into()),
-            (ResponseId, self.response_id.into()),
-            (ResponseModel, self.response_model.into()),
-            (Elapsed, self.elapsed.into()),
-            (State, self.state.to_string().into()),
-            (Error, self.error.into()),
-        ])
-    }
-
-    fn update_columns() -> Vec<impl IntoIden> {
-        use WorkflowStepExecutionIden::*;
-        vec![UpdatedAt, DeletedAt, ResponseId, ResponseModel, Elapsed, State, Error]
-    }
-
-    fn from_row(row: &Row) -> rusqlite::Result<Self> {
-        let state_str: String = row.get("state")?;
-        let state = WorkflowStepExecutionState::from_str(&state_str)
-            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e)))?;
-        
-        Ok(Self {
-            model: row.get("model")?,
-            id: row.get("id")?,
-            created_at: row.get("created_at")?,
-            updated_at: row.get("updated_at")?,
-            deleted_at: row.get("deleted_at")?,
-            workflow_execution_id: row.get("workflow_execution_id")?,
-            workflow_step_id: row.get("workflow_step_id")?,
-            request_id: row.get("request_id")?,
-            response_id: row.get("response_id")?,
-            response_model: row.get("response_model")?,
-            elapsed: row.get("elapsed")?,
-            state,
-            error: row.get("error")?,
+            assertions_passed: row.get("assertions_passed")?,
+            assertions_failed: row.get("assertions_failed")?,
+            resumed_from_id: row.get("resumed_from_id")?,
+            parent_execution_id: row.get("parent_execution_id")?,
+            dataset_row: row.get("dataset_row")?,
+            load_test_id: row.get("load_test_id")?,
+            caller_execution_id: row.get("caller_execution_id")?,
         })
     }
 }
@@ -2704,7 +2701,7 @@ impl UpsertModelInfo for WorkflowStepExecution {
 // ============================================================================
 
 // WorkflowNode: Individual node in visual workflow canvas
-#[derive(Debug, Clone, Serialize, Deserialize, Default, TS)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, TS)]
 #[serde(default, rename_all = "camelCase")]
 #[ts(export, export_to = "gen_models.ts")]
 #[enum_def(table_name = "workflow_nodes")]
@@ -2716,6 +2713,7 @@ pub struct WorkflowNode {
     pub updated_at: NaiveDateTime,
     pub deleted_at: Option<NaiveDateTime>,
 
+    pub workspace_id: String,
     pub workflow_id: String,
     pub node_type: NodeType,
     pub node_subtype: String,
@@ -2730,6 +2728,7 @@ pub struct WorkflowNode {
     pub config: Value,
     pub enabled: bool,
 
+    /// Step of a legacy linear workflow that this node was converted from
     pub legacy_step_id: Option<String>,
 }
 
@@ -2802,6 +2801,7 @@ impl UpsertModelInfo for WorkflowNode {
             (CreatedAt, upsert_date(source, self.created_at)),
             (UpdatedAt, upsert_date(source, self.updated_at)),
             (DeletedAt, self.deleted_at.map(|d| d.and_utc().timestamp()).into()),
+            (WorkspaceId, self.workspace_id.into()),
             (WorkflowId, self.workflow_id.into()),
             (NodeType, self.node_type.to_string().into()),
             (NodeSubtype, self.node_subtype.into()),
@@ -2837,6 +2837,7 @@ impl UpsertModelInfo for WorkflowNode {
             created_at: row.get("created_at")?,
             updated_at: row.get("updated_at")?,
             deleted_at: row.get("deleted_at")?,
+            workspace_id: row.get("workspace_id")?,
             workflow_id: row.get("workflow_id")?,
             node_type,
             node_subtype: row.get("node_subtype")?,
@@ -2854,7 +2855,7 @@ impl UpsertModelInfo for WorkflowNode {
 }
 
 // WorkflowEdge: Connection between workflow nodes
-#[derive(Debug, Clone, Serialize, Deserialize, Default, TS)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, TS)]
 #[serde(default, rename_all = "camelCase")]
 #[ts(export, export_to = "gen_models.ts")]
 #[enum_def(table_name = "workflow_edges")]
@@ -2866,6 +2867,7 @@ pub struct WorkflowEdge {
     pub updated_at: NaiveDateTime,
     pub deleted_at: Option<NaiveDateTime>,
 
+    pub workspace_id: String,
     pub workflow_id: String,
     pub source_node_id: String,
     pub target_node_id: String,
@@ -2946,6 +2948,7 @@ impl UpsertModelInfo for WorkflowEdge {
             (CreatedAt, upsert_date(source, self.created_at)),
             (UpdatedAt, upsert_date(source, self.updated_at)),
             (DeletedAt, self.deleted_at.map(|d| d.and_utc().timestamp()).into()),
+            (WorkspaceId, self.workspace_id.into()),
             (WorkflowId, self.workflow_id.into()),
             (SourceNodeId, self.source_node_id.into()),
             (TargetNodeId, self.target_node_id.into()),
@@ -2957,7 +2960,15 @@ impl UpsertModelInfo for WorkflowEdge {
 
     fn update_columns() -> Vec<impl IntoIden> {
         use WorkflowEdgeIden::*;
-        vec![UpdatedAt, DeletedAt]
+        vec![
+            UpdatedAt,
+            DeletedAt,
+            SourceNodeId,
+            TargetNodeId,
+            SourceAnchor,
+            TargetAnchor,
+            EdgeType,
+        ]
     }
 
     fn from_row(row: &Row) -> rusqlite::Result<Self> {
@@ -2971,6 +2982,7 @@ impl UpsertModelInfo for WorkflowEdge {
             created_at: row.get("created_at")?,
             updated_at: row.get("updated_at")?,
             deleted_at: row.get("deleted_at")?,
+            workspace_id: row.get("workspace_id")?,
             workflow_id: row.get("workflow_id")?,
             source_node_id: row.get("source_node_id")?,
             target_node_id: row.get("target_node_id")?,
@@ -3076,6 +3088,12 @@ pub struct WorkflowNodeExecution {
     pub result: Option<Value>,
 
     pub loop_iteration: Option<i32>,
+
+    pub response_id: Option<String>,
+    pub response_model: Option<String>,  // "http_response" | "grpc_connection" | "websocket_connection"
+
+    /// 1-based attempt number, incremented for every retry of the node
+    pub attempt: i32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS, PartialEq, Eq)]
@@ -3160,12 +3178,15 @@ impl UpsertModelInfo for WorkflowNodeExecution {
             (Error, self.error.into()),
             (Result, self.result.map(|r| r.to_string()).into()),
             (LoopIteration, self.loop_iteration.into()),
+            (ResponseId, self.response_id.into()),
+            (ResponseModel, self.response_model.into()),
+            (Attempt, self.attempt.into()),
         ])
     }
 
     fn update_columns() -> Vec<impl IntoIden> {
         use WorkflowNodeExecutionIden::*;
-        vec![UpdatedAt, DeletedAt, Elapsed, State, Error, Result]
+        vec![UpdatedAt, DeletedAt, Elapsed, State, Error, Result, ResponseId, ResponseModel]
     }
 
     fn from_row(row: &Row) -> rusqlite::Result<Self> {
@@ -3189,6 +3210,122 @@ impl UpsertModelInfo for WorkflowNodeExecution {
             error: row.get("error")?,
             result: result_value,
             loop_iteration: row.get("loop_iteration")?,
+            response_id: row.get("response_id")?,
+            response_model: row.get("response_model")?,
+            attempt: row.get("attempt")?,
+        })
+    }
+}
+
+// WorkflowLoadTest: Aggregated results of running a workflow many times concurrently
+#[derive(Debug, Clone, Serialize, Deserialize, Default, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+#[enum_def(table_name = "workflow_load_tests")]
+pub struct WorkflowLoadTest {
+    #[ts(type = "\"workflow_load_test\"")]
+    pub model: String,
+    pub id: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    pub deleted_at: Option<NaiveDateTime>,
+    pub workflow_id: String,
+    pub workspace_id: String,
+    pub environment_id: Option<String>,
+    pub state: WorkflowExecutionState,
+
+    /// Total number of workflow runs
+    pub iterations: i32,
+    /// Number of runs in flight at once
+    pub concurrency: i32,
+    /// Time over which the concurrent workers are started
+    pub ramp_up_ms: i32,
+
+    pub completed: i32,
+    pub failed: i32,
+    pub elapsed: Option<i32>,
+    pub error: Option<String>,
+
+    /// Per-node latency percentiles, error rates and throughput
+    pub summary: Option<Value>,
+}
+
+impl UpsertModelInfo for WorkflowLoadTest {
+    fn table_name() -> impl IntoTableRef + IntoIden {
+        WorkflowLoadTestIden::Table
+    }
+
+    fn id_column() -> impl IntoIden + Eq + Clone {
+        WorkflowLoadTestIden::Id
+    }
+
+    fn generate_id() -> String {
+        generate_prefixed_id("wl")
+    }
+
+    fn order_by() -> (impl IntoColumnRef, Order) {
+        (WorkflowLoadTestIden::CreatedAt, Desc)
+    }
+
+    fn get_id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn insert_values(
+        self,
+        source: &UpdateSource,
+    ) -> Result<Vec<(impl IntoIden + Eq, impl Into<SimpleExpr>)>> {
+        use WorkflowLoadTestIden::*;
+        Ok(vec![
+            (CreatedAt, upsert_date(source, self.created_at)),
+            (UpdatedAt, upsert_date(source, self.updated_at)),
+            (DeletedAt, self.deleted_at.map(|d| d.and_utc().timestamp()).into()),
+            (WorkflowId, self.workflow_id.into()),
+            (WorkspaceId, self.workspace_id.into()),
+            (EnvironmentId, self.environment_id.into()),
+            (State, self.state.to_string().into()),
+            (Iterations, self.iterations.into()),
+            (Concurrency, self.concurrency.into()),
+            (RampUpMs, self.ramp_up_ms.into()),
+            (Completed, self.completed.into()),
+            (Failed, self.failed.into()),
+            (Elapsed, self.elapsed.into()),
+            (Error, self.error.into()),
+            (Summary, self.summary.map(|s| s.to_string()).into()),
+        ])
+    }
+
+    fn update_columns() -> Vec<impl IntoIden> {
+        use WorkflowLoadTestIden::*;
+        vec![UpdatedAt, DeletedAt, State, Completed, Failed, Elapsed, Error, Summary]
+    }
+
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        let state_str: String = row.get("state")?;
+        let state = WorkflowExecutionState::from_str(&state_str)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e)))?;
+
+        let summary: Option<String> = row.get("summary")?;
+        let summary = summary.and_then(|s| serde_json::from_str(&s).ok());
+
+        Ok(Self {
+            model: row.get("model")?,
+            id: row.get("id")?,
+            created_at: row.get("created_at")?,
+            updated_at: row.get("updated_at")?,
+            deleted_at: row.get("deleted_at")?,
+            workflow_id: row.get("workflow_id")?,
+            workspace_id: row.get("workspace_id")?,
+            environment_id: row.get("environment_id")?,
+            state,
+            iterations: row.get("iterations")?,
+            concurrency: row.get("concurrency")?,
+            ramp_up_ms: row.get("ramp_up_ms")?,
+            completed: row.get("completed")?,
+            failed: row.get("failed")?,
+            elapsed: row.get("elapsed")?,
+            error: row.get("error")?,
+            summary,
         })
     }
 }
@@ -3283,6 +3420,21 @@ pub struct NodeTypeDefinition {
     pub default_config: Value,
 }
 
+/// Schema for the optional `policy` object that every node config may contain, controlling
+/// retries, timeouts and whether a failure stops the workflow
+pub fn get_node_policy_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "max_retries": { "type": "integer", "minimum": 0, "maximum": 10 },
+            "retry_backoff_ms": { "type": "integer", "minimum": 0 },
+            "backoff_multiplier": { "type": "number", "minimum": 1 },
+            "timeout_ms": { "type": "integer", "minimum": 0 },
+            "continue_on_error": { "type": "boolean" }
+        }
+    })
+}
+
 /// Built-in node type registry with all 11 supported node types
 pub fn get_node_type_definitions() -> Vec<NodeTypeDefinition> {
     vec![
@@ -3306,17 +3458,18 @@ pub fn get_node_type_definitions() -> Vec<NodeTypeDefinition> {
             color: "#10b981".to_string(),
             schema: json!({
                 "type": "object",
-                "required": ["url", "method"],
+                "required": ["method"],
                 "properties": {
                     "url": { "type": "string", "format": "uri" },
-                    "method": { "type": "string", "enum": ["GET", "POST", "PUT", "DELETE"] },
-                    "auth_token": { "type": "string" }
+                    "method": { "type": "string", "enum": ["GET", "POST", "PUT", "PATCH", "DELETE"] },
+                    "auth_token": { "type": "string" },
+                    "allow_unauthenticated": { "type": "boolean" }
                 }
             }),
             default_config: json!({
-                "url": "https://webhook.yaak.app/{{uuid()}}",
                 "method": "POST",
-                "auth_token": ""
+                "auth_token": "",
+                "allow_unauthenticated": false
             }),
         },
         NodeTypeDefinition {
@@ -3332,16 +3485,18 @@ pub fn get_node_type_definitions() -> Vec<NodeTypeDefinition> {
                 "properties": {
                     "schedule_type": { "type": "string", "enum": ["cron", "interval"] },
                     "cron_expression": { "type": "string" },
-                    "interval_minutes": { "type": "integer", "minimum": 1 }
+                    "interval_minutes": { "type": "integer", "minimum": 1 },
+                    "overlap_policy": { "type": "string", "enum": ["skip", "queue"] }
                 }
             }),
             default_config: json!({
                 "schedule_type": "interval",
-                "interval_minutes": 60
+                "interval_minutes": 60,
+                "overlap_policy": "skip"
             }),
         },
 
-        // ============ Actions (5) ============
+        // ============ Actions (6) ============
         NodeTypeDefinition {
             category: NodeType::Action,
             subtype: "http_request".to_string(),
@@ -3351,12 +3506,16 @@ pub fn get_node_type_definitions() -> Vec<NodeTypeDefinition> {
             color: "#8b5cf6".to_string(),
             schema: json!({
                 "type": "object",
-                "required": ["method", "url"],
+                "if": { "required": ["request_id"] },
+                "else": { "required": ["method", "url"] },
                 "properties": {
+                    "request_id": { "type": "string" },
                     "method": { "type": "string", "enum": ["GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS"] },
                     "url": { "type": "string" },
                     "headers": { "type": "array", "items": { "type": "object" } },
                     "body": { "type": "string" },
+                    "body_type": { "type": "string" },
+                    "cookie_jar_id": { "type": "string" },
                     "auth_enabled": { "type": "boolean" }
                 }
             }),
@@ -3377,12 +3536,16 @@ pub fn get_node_type_definitions() -> Vec<NodeTypeDefinition> {
             color: "#8b5cf6".to_string(),
             schema: json!({
                 "type": "object",
-                "required": ["url", "service", "method"],
+                "if": { "required": ["request_id"] },
+                "else": { "required": ["url", "service", "method"] },
                 "properties": {
+                    "request_id": { "type": "string" },
                     "url": { "type": "string" },
                     "service": { "type": "string" },
                     "method": { "type": "string" },
-                    "message": { "type": "string" }
+                    "message": { "type": ["string", "object"] },
+                    "metadata": { "type": "array", "items": { "type": "object" } },
+                    "proto_files": { "type": "array", "items": { "type": "string" } }
                 }
             }),
             default_config: json!({
@@ -3453,10 +3616,15 @@ pub fn get_node_type_definitions() -> Vec<NodeTypeDefinition> {
             color: "#06b6d4".to_string(),
             schema: json!({
                 "type": "object",
-                "required": ["url"],
+                "if": { "required": ["request_id"] },
+                "else": { "required": ["url"] },
                 "properties": {
+                    "request_id": { "type": "string" },
                     "url": { "type": "string", "format": "uri" },
-                    "messages": { "type": "array", "items": { "type": "string" } },
+                    "headers": { "type": "array", "items": { "type": "object" } },
+                    "messages": { "type": "array", "items": { "type": ["string", "object"] } },
+                    "expect_count": { "type": "integer", "minimum": 1 },
+                    "until_match": { "type": "string" },
                     "timeout_seconds": { "type": "integer", "minimum": 1, "maximum": 300 }
                 }
             }),
@@ -3466,8 +3634,30 @@ pub fn get_node_type_definitions() -> Vec<NodeTypeDefinition> {
                 "timeout_seconds": 30
             }),
         },
+        NodeTypeDefinition {
+            category: NodeType::Action,
+            subtype: "sub_workflow".to_string(),
+            name: "Sub-Workflow".to_string(),
+            description: "Run another workflow in this workspace".to_string(),
+            icon: "🧩".to_string(),
+            color: "#a855f7".to_string(),
+            schema: json!({
+                "type": "object",
+                "required": ["workflow_id"],
+                "properties": {
+                    "workflow_id": { "type": "string", "minLength": 1 },
+                    "inputs": { "type": "object" },
+                    "outputs": { "type": "array", "items": { "type": "string" } }
+                }
+            }),
+            default_config: json!({
+                "workflow_id": "",
+                "inputs": {},
+                "outputs": []
+            }),
+        },
 
-        // ============ Logic Control (3) ============
+        // ============ Logic Control (5) ============
         NodeTypeDefinition {
             category: NodeType::Logic,
             subtype: "conditional".to_string(),
@@ -3479,11 +3669,11 @@ pub fn get_node_type_definitions() -> Vec<NodeTypeDefinition> {
                 "type": "object",
                 "required": ["condition"],
                 "properties": {
-                    "condition": { "type": "string" }
+                    "condition": { "type": "string", "minLength": 1 }
                 }
             }),
             default_config: json!({
-                "condition": "{{step[0].response.status}} == 200"
+                "condition": "status == 200"
             }),
         },
         NodeTypeDefinition {
@@ -3519,6 +3709,7 @@ pub fn get_node_type_definitions() -> Vec<NodeTypeDefinition> {
                 "required": ["branch_count"],
                 "properties": {
                     "branch_count": { "type": "integer", "minimum": 2, "maximum": 10 },
+                    "max_concurrency": { "type": "integer", "minimum": 1 },
                     "fail_fast": { "type": "boolean" }
                 }
             }),
@@ -3527,6 +3718,87 @@ pub fn get_node_type_definitions() -> Vec<NodeTypeDefinition> {
                 "fail_fast": true
             }),
         },
+        NodeTypeDefinition {
+            category: NodeType::Logic,
+            subtype: "assertion".to_string(),
+            name: "Assertion".to_string(),
+            description: "Check results of a previous node".to_string(),
+            icon: "✅".to_string(),
+            color: "#22c55e".to_string(),
+            schema: json!({
+                "type": "object",
+                "required": ["assertions"],
+                "properties": {
+                    "source_node": { "type": "string" },
+                    "stop_on_failure": { "type": "boolean" },
+                    "assertions": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "required": ["type"],
+                            "properties": {
+                                "type": {
+                                    "type": "string",
+                                    "enum": ["status", "header", "json_schema", "json_path", "response_time", "expression"]
+                                },
+                                "name": { "type": "string" },
+                                "path": { "type": "string" },
+                                "schema": { "type": "object" },
+                                "operator": {
+                                    "type": "string",
+                                    "enum": [
+                                        "equals", "not_equals", "less_than", "less_than_or_equal", "greater_than",
+                                        "greater_than_or_equal", "contains", "not_contains", "matches", "exists",
+                                        "not_exists"
+                                    ]
+                                },
+                                "expected": {}
+                            }
+                        }
+                    }
+                }
+            }),
+            default_config: json!({
+                "assertions": [
+                    { "type": "status", "operator": "equals", "expected": 200 }
+                ],
+                "stop_on_failure": true
+            }),
+        },
+        NodeTypeDefinition {
+            category: NodeType::Logic,
+            subtype: "set_variable".to_string(),
+            name: "Set Variable".to_string(),
+            description: "Capture values from a previous node into variables".to_string(),
+            icon: "📌".to_string(),
+            color: "#eab308".to_string(),
+            schema: json!({
+                "type": "object",
+                "required": ["variables"],
+                "properties": {
+                    "source_node": { "type": "string" },
+                    "environment_id": { "type": "string" },
+                    "variables": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "required": ["name", "type", "path"],
+                            "properties": {
+                                "name": { "type": "string", "minLength": 1 },
+                                "type": { "type": "string", "enum": ["json_path", "regex", "header"] },
+                                "path": { "type": "string" },
+                                "default": { "type": "string" }
+                            }
+                        }
+                    }
+                }
+            }),
+            default_config: json!({
+                "variables": [
+                    { "name": "token", "type": "json_path", "path": "$.body.token" }
+                ]
+            }),
+        },
     ]
 }
 
@@ -11,7 +11,15 @@ impl<'a> DbContext<'a> {
     }
 
     pub fn list_grpc_requests(&self, workspace_id: &str) -> Result<Vec<GrpcRequest>> {
-        self.find_many(GrpcRequestIden::WorkspaceId, workspace_id, None)
+        let requests: Vec<GrpcRequest> =
+            self.find_many(GrpcRequestIden::WorkspaceId, workspace_id, None)?;
+        // Requests of inline workflow nodes are an implementation detail of the node
+        Ok(requests.into_iter().filter(|r| r.workflow_node_id.is_none()).collect())
+    }
+
+    /// The hidden request an inline workflow gRPC node connects through, if it ran before
+    pub fn get_workflow_node_grpc_request(&self, workflow_node_id: &str) -> Option<GrpcRequest> {
+        self.find_optional(GrpcRequestIden::WorkflowNodeId, workflow_node_id)
     }
 
     pub fn delete_grpc_request(
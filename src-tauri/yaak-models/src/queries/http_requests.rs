@@ -11,7 +11,19 @@ impl<'a> DbContext<'a> {
     }
 
     pub fn list_http_requests(&self, workspace_id: &str) -> Result<Vec<HttpRequest>> {
-        self.find_many(HttpRequestIden::WorkspaceId, workspace_id, None)
+        let requests: Vec<HttpRequest> =
+            self.find_many(HttpRequestIden::WorkspaceId, workspace_id, None)?;
+        // Requests of inline workflow nodes are an implementation detail of the node
+        Ok(requests.into_iter().filter(|r| r.workflow_node_id.is_none()).collect())
+    }
+
+    /// The hidden request an inline workflow HTTP node sends through, if it ran before
+    ///
+    /// Responses, connections and events all belong to a saved request, so inline request nodes
+    /// of every kind save one owned by the node. It's reused across runs to keep their history
+    /// together, left out of listings, and deleted along with the node.
+    pub fn get_workflow_node_http_request(&self, workflow_node_id: &str) -> Option<HttpRequest> {
+        self.find_optional(HttpRequestIden::WorkflowNodeId, workflow_node_id)
     }
 
     pub fn delete_http_request(
@@ -66,6 +66,13 @@ impl<'a> DbContext<'a> {
             };
         }
 
+        // And the body of the request that was sent
+        if let Some(p) = http_response.request.as_ref().and_then(|r| r.body_path.clone()) {
+            if let Err(e) = fs::remove_file(p) {
+                error!("Failed to delete request body file: {}", e);
+            };
+        }
+
         Ok(self.delete(http_response, source)?)
     }
 
@@ -18,7 +18,7 @@ mod websocket_connections;
 mod websocket_events;
 mod websocket_requests;
 pub mod workflow_executions;
-pub mod workflow_steps;
+pub mod workflow_load_tests;
 pub mod workflows;
 pub mod workflow_nodes;
 pub mod workflow_edges;
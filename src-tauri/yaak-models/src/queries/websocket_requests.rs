@@ -11,7 +11,18 @@ impl<'a> DbContext<'a> {
     }
 
     pub fn list_websocket_requests(&self, workspace_id: &str) -> Result<Vec<WebsocketRequest>> {
-        self.find_many(WebsocketRequestIden::WorkspaceId, workspace_id, None)
+        let requests: Vec<WebsocketRequest> =
+            self.find_many(WebsocketRequestIden::WorkspaceId, workspace_id, None)?;
+        // Requests of inline workflow nodes are an implementation detail of the node
+        Ok(requests.into_iter().filter(|r| r.workflow_node_id.is_none()).collect())
+    }
+
+    /// The hidden request an inline workflow WebSocket node connects through, if it ran before
+    pub fn get_workflow_node_websocket_request(
+        &self,
+        workflow_node_id: &str,
+    ) -> Option<WebsocketRequest> {
+        self.find_optional(WebsocketRequestIden::WorkflowNodeId, workflow_node_id)
     }
 
     pub fn delete_websocket_request(
@@ -44,35 +44,6 @@ impl<'a> DbContext<'a> {
 
     /// List all workflow edges for a workspace (across all workflows)
     pub fn list_workflow_edges_by_workspace(&self, workspace_id: &str) -> Result<Vec<WorkflowEdge>> {
-        use sea_query::{Expr, Query, SqliteQueryBuilder};
-        use sea_query_rusqlite::RusqliteBinder;
-        use crate::models::{WorkflowIden, UpsertModelInfo};
-
-        let (sql, params) = Query::select()
-            .columns([
-                (WorkflowEdgeIden::Table, WorkflowEdgeIden::Id),
-                (WorkflowEdgeIden::Table, WorkflowEdgeIden::Model),
-                (WorkflowEdgeIden::Table, WorkflowEdgeIden::WorkflowId),
-                (WorkflowEdgeIden::Table, WorkflowEdgeIden::SourceNodeId),
-                (WorkflowEdgeIden::Table, WorkflowEdgeIden::TargetNodeId),
-                (WorkflowEdgeIden::Table, WorkflowEdgeIden::SourceAnchor),
-                (WorkflowEdgeIden::Table, WorkflowEdgeIden::TargetAnchor),
-                (WorkflowEdgeIden::Table, WorkflowEdgeIden::EdgeType),
-                (WorkflowEdgeIden::Table, WorkflowEdgeIden::CreatedAt),
-                (WorkflowEdgeIden::Table, WorkflowEdgeIden::UpdatedAt),
-                (WorkflowEdgeIden::Table, WorkflowEdgeIden::DeletedAt),
-            ])
-            .from(WorkflowEdgeIden::Table)
-            .inner_join(
-                WorkflowIden::Table,
-                Expr::col((WorkflowEdgeIden::Table, WorkflowEdgeIden::WorkflowId))
-                    .equals((WorkflowIden::Table, WorkflowIden::Id))
-            )
-            .and_where(Expr::col((WorkflowIden::Table, WorkflowIden::WorkspaceId)).eq(workspace_id))
-            .build_rusqlite(SqliteQueryBuilder);
-
-        let mut stmt = self.conn.resolve().prepare(sql.as_str())?;
-        let rows = stmt.query_map(&*params.as_params(), |row| WorkflowEdge::from_row(row))?;
-        rows.collect::<std::result::Result<Vec<_>, _>>().map_err(Into::into)
+        self.find_many(WorkflowEdgeIden::WorkspaceId, workspace_id, None)
     }
 }
@@ -1,8 +1,7 @@
 use crate::db_context::DbContext;
 use crate::error::Result;
 use crate::models::{
-    WorkflowExecution, WorkflowExecutionIden,
-    WorkflowStepExecution, WorkflowStepExecutionIden,
+    WorkflowExecution, WorkflowExecutionIden, WorkflowNodeExecution, WorkflowNodeExecutionIden,
 };
 use crate::util::UpdateSource;
 
@@ -20,15 +19,27 @@ impl<'a> DbContext<'a> {
         Ok(executions)
     }
 
-    /// Get all step executions for a workflow execution
-    pub fn get_workflow_step_executions(
+    /// List the executions that sub-workflow nodes of `caller_execution_id` started, in the order
+    /// they ran
+    pub fn list_workflow_executions_by_caller(
+        &self,
+        caller_execution_id: &str,
+    ) -> Result<Vec<WorkflowExecution>> {
+        let mut executions: Vec<WorkflowExecution> =
+            self.find_many(WorkflowExecutionIden::CallerExecutionId, caller_execution_id, None)?;
+        executions.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        Ok(executions)
+    }
+
+    /// Get all node executions for a canvas workflow execution
+    pub fn get_workflow_node_executions(
         &self,
         execution_id: &str,
-    ) -> Result<Vec<WorkflowStepExecution>> {
-        let mut step_executions: Vec<WorkflowStepExecution> = self.find_many(WorkflowStepExecutionIden::WorkflowExecutionId, execution_id, None)?;
+    ) -> Result<Vec<WorkflowNodeExecution>> {
+        let mut node_executions: Vec<WorkflowNodeExecution> = self.find_many(WorkflowNodeExecutionIden::WorkflowExecutionId, execution_id, None)?;
         // Sort by created_at ascending (execution order)
-        step_executions.sort_by(|a, b| a.created_at.cmp(&b.created_at));
-        Ok(step_executions)
+        node_executions.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        Ok(node_executions)
     }
 
     /// Update execution (for state, elapsed, error changes)
@@ -39,4 +50,16 @@ impl<'a> DbContext<'a> {
     ) -> Result<WorkflowExecution> {
         self.upsert(execution, source)
     }
+
+    /// Delete an execution along with its node executions
+    pub fn delete_workflow_execution(
+        &self,
+        execution: &WorkflowExecution,
+        source: &UpdateSource,
+    ) -> Result<WorkflowExecution> {
+        for node_execution in self.get_workflow_node_executions(&execution.id)? {
+            self.delete(&node_execution, source)?;
+        }
+        self.delete(execution, source)
+    }
 }
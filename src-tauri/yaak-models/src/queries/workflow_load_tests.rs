@@ -0,0 +1,33 @@
+use crate::db_context::DbContext;
+use crate::error::Result;
+use crate::models::{WorkflowLoadTest, WorkflowLoadTestIden};
+use crate::util::UpdateSource;
+
+impl<'a> DbContext<'a> {
+    /// Get a single workflow load test by ID
+    pub fn get_workflow_load_test(&self, id: &str) -> Result<WorkflowLoadTest> {
+        self.find_one(WorkflowLoadTestIden::Id, id)
+    }
+
+    /// List load tests of a workflow (newest first)
+    pub fn list_workflow_load_tests(&self, workflow_id: &str) -> Result<Vec<WorkflowLoadTest>> {
+        let mut load_tests: Vec<WorkflowLoadTest> =
+            self.find_many(WorkflowLoadTestIden::WorkflowId, workflow_id, None)?;
+        load_tests.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        Ok(load_tests)
+    }
+
+    /// List load tests across all workflows of a workspace
+    pub fn list_workflow_load_tests_by_workspace(&self, workspace_id: &str) -> Result<Vec<WorkflowLoadTest>> {
+        self.find_many(WorkflowLoadTestIden::WorkspaceId, workspace_id, None)
+    }
+
+    /// Delete a load test and its summary
+    pub fn delete_workflow_load_test(
+        &self,
+        load_test: &WorkflowLoadTest,
+        source: &UpdateSource,
+    ) -> Result<WorkflowLoadTest> {
+        self.delete(load_test, source)
+    }
+}
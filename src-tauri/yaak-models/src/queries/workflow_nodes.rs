@@ -34,41 +34,6 @@ impl<'a> DbContext<'a> {
 
     /// List all workflow nodes for a workspace (across all workflows)
     pub fn list_workflow_nodes_by_workspace(&self, workspace_id: &str) -> Result<Vec<WorkflowNode>> {
-        use sea_query::{Expr, Query, SqliteQueryBuilder};
-        use sea_query_rusqlite::RusqliteBinder;
-        use crate::models::{WorkflowIden, UpsertModelInfo};
-
-        let (sql, params) = Query::select()
-            .columns([
-                (WorkflowNodeIden::Table, WorkflowNodeIden::Id),
-                (WorkflowNodeIden::Table, WorkflowNodeIden::Model),
-                (WorkflowNodeIden::Table, WorkflowNodeIden::WorkflowId),
-                (WorkflowNodeIden::Table, WorkflowNodeIden::NodeType),
-                (WorkflowNodeIden::Table, WorkflowNodeIden::NodeSubtype),
-                (WorkflowNodeIden::Table, WorkflowNodeIden::Name),
-                (WorkflowNodeIden::Table, WorkflowNodeIden::Description),
-                (WorkflowNodeIden::Table, WorkflowNodeIden::PositionX),
-                (WorkflowNodeIden::Table, WorkflowNodeIden::PositionY),
-                (WorkflowNodeIden::Table, WorkflowNodeIden::Width),
-                (WorkflowNodeIden::Table, WorkflowNodeIden::Height),
-                (WorkflowNodeIden::Table, WorkflowNodeIden::Config),
-                (WorkflowNodeIden::Table, WorkflowNodeIden::Enabled),
-                (WorkflowNodeIden::Table, WorkflowNodeIden::LegacyStepId),
-                (WorkflowNodeIden::Table, WorkflowNodeIden::CreatedAt),
-                (WorkflowNodeIden::Table, WorkflowNodeIden::UpdatedAt),
-                (WorkflowNodeIden::Table, WorkflowNodeIden::DeletedAt),
-            ])
-            .from(WorkflowNodeIden::Table)
-            .inner_join(
-                WorkflowIden::Table,
-                Expr::col((WorkflowNodeIden::Table, WorkflowNodeIden::WorkflowId))
-                    .equals((WorkflowIden::Table, WorkflowIden::Id))
-            )
-            .and_where(Expr::col((WorkflowIden::Table, WorkflowIden::WorkspaceId)).eq(workspace_id))
-            .build_rusqlite(SqliteQueryBuilder);
-
-        let mut stmt = self.conn.resolve().prepare(sql.as_str())?;
-        let rows = stmt.query_map(&*params.as_params(), |row| WorkflowNode::from_row(row))?;
-        rows.collect::<std::result::Result<Vec<_>, _>>().map_err(Into::into)
+        self.find_many(WorkflowNodeIden::WorkspaceId, workspace_id, None)
     }
 }
@@ -21,6 +21,7 @@ impl<'a> DbContext<'a> {
                 &Workspace {
                     name: "Yaak".to_string(),
                     setting_follow_redirects: true,
+                    setting_max_redirects: 10,
                     setting_validate_certificates: true,
                     ..Default::default()
                 },
@@ -595,10 +595,16 @@ impl PluginManager {
         &self,
         window: &WebviewWindow<R>,
     ) -> Result<Vec<(PluginHandle, GetHttpAuthenticationSummaryResponse)>> {
-        let window_context = PluginWindowContext::new(window);
+        self.http_authentication_summaries(&PluginWindowContext::new(window)).await
+    }
+
+    async fn http_authentication_summaries(
+        &self,
+        window_context: &PluginWindowContext,
+    ) -> Result<Vec<(PluginHandle, GetHttpAuthenticationSummaryResponse)>> {
         let reply_events = self
             .send_and_wait(
-                &window_context,
+                window_context,
                 &InternalEventPayload::GetHttpAuthenticationSummaryRequest(EmptyPayload {}),
             )
             .await?;
@@ -714,9 +720,9 @@ impl PluginManager {
         Ok(())
     }
 
-    pub async fn call_http_authentication<R: Runtime>(
+    pub async fn call_http_authentication(
         &self,
-        window: &WebviewWindow<R>,
+        window_context: &PluginWindowContext,
         auth_name: &str,
         req: CallHttpAuthenticationRequest,
     ) -> Result<CallHttpAuthenticationResponse> {
@@ -734,7 +740,7 @@ impl PluginManager {
             });
         }
 
-        let handlers = self.get_http_authentication_summaries(window).await?;
+        let handlers = self.http_authentication_summaries(window_context).await?;
         let (plugin, _) = handlers
             .iter()
             .find(|(_, a)| a.name == auth_name)
@@ -742,7 +748,7 @@ impl PluginManager {
 
         let event = self
             .send_to_plugin_and_wait(
-                &PluginWindowContext::new(window),
+                window_context,
                 &plugin,
                 &InternalEventPayload::CallHttpAuthenticationRequest(req),
             )
@@ -1,4 +1,7 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { JsonValue } from "./serde_json/JsonValue";
+
+export type EdgeType = "sequential" | "conditional" | "parallel" | "loop";
 
 export type Environment = { model: "environment", id: string, workspaceId: string, createdAt: string, updatedAt: string, name: string, public: boolean, parentModel: string, parentId: string | null, variables: Array<EnvironmentVariable>, color: string | null, sortPriority: number, };
 
@@ -14,10 +17,22 @@ export type HttpRequestHeader = { enabled?: boolean, name: string, value: string
 
 export type HttpUrlParameter = { enabled?: boolean, name: string, value: string, id?: string, };
 
-export type SyncModel = { "type": "workspace" } & Workspace | { "type": "environment" } & Environment | { "type": "folder" } & Folder | { "type": "http_request" } & HttpRequest | { "type": "grpc_request" } & GrpcRequest | { "type": "websocket_request" } & WebsocketRequest;
+export type NodeType = "trigger" | "action" | "logic";
+
+export type SyncModel = { "type": "workspace" } & Workspace | { "type": "environment" } & Environment | { "type": "folder" } & Folder | { "type": "http_request" } & HttpRequest | { "type": "grpc_request" } & GrpcRequest | { "type": "websocket_request" } & WebsocketRequest | { "type": "workflow" } & Workflow | { "type": "workflow_node" } & WorkflowNode | { "type": "workflow_edge" } & WorkflowEdge;
 
 export type SyncState = { model: "sync_state", id: string, workspaceId: string, createdAt: string, updatedAt: string, flushedAt: string, modelId: string, checksum: string, relPath: string, syncDir: string, };
 
 export type WebsocketRequest = { model: "websocket_request", id: string, createdAt: string, updatedAt: string, workspaceId: string, folderId: string | null, authentication: Record<string, any>, authenticationType: string | null, description: string, headers: Array<HttpRequestHeader>, message: string, name: string, sortPriority: number, url: string, urlParameters: Array<HttpUrlParameter>, };
 
+export type Workflow = { model: "workflow", id: string, createdAt: string, updatedAt: string, deletedAt: string | null, workspaceId: string, name: string, description: string | null, environmentId: string | null, sortPriority: number, 
+/**
+ * CSV or JSON file to run the workflow once per row of, with the row's fields as variables
+ */
+datasetPath: string | null, };
+
+export type WorkflowEdge = { model: "workflow_edge", id: string, createdAt: string, updatedAt: string, deletedAt: string | null, workspaceId: string, workflowId: string, sourceNodeId: string, targetNodeId: string, sourceAnchor: string, targetAnchor: string, edgeType: EdgeType, };
+
+export type WorkflowNode = { model: "workflow_node", id: string, createdAt: string, updatedAt: string, deletedAt: string | null, workspaceId: string, workflowId: string, nodeType: NodeType, nodeSubtype: string, positionX: number, positionY: number, width: number, height: number, name: string, description: string | null, config: JsonValue, enabled: boolean, legacyStepId: string | null, };
+
 export type Workspace = { model: "workspace", id: string, createdAt: string, updatedAt: string, authentication: Record<string, any>, authenticationType: string | null, description: string, headers: Array<HttpRequestHeader>, name: string, encryptionKeyChallenge: string | null, settingValidateCertificates: boolean, settingFollowRedirects: boolean, settingRequestTimeout: number, };
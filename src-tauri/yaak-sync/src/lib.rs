@@ -11,6 +11,8 @@ pub mod models;
 mod sync;
 mod watch;
 
+pub use sync::import_dir;
+
 pub fn init<R: Runtime>() -> TauriPlugin<R> {
     Builder::new("yaak-sync")
         .invoke_handler(generate_handler![calculate, calculate_fs, apply, watch])
@@ -9,7 +9,8 @@ use std::fs;
 use std::path::Path;
 use ts_rs::TS;
 use yaak_models::models::{
-    AnyModel, Environment, Folder, GrpcRequest, HttpRequest, WebsocketRequest, Workspace,
+    AnyModel, Environment, Folder, GrpcRequest, HttpRequest, WebsocketRequest, Workflow,
+    WorkflowEdge, WorkflowNode, Workspace,
 };
 
 #[derive(Debug, Clone, PartialEq, Serialize, TS)]
@@ -22,6 +23,9 @@ pub enum SyncModel {
     HttpRequest(HttpRequest),
     GrpcRequest(GrpcRequest),
     WebsocketRequest(WebsocketRequest),
+    Workflow(Workflow),
+    WorkflowNode(WorkflowNode),
+    WorkflowEdge(WorkflowEdge),
 }
 
 impl<'de> Deserialize<'de> for SyncModel {
@@ -68,6 +72,18 @@ impl<'de> Deserialize<'de> for SyncModel {
                 let x: WebsocketRequest = spte::deserialize(v).map_err(serde::de::Error::custom)?;
                 Ok(SyncModel::WebsocketRequest(x))
             }
+            "workflow" => {
+                let x: Workflow = spte::deserialize(v).map_err(serde::de::Error::custom)?;
+                Ok(SyncModel::Workflow(x))
+            }
+            "workflow_node" => {
+                let x: WorkflowNode = spte::deserialize(v).map_err(serde::de::Error::custom)?;
+                Ok(SyncModel::WorkflowNode(x))
+            }
+            "workflow_edge" => {
+                let x: WorkflowEdge = spte::deserialize(v).map_err(serde::de::Error::custom)?;
+                Ok(SyncModel::WorkflowEdge(x))
+            }
             other => Err(serde::de::Error::unknown_variant(
                 other,
                 &[
@@ -77,6 +93,9 @@ impl<'de> Deserialize<'de> for SyncModel {
                     "http_request",
                     "grpc_request",
                     "websocket_request",
+                    "workflow",
+                    "workflow_node",
+                    "workflow_edge",
                 ],
             )),
         }
@@ -164,6 +183,9 @@ impl SyncModel {
             SyncModel::HttpRequest(m) => m.id,
             SyncModel::GrpcRequest(m) => m.id,
             SyncModel::WebsocketRequest(m) => m.id,
+            SyncModel::Workflow(m) => m.id,
+            SyncModel::WorkflowNode(m) => m.id,
+            SyncModel::WorkflowEdge(m) => m.id,
         }
     }
 
@@ -175,6 +197,9 @@ impl SyncModel {
             SyncModel::HttpRequest(m) => m.workspace_id,
             SyncModel::GrpcRequest(m) => m.workspace_id,
             SyncModel::WebsocketRequest(m) => m.workspace_id,
+            SyncModel::Workflow(m) => m.workspace_id,
+            SyncModel::WorkflowNode(m) => m.workspace_id,
+            SyncModel::WorkflowEdge(m) => m.workspace_id,
         }
     }
 
@@ -186,6 +211,9 @@ impl SyncModel {
             SyncModel::HttpRequest(m) => m.updated_at,
             SyncModel::GrpcRequest(m) => m.updated_at,
             SyncModel::WebsocketRequest(m) => m.updated_at,
+            SyncModel::Workflow(m) => m.updated_at,
+            SyncModel::WorkflowNode(m) => m.updated_at,
+            SyncModel::WorkflowEdge(m) => m.updated_at,
         }
     }
 }
@@ -201,6 +229,9 @@ impl TryFrom<AnyModel> for SyncModel {
             AnyModel::HttpRequest(m) => SyncModel::HttpRequest(m),
             AnyModel::WebsocketRequest(m) => SyncModel::WebsocketRequest(m),
             AnyModel::Workspace(m) => SyncModel::Workspace(m),
+            AnyModel::Workflow(m) => SyncModel::Workflow(m),
+            AnyModel::WorkflowNode(m) => SyncModel::WorkflowNode(m),
+            AnyModel::WorkflowEdge(m) => SyncModel::WorkflowEdge(m),
 
             // Non-sync models
             AnyModel::CookieJar(m) => return Err(UnknownModel(m.model)),
@@ -215,14 +246,10 @@ impl TryFrom<AnyModel> for SyncModel {
             AnyModel::WebsocketEvent(m) => return Err(UnknownModel(m.model)),
             AnyModel::WorkspaceMeta(m) => return Err(UnknownModel(m.model)),
             AnyModel::SyncState(m) => return Err(UnknownModel(m.model)),
-            AnyModel::Workflow(m) => return Err(UnknownModel(m.model)),
-            AnyModel::WorkflowStep(m) => return Err(UnknownModel(m.model)),
             AnyModel::WorkflowExecution(m) => return Err(UnknownModel(m.model)),
-            AnyModel::WorkflowStepExecution(m) => return Err(UnknownModel(m.model)),
-            AnyModel::WorkflowNode(m) => return Err(UnknownModel(m.model)),
-            AnyModel::WorkflowEdge(m) => return Err(UnknownModel(m.model)),
             AnyModel::WorkflowViewport(m) => return Err(UnknownModel(m.model)),
             AnyModel::WorkflowNodeExecution(m) => return Err(UnknownModel(m.model)),
+            AnyModel::WorkflowLoadTest(m) => return Err(UnknownModel(m.model)),
         };
         Ok(m)
     }
@@ -306,4 +333,62 @@ color: null
 
         Ok(())
     }
+
+    #[test]
+    fn deserializes_workflow_models_via_syncmodel() -> Result<()> {
+        let raw = r#"
+type: workflow_node
+model: workflow_node
+id: wn_a1b2c3d4e5
+workspaceId: wk_kfSI3JDHd7
+workflowId: wf_Xq8Rz2Lm4N
+createdAt: 2025-11-20T10:00:00
+updatedAt: 2025-11-20T10:05:00
+deletedAt: null
+nodeType: action
+nodeSubtype: http_request
+positionX: 300.0
+positionY: 200.0
+width: 200.0
+height: 100.0
+name: Get User
+config:
+  request_id: rq_123
+enabled: true
+"#;
+        let m: SyncModel = serde_yaml::from_str(raw)?;
+        assert_eq!(m.workspace_id(), "wk_kfSI3JDHd7");
+        match m {
+            SyncModel::WorkflowNode(node) => {
+                assert_eq!(node.workflow_id, "wf_Xq8Rz2Lm4N");
+                assert_eq!(node.config["request_id"], "rq_123");
+            }
+            _ => panic!("expected workflow node"),
+        }
+
+        let raw = r#"
+type: workflow_edge
+model: workflow_edge
+id: we_f6g7h8i9j0
+workspaceId: wk_kfSI3JDHd7
+workflowId: wf_Xq8Rz2Lm4N
+createdAt: 2025-11-20T10:00:00
+updatedAt: 2025-11-20T10:05:00
+sourceNodeId: wn_start
+targetNodeId: wn_a1b2c3d4e5
+sourceAnchor: output
+targetAnchor: input
+edgeType: sequential
+"#;
+        let m: SyncModel = serde_yaml::from_str(raw)?;
+        match m {
+            SyncModel::WorkflowEdge(edge) => {
+                assert_eq!(edge.source_node_id, "wn_start");
+                assert_eq!(edge.target_node_id, "wn_a1b2c3d4e5");
+            }
+            _ => panic!("expected workflow edge"),
+        }
+
+        Ok(())
+    }
 }
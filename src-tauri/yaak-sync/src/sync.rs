@@ -11,7 +11,7 @@ use std::io::Write;
 use std::path::{Path, PathBuf};
 use tauri::{AppHandle, Runtime};
 use ts_rs::TS;
-use yaak_models::models::{SyncState, WorkspaceMeta};
+use yaak_models::models::{SyncState, WorkflowEdge, WorkflowNode, WorkspaceMeta};
 use yaak_models::query_manager::QueryManagerExt;
 use yaak_models::util::{UpdateSource, get_workspace_export_resources};
 
@@ -212,6 +212,34 @@ pub(crate) fn get_fs_candidates(dir: &Path) -> Result<Vec<FsCandidate>> {
     Ok(candidates)
 }
 
+/// Load every model in a sync directory into the database, without ever writing to the directory.
+/// Used to run workflows straight from a checked-out repo. Returns the IDs of the workspaces found.
+pub fn import_dir<R: Runtime>(app_handle: &AppHandle<R>, sync_dir: &Path) -> Result<Vec<String>> {
+    let fs_candidates = get_fs_candidates(sync_dir)?;
+
+    let mut workspace_ids =
+        fs_candidates.iter().map(|c| c.model.workspace_id()).collect::<Vec<String>>();
+    workspace_ids.sort();
+    workspace_ids.dedup();
+
+    for workspace_id in &workspace_ids {
+        let db_candidates = get_db_candidates(app_handle, workspace_id, sync_dir)?;
+        let fs_candidates = fs_candidates
+            .iter()
+            .filter(|c| &c.model.workspace_id() == workspace_id)
+            .cloned()
+            .collect::<Vec<FsCandidate>>();
+        let sync_ops = compute_sync_ops(db_candidates, fs_candidates)
+            .into_iter()
+            .filter(|op| matches!(op, SyncOp::DbCreate { .. } | SyncOp::DbUpdate { .. }))
+            .collect::<Vec<SyncOp>>();
+        let sync_state_ops = apply_sync_ops(app_handle, workspace_id, sync_dir, sync_ops)?;
+        apply_sync_state_ops(app_handle, workspace_id, sync_dir, sync_state_ops)?;
+    }
+
+    Ok(workspace_ids)
+}
+
 pub(crate) fn compute_sync_ops(
     db_candidates: Vec<DbCandidate>,
     fs_candidates: Vec<FsCandidate>,
@@ -350,6 +378,22 @@ fn workspace_models<R: Runtime>(
         sync_models.push(SyncModel::WebsocketRequest(m));
     }
 
+    // Nodes and edges are soft-deleted, so leave those out to have them removed from the sync dir
+    let db = app_handle.db();
+    for m in db.list_workflows_by_workspace(workspace_id)? {
+        sync_models.push(SyncModel::Workflow(m));
+    }
+    for m in db.list_workflow_nodes_by_workspace(workspace_id)? {
+        if m.deleted_at.is_none() {
+            sync_models.push(SyncModel::WorkflowNode(m));
+        }
+    }
+    for m in db.list_workflow_edges_by_workspace(workspace_id)? {
+        if m.deleted_at.is_none() {
+            sync_models.push(SyncModel::WorkflowEdge(m));
+        }
+    }
+
     Ok(sync_models)
 }
 
@@ -375,6 +419,9 @@ pub(crate) fn apply_sync_ops<R: Runtime>(
     let mut http_requests_to_upsert = Vec::new();
     let mut grpc_requests_to_upsert = Vec::new();
     let mut websocket_requests_to_upsert = Vec::new();
+    let mut workflows_to_upsert = Vec::new();
+    let mut workflow_nodes_to_upsert = Vec::new();
+    let mut workflow_edges_to_upsert = Vec::new();
 
     for op in sync_ops {
         // Only apply things if workspace ID matches
@@ -437,6 +484,9 @@ pub(crate) fn apply_sync_ops<R: Runtime>(
                     SyncModel::HttpRequest(m) => http_requests_to_upsert.push(m),
                     SyncModel::WebsocketRequest(m) => websocket_requests_to_upsert.push(m),
                     SyncModel::Workspace(m) => workspaces_to_upsert.push(m),
+                    SyncModel::Workflow(m) => workflows_to_upsert.push(m),
+                    SyncModel::WorkflowNode(m) => workflow_nodes_to_upsert.push(m),
+                    SyncModel::WorkflowEdge(m) => workflow_edges_to_upsert.push(m),
                 };
                 SyncStateOp::Create {
                     model_id,
@@ -454,6 +504,9 @@ pub(crate) fn apply_sync_ops<R: Runtime>(
                     SyncModel::HttpRequest(m) => http_requests_to_upsert.push(m),
                     SyncModel::WebsocketRequest(m) => websocket_requests_to_upsert.push(m),
                     SyncModel::Workspace(m) => workspaces_to_upsert.push(m),
+                    SyncModel::Workflow(m) => workflows_to_upsert.push(m),
+                    SyncModel::WorkflowNode(m) => workflow_nodes_to_upsert.push(m),
+                    SyncModel::WorkflowEdge(m) => workflow_edges_to_upsert.push(m),
                 }
                 SyncStateOp::Update {
                     state: state.to_owned(),
@@ -482,6 +535,18 @@ pub(crate) fn apply_sync_ops<R: Runtime>(
             &UpdateSource::Sync,
         )?;
 
+        // Workflows come after the workspaces they belong to, and nodes before the edges
+        // connecting them
+        for m in workflows_to_upsert {
+            tx.upsert(&m, &UpdateSource::Sync)?;
+        }
+        for m in workflow_nodes_to_upsert {
+            tx.upsert(&m, &UpdateSource::Sync)?;
+        }
+        for m in workflow_edges_to_upsert {
+            tx.upsert(&m, &UpdateSource::Sync)?;
+        }
+
         // Ensure we create WorkspaceMeta models for each new workspace, with the appropriate sync dir
         let sync_dir_string = sync_dir.to_string_lossy().to_string();
         for workspace in upserted_models.workspaces {
@@ -608,6 +673,19 @@ fn delete_model<R: Runtime>(app_handle: &AppHandle<R>, model: &SyncModel) -> Res
         SyncModel::WebsocketRequest(m) => {
             db.delete_websocket_request(&m, &UpdateSource::Sync)?;
         }
+        SyncModel::Workflow(m) => {
+            db.delete_workflow(&m, &UpdateSource::Sync)?;
+        }
+        // Soft-delete nodes and edges like the canvas does, so past executions can still
+        // reference them
+        SyncModel::WorkflowNode(m) => {
+            let node = WorkflowNode { deleted_at: Some(Utc::now().naive_utc()), ..m.to_owned() };
+            db.upsert_workflow_node(&node, &UpdateSource::Sync)?;
+        }
+        SyncModel::WorkflowEdge(m) => {
+            let edge = WorkflowEdge { deleted_at: Some(Utc::now().naive_utc()), ..m.to_owned() };
+            db.upsert_workflow_edge(&edge, &UpdateSource::Sync)?;
+        }
     };
     Ok(())
 }
@@ -23,6 +23,12 @@ pub enum Error {
     #[error("Workflow Error: Header '{0}' not found in step {1} response")]
     WorkflowHeaderNotFound(String, usize),
 
+    #[error("Workflow Error: Node '{0}' has not been executed yet")]
+    WorkflowNodeNotExecuted(String),
+
+    #[error("Workflow Error: Field '{0}' not found in node '{1}' output")]
+    WorkflowNodeFieldNotFound(String, String),
+
     #[error("Workflow Error: Invalid syntax - {0}")]
     WorkflowInvalidSyntax(String),
 }
@@ -684,6 +684,7 @@ mod render_json_value_raw_tests {
 #[derive(Debug, Clone)]
 pub struct WorkflowContext {
     step_responses: Vec<StepResponse>,
+    node_outputs: HashMap<String, serde_json::Value>,
     loop_context: Option<LoopContext>,
     conditional_branch: Option<String>,
 }
@@ -710,6 +711,7 @@ impl WorkflowContext {
     pub fn new() -> Self {
         Self {
             step_responses: Vec::new(),
+            node_outputs: HashMap::new(),
             loop_context: None,
             conditional_branch: None,
         }
@@ -723,6 +725,20 @@ impl WorkflowContext {
         self.step_responses.get(index)
     }
 
+    /// Register a canvas node output so it can be referenced by node id or name
+    pub fn add_node_output(&mut self, node_id: &str, node_name: &str, output: serde_json::Value) {
+        if !node_name.is_empty() {
+            self.node_outputs.insert(node_name.to_string(), output.clone());
+            self.node_outputs.insert(node_name_to_ident(node_name), output.clone());
+        }
+        // Insert the ID last so it always wins over a clashing name
+        self.node_outputs.insert(node_id.to_string(), output);
+    }
+
+    pub fn get_node_output(&self, key: &str) -> Option<&serde_json::Value> {
+        self.node_outputs.get(key)
+    }
+
     pub fn set_loop_context(&mut self, context: LoopContext) {
         self.loop_context = Some(context);
     }
@@ -862,6 +878,9 @@ async fn render_value_with_workflow<T: TemplateCallback>(
 ) -> Result<String> {
     // Check if it's a workflow variable
     if let Some(wf_ctx) = workflow_ctx {
+        if name.starts_with("workflow.node.") {
+            return resolve_node_variable(name, wf_ctx);
+        }
         if name.starts_with("workflow.") {
             return resolve_workflow_variable(name, wf_ctx);
         }
@@ -1001,6 +1020,44 @@ fn extract_json_field(json: &serde_json::Value, path: &str, step_index: usize) -
     Ok(result)
 }
 
+/// Convert a node name into a form usable inside a template tag. Anything that isn't valid
+/// in an identifier becomes an underscore, so "Get Token" can be referenced as `Get_Token`.
+pub fn node_name_to_ident(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_alphanumeric() || c == '_' || c == '-' { c } else { '_' })
+        .collect()
+}
+
+/// Resolve canvas node output variables
+/// Syntax: workflow.node.<id or name>.field.path
+/// Examples:
+///   - workflow.node.wn_abc123.status
+///   - workflow.node.Login.body.token
+///   - workflow.node.Login.headers.content-type
+///   - workflow.node.Get_Users.body.0.email
+fn resolve_node_variable(path: &str, workflow_ctx: &WorkflowContext) -> Result<String> {
+    let rest = path.strip_prefix("workflow.node.").unwrap_or_default();
+    let (key, field_path) = match rest.split_once('.') {
+        Some((key, field_path)) => (key, field_path),
+        None => (rest, ""),
+    };
+
+    if key.is_empty() {
+        return Err(crate::error::Error::WorkflowInvalidSyntax(format!(
+            "Invalid node variable syntax: {}",
+            path
+        )));
+    }
+
+    let output = workflow_ctx
+        .get_node_output(key)
+        .ok_or_else(|| crate::error::Error::WorkflowNodeNotExecuted(key.to_string()))?;
+
+    extract_json_field_simple(output, field_path).map_err(|_| {
+        crate::error::Error::WorkflowNodeFieldNotFound(field_path.to_string(), key.to_string())
+    })
+}
+
 /// Resolve loop template variables
 /// Syntax: loop.index, loop.total, loop.item.field
 /// Examples:
@@ -238,4 +238,38 @@ mod tests {
         assert!(result.contains("processing"));
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_node_output_by_id_and_name() -> Result<()> {
+        let mut ctx = WorkflowContext::new();
+        ctx.add_node_output(
+            "wn_abc123",
+            "Get Token",
+            json!({"status": 200, "body": {"token": "secret", "items": [{"id": 7}]}}),
+        );
+
+        let vars = HashMap::new();
+        let opt = RenderOptions {
+            error_behavior: RenderErrorBehavior::Throw,
+        };
+
+        let template = "${[ workflow.node.wn_abc123.status ]} ${[ workflow.node.Get_Token.body.token ]} ${[ workflow.node.Get_Token.body.items.0.id ]}";
+        let result = parse_and_render_with_workflow(template, &vars, Some(&ctx), &EmptyCB {}, &opt).await?;
+        assert_eq!(result, "200 secret 7");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_node_output_not_executed() -> Result<()> {
+        let ctx = WorkflowContext::new();
+
+        let vars = HashMap::new();
+        let opt = RenderOptions {
+            error_behavior: RenderErrorBehavior::Throw,
+        };
+
+        let result = parse_and_render_with_workflow("${[ workflow.node.Missing.status ]}", &vars, Some(&ctx), &EmptyCB {}, &opt).await;
+        assert_eq!(result, Err(crate::error::Error::WorkflowNodeNotExecuted("Missing".to_string())));
+        Ok(())
+    }
 }
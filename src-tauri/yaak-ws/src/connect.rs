@@ -1,4 +1,6 @@
+use crate::error::Error::GenericError;
 use log::info;
+use std::net::SocketAddr;
 use std::sync::Arc;
 use tauri::http::HeaderMap;
 use tokio::net::TcpStream;
@@ -6,9 +8,9 @@ use tokio_tungstenite::tungstenite::client::IntoClientRequest;
 use tokio_tungstenite::tungstenite::handshake::client::Response;
 use tokio_tungstenite::tungstenite::http::HeaderValue;
 use tokio_tungstenite::tungstenite::protocol::WebSocketConfig;
-use tokio_tungstenite::{
-    Connector, MaybeTlsStream, WebSocketStream, connect_async_tls_with_config,
-};
+use tokio_tungstenite::{Connector, MaybeTlsStream, WebSocketStream, client_async_tls_with_config};
+use yaak_http::dns::DnsResolver;
+use yaak_http::tls::{TlsOptions, default_port};
 
 // Enabling ALPN breaks websocket requests
 const WITH_ALPN: bool = false;
@@ -16,12 +18,27 @@ const WITH_ALPN: bool = false;
 pub(crate) async fn ws_connect(
     url: &str,
     headers: HeaderMap<HeaderValue>,
-    validate_certificates: bool,
+    tls_options: &TlsOptions,
+    dns_resolver: &DnsResolver,
 ) -> crate::error::Result<(WebSocketStream<MaybeTlsStream<TcpStream>>, Response)> {
     info!("Connecting to WS {url}");
-    let tls_config = yaak_http::tls::get_config(validate_certificates, WITH_ALPN);
 
     let mut req = url.into_client_request()?;
+    let host = req.uri().host().unwrap_or_default();
+    let port = req.uri().port_u16().or_else(|| req.uri().scheme_str().and_then(default_port));
+    let tls_config = yaak_http::tls::get_config(tls_options, host, port, WITH_ALPN)?;
+
+    // Connect ourselves so the host is resolved with the workspace's DNS settings
+    let addrs = dns_resolver
+        .lookup(host)
+        .await?
+        .into_iter()
+        .map(|ip| SocketAddr::new(ip, port.unwrap_or_default()))
+        .collect::<Vec<_>>();
+    let stream = TcpStream::connect(addrs.as_slice())
+        .await
+        .map_err(|e| GenericError(format!("Failed to connect to {host}: {e}")))?;
+
     let req_headers = req.headers_mut();
     for (name, value) in headers {
         if let Some(name) = name {
@@ -29,10 +46,10 @@ pub(crate) async fn ws_connect(
         }
     }
 
-    let (stream, response) = connect_async_tls_with_config(
+    let (stream, response) = client_async_tls_with_config(
         req,
+        stream,
         Some(WebSocketConfig::default()),
-        false,
         Some(Connector::Rustls(Arc::new(tls_config))),
     )
     .await?;
@@ -0,0 +1,416 @@
+use crate::error::{Error, Result};
+use crate::manager::WebsocketManager;
+use crate::render::render_websocket_request;
+use crate::resolve::resolve_websocket_request;
+use log::{debug, info};
+use std::str::FromStr;
+use tauri::http::{HeaderMap, HeaderName};
+use tauri::{AppHandle, Manager, Runtime, Url};
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::tungstenite::http::HeaderValue;
+use yaak_http::apply_path_placeholders;
+use yaak_http::dns::DnsResolver;
+use yaak_http::tls::TlsOptions;
+use yaak_models::models::{
+    HttpResponseHeader, WebsocketConnection, WebsocketConnectionState, WebsocketEvent,
+    WebsocketEventType, WebsocketRequest,
+};
+use yaak_models::query_manager::QueryManagerExt;
+use yaak_models::util::UpdateSource;
+use yaak_plugins::events::{
+    CallHttpAuthenticationRequest, HttpHeader, PluginWindowContext, RenderPurpose,
+};
+use yaak_plugins::manager::PluginManager;
+use yaak_plugins::template_callback::PluginTemplateCallback;
+use yaak_templates::{RenderErrorBehavior, RenderOptions};
+
+/// What to open a connection with
+pub struct ConnectParams<'a> {
+    pub window_context: &'a PluginWindowContext,
+    pub update_source: &'a UpdateSource,
+    pub unrendered_request: &'a WebsocketRequest,
+    pub environment_id: Option<&'a str>,
+    pub cookie_jar_id: Option<&'a str>,
+    /// Every server event is also forwarded here after being stored
+    pub events_tx: Option<mpsc::Sender<WebsocketEvent>>,
+}
+
+/// Open a connection for a request, persisting the connection and all of its events.
+///
+/// No window is required, which lets callers like workflows connect in the background. The request
+/// must be saved though, since the connection and its events reference it.
+pub async fn connect_websocket<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    ws_manager: &mut WebsocketManager,
+    params: ConnectParams<'_>,
+) -> Result<WebsocketConnection> {
+    let ConnectParams {
+        window_context,
+        update_source,
+        unrendered_request,
+        environment_id,
+        cookie_jar_id,
+        events_tx,
+    } = params;
+    let app_handle = app_handle.clone();
+    let plugin_manager = app_handle.state::<PluginManager>();
+    let environment_chain = app_handle.db().resolve_environments(
+        &unrendered_request.workspace_id,
+        unrendered_request.folder_id.as_deref(),
+        environment_id,
+    )?;
+    let workspace = app_handle.db().get_workspace(&unrendered_request.workspace_id)?;
+    let (resolved_request, auth_context_id) =
+        resolve_websocket_request(&app_handle, unrendered_request)?;
+    let request = render_websocket_request(
+        &resolved_request,
+        environment_chain,
+        &PluginTemplateCallback::new(&app_handle, window_context, RenderPurpose::Send),
+        &RenderOptions {
+            error_behavior: RenderErrorBehavior::Throw,
+        },
+    )
+    .await?;
+
+    let connection = app_handle.db().upsert_websocket_connection(
+        &WebsocketConnection {
+            workspace_id: request.workspace_id.clone(),
+            request_id: unrendered_request.id.clone(),
+            ..Default::default()
+        },
+        update_source,
+    )?;
+
+    let (mut url, url_parameters) = apply_path_placeholders(&request.url, request.url_parameters);
+    if !url.starts_with("ws://") && !url.starts_with("wss://") {
+        url.insert_str(0, "ws://");
+    }
+
+    // Add URL parameters to URL
+    let mut url = match Url::parse(&url) {
+        Ok(url) => url,
+        Err(e) => {
+            return Ok(app_handle.db().upsert_websocket_connection(
+                &WebsocketConnection {
+                    error: Some(format!("Failed to parse URL {}", e.to_string())),
+                    state: WebsocketConnectionState::Closed,
+                    ..connection
+                },
+                update_source,
+            )?);
+        }
+    };
+
+    let mut headers = HeaderMap::new();
+
+    for h in request.headers.clone() {
+        if h.name.is_empty() && h.value.is_empty() {
+            continue;
+        }
+
+        if !h.enabled {
+            continue;
+        }
+
+        match parse_header(&h.name, &h.value) {
+            Ok((name, value)) => {
+                headers.insert(name, value);
+            }
+            Err(e) => {
+                return Ok(app_handle.db().upsert_websocket_connection(
+                    &WebsocketConnection {
+                        error: Some(e.to_string()),
+                        state: WebsocketConnectionState::Closed,
+                        ..connection
+                    },
+                    update_source,
+                )?);
+            }
+        }
+    }
+
+    match request.authentication_type {
+        None => {
+            // No authentication found. Not even inherited
+        }
+        Some(authentication_type) if authentication_type == "none" => {
+            // Explicitly no authentication
+        }
+        Some(authentication_type) => {
+            let auth = request.authentication.clone();
+            let plugin_req = CallHttpAuthenticationRequest {
+                context_id: format!("{:x}", md5::compute(auth_context_id)),
+                values: serde_json::from_value(serde_json::to_value(&auth).unwrap()).unwrap(),
+                method: "POST".to_string(),
+                url: request.url.clone(),
+                headers: request
+                    .headers
+                    .clone()
+                    .into_iter()
+                    .map(|h| HttpHeader {
+                        name: h.name,
+                        value: h.value,
+                    })
+                    .collect(),
+            };
+            let plugin_result = plugin_manager
+                .call_http_authentication(window_context, &authentication_type, plugin_req)
+                .await?;
+            for header in plugin_result.set_headers.unwrap_or_default() {
+                match (HeaderName::from_str(&header.name), HeaderValue::from_str(&header.value)) {
+                    (Ok(name), Ok(value)) => {
+                        headers.insert(name, value);
+                    }
+                    _ => continue,
+                };
+            }
+            if let Some(params) = plugin_result.set_query_parameters {
+                let mut query_pairs = url.query_pairs_mut();
+                for p in params {
+                    query_pairs.append_pair(&p.name, &p.value);
+                }
+            }
+        }
+    }
+
+    // Add cookies to WS HTTP Upgrade
+    if let Some(id) = cookie_jar_id {
+        let cookie_jar = app_handle.db().get_cookie_jar(&id)?;
+
+        let cookies = cookie_jar
+            .cookies
+            .iter()
+            .filter_map(|cookie| {
+                // HACK: same as in src-tauri/src/http_request.rs
+                let json_cookie = serde_json::to_value(cookie).ok()?;
+                match serde_json::from_value(json_cookie) {
+                    Ok(cookie) => Some(Ok(cookie)),
+                    Err(_e) => None,
+                }
+            })
+            .collect::<Vec<Result<_>>>();
+
+        let store = reqwest_cookie_store::CookieStore::from_cookies(cookies, true)?;
+
+        // Convert WS URL -> HTTP URL bc reqwest_cookie_store's `get_request_values`
+        // strictly matches based on Path/HttpOnly/Secure attributes even though WS upgrades are HTTP requests
+        let http_url = convert_ws_url_to_http(&url);
+        let pairs: Vec<_> = store.get_request_values(&http_url).collect();
+        debug!("Inserting {} cookies into WS upgrade to {}", pairs.len(), url);
+
+        let cookie_header_value = pairs
+            .into_iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect::<Vec<_>>()
+            .join("; ");
+
+        if !cookie_header_value.is_empty() {
+            headers.insert(
+                HeaderName::from_static("cookie"),
+                HeaderValue::from_str(&cookie_header_value).unwrap(),
+            );
+        }
+    }
+
+    let (receive_tx, mut receive_rx) = mpsc::channel::<Message>(128);
+
+    {
+        let valid_query_pairs = url_parameters
+            .into_iter()
+            .filter(|p| p.enabled && !p.name.is_empty())
+            .collect::<Vec<_>>();
+        // NOTE: Only mutate query pairs if there are any, or it will append an empty `?` to the URL
+        if !valid_query_pairs.is_empty() {
+            let mut query_pairs = url.query_pairs_mut();
+            for p in valid_query_pairs {
+                query_pairs.append_pair(p.name.as_str(), p.value.as_str());
+            }
+        }
+    }
+
+    let response = match ws_manager
+        .connect(
+            &connection.id,
+            url.as_str(),
+            headers,
+            receive_tx,
+            &TlsOptions::from(&workspace),
+            &DnsResolver::from(&workspace),
+        )
+        .await
+    {
+        Ok(r) => r,
+        Err(e) => {
+            return Ok(app_handle.db().upsert_websocket_connection(
+                &WebsocketConnection {
+                    error: Some(e.to_string()),
+                    state: WebsocketConnectionState::Closed,
+                    ..connection
+                },
+                update_source,
+            )?);
+        }
+    };
+
+    app_handle.db().upsert_websocket_event(
+        &WebsocketEvent {
+            connection_id: connection.id.clone(),
+            request_id: request.id.clone(),
+            workspace_id: connection.workspace_id.clone(),
+            is_server: false,
+            message_type: WebsocketEventType::Open,
+            ..Default::default()
+        },
+        update_source,
+    )?;
+
+    let response_headers = response
+        .headers()
+        .into_iter()
+        .map(|(name, value)| HttpResponseHeader {
+            name: name.to_string(),
+            value: value.to_str().unwrap().to_string(),
+        })
+        .collect::<Vec<HttpResponseHeader>>();
+
+    let connection = app_handle.db().upsert_websocket_connection(
+        &WebsocketConnection {
+            state: WebsocketConnectionState::Connected,
+            headers: response_headers,
+            status: response.status().as_u16() as i32,
+            url: request.url.clone(),
+            ..connection
+        },
+        update_source,
+    )?;
+
+    {
+        let connection_id = connection.id.clone();
+        let request_id = request.id.to_string();
+        let workspace_id = request.workspace_id.clone();
+        let connection = connection.clone();
+        let update_source = update_source.clone();
+        let mut has_written_close = false;
+        tokio::spawn(async move {
+            while let Some(message) = receive_rx.recv().await {
+                if let Message::Close(_) = message {
+                    has_written_close = true;
+                }
+
+                let event = app_handle
+                    .db()
+                    .upsert_websocket_event(
+                        &WebsocketEvent {
+                            connection_id: connection_id.clone(),
+                            request_id: request_id.clone(),
+                            workspace_id: workspace_id.clone(),
+                            is_server: true,
+                            message_type: match message {
+                                Message::Text(_) => WebsocketEventType::Text,
+                                Message::Binary(_) => WebsocketEventType::Binary,
+                                Message::Ping(_) => WebsocketEventType::Ping,
+                                Message::Pong(_) => WebsocketEventType::Pong,
+                                Message::Close(_) => WebsocketEventType::Close,
+                                // Raw frame will never happen during a read
+                                Message::Frame(_) => WebsocketEventType::Frame,
+                            },
+                            message: message.into_data().into(),
+                            ..Default::default()
+                        },
+                        &update_source,
+                    )
+                    .unwrap();
+
+                if let Some(tx) = &events_tx {
+                    // The receiver may have stopped listening, which is fine
+                    let _ = tx.send(event).await;
+                }
+            }
+            info!("Websocket connection closed");
+            if !has_written_close {
+                app_handle
+                    .db()
+                    .upsert_websocket_event(
+                        &WebsocketEvent {
+                            connection_id: connection_id.clone(),
+                            request_id: request_id.clone(),
+                            workspace_id: workspace_id.clone(),
+                            is_server: true,
+                            message_type: WebsocketEventType::Close,
+                            ..Default::default()
+                        },
+                        &update_source,
+                    )
+                    .unwrap();
+            }
+            app_handle
+                .db()
+                .upsert_websocket_connection(
+                    &WebsocketConnection {
+                        workspace_id: request.workspace_id.clone(),
+                        request_id: request_id.to_string(),
+                        state: WebsocketConnectionState::Closed,
+                        ..connection
+                    },
+                    &update_source,
+                )
+                .unwrap();
+        });
+    }
+
+    Ok(connection)
+}
+
+/// Send a text message on an open connection and store it as a client event
+pub async fn send_websocket_message<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    update_source: &UpdateSource,
+    ws_manager: &mut WebsocketManager,
+    connection: &WebsocketConnection,
+    message: String,
+) -> Result<WebsocketEvent> {
+    ws_manager.send(&connection.id, Message::Text(message.clone().into())).await?;
+
+    Ok(app_handle.db().upsert_websocket_event(
+        &WebsocketEvent {
+            connection_id: connection.id.clone(),
+            request_id: connection.request_id.clone(),
+            workspace_id: connection.workspace_id.clone(),
+            is_server: false,
+            message_type: WebsocketEventType::Text,
+            message: message.into(),
+            ..Default::default()
+        },
+        update_source,
+    )?)
+}
+
+/// Parse a header as typed by the user, which isn't necessarily valid
+fn parse_header(name: &str, value: &str) -> Result<(HeaderName, HeaderValue)> {
+    let header_name = HeaderName::from_str(name)
+        .map_err(|e| Error::GenericError(format!("Invalid header name {name:?}: {e}")))?;
+    let header_value = HeaderValue::from_str(value)
+        .map_err(|e| Error::GenericError(format!("Invalid value for header {name}: {e}")))?;
+    Ok((header_name, header_value))
+}
+
+/// Convert WS URL to HTTP URL for cookie filtering
+/// WebSocket upgrade requests are HTTP requests initially, so HttpOnly cookies should apply
+fn convert_ws_url_to_http(ws_url: &Url) -> Url {
+    let mut http_url = ws_url.clone();
+
+    match ws_url.scheme() {
+        "ws" => {
+            http_url.set_scheme("http").expect("Failed to set http scheme");
+        }
+        "wss" => {
+            http_url.set_scheme("https").expect("Failed to set https scheme");
+        }
+        _ => {
+            // Already HTTP/HTTPS, no conversion needed
+        }
+    }
+
+    http_url
+}
@@ -1,7 +1,8 @@
 mod commands;
 mod connect;
+pub mod connection;
 pub mod error;
-mod manager;
+pub mod manager;
 mod render;
 mod resolve;
 
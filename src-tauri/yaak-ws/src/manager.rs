@@ -12,6 +12,8 @@ use tokio_tungstenite::tungstenite::Message;
 use tokio_tungstenite::tungstenite::handshake::client::Response;
 use tokio_tungstenite::tungstenite::http::{HeaderMap, HeaderValue};
 use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+use yaak_http::dns::DnsResolver;
+use yaak_http::tls::TlsOptions;
 
 #[derive(Clone)]
 pub struct WebsocketManager {
@@ -34,11 +36,12 @@ impl WebsocketManager {
         url: &str,
         headers: HeaderMap<HeaderValue>,
         receive_tx: mpsc::Sender<Message>,
-        validate_certificates: bool,
+        tls_options: &TlsOptions,
+        dns_resolver: &DnsResolver,
     ) -> Result<Response> {
         let tx = receive_tx.clone();
 
-        let (stream, response) = ws_connect(url, headers, validate_certificates).await?;
+        let (stream, response) = ws_connect(url, headers, tls_options, dns_resolver).await?;
         let (write, mut read) = stream.split();
 
         self.connections.lock().await.insert(id.to_string(), write);
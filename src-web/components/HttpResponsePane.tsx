@@ -25,6 +25,7 @@ import { ErrorBoundary } from './ErrorBoundary';
 import { RecentHttpResponsesDropdown } from './RecentHttpResponsesDropdown';
 import { ResponseHeaders } from './ResponseHeaders';
 import { ResponseInfo } from './ResponseInfo';
+import { ResponseRequest } from './ResponseRequest';
 import { AudioViewer } from './responseViewers/AudioViewer';
 import { CsvViewer } from './responseViewers/CsvViewer';
 import { EventStreamViewer } from './responseViewers/EventStreamViewer';
@@ -46,6 +47,7 @@ interface Props {
 const TAB_BODY = 'body';
 const TAB_HEADERS = 'headers';
 const TAB_INFO = 'info';
+const TAB_REQUEST = 'request';
 
 export function HttpResponsePane({ style, className, activeRequestId }: Props) {
   const { activeResponse, setPinnedResponseId, responses } = usePinnedHttpResponse(activeRequestId);
@@ -84,6 +86,10 @@ export function HttpResponsePane({ style, className, activeRequestId }: Props) {
         value: TAB_INFO,
         label: 'Info',
       },
+      {
+        value: TAB_REQUEST,
+        label: 'Request',
+      },
     ],
     [activeResponse?.headers, mimeType, setViewMode, viewMode],
   );
@@ -210,6 +216,9 @@ export function HttpResponsePane({ style, className, activeRequestId }: Props) {
               <TabContent value={TAB_INFO}>
                 <ResponseInfo response={activeResponse} />
               </TabContent>
+              <TabContent value={TAB_REQUEST}>
+                <ResponseRequest response={activeResponse} />
+              </TabContent>
             </Tabs>
           )}
         </div>
@@ -38,6 +38,45 @@ export function ResponseInfo({ response }: Props) {
             </div>
           }
         </KeyValueRow>
+        {response.redirects.map((r, i) => (
+          <KeyValueRow labelColor="info" key={i} label={`Redirect ${i + 1}`}>
+            <div className="select-text cursor-text">
+              {r.status} {r.method} {r.url} → {r.location}
+            </div>
+          </KeyValueRow>
+        ))}
+        {(
+          [
+            ['DNS', response.timings.dns],
+            ['Connect', response.timings.connect],
+            ['TLS', response.timings.tls],
+            ['Time to First Byte', response.timings.ttfb],
+            ['Download', response.timings.download],
+          ] as const
+        ).map(([label, ms]) => (
+          <KeyValueRow labelColor="info" key={label} label={label}>
+            {ms} ms
+          </KeyValueRow>
+        ))}
+        {response.tls != null && (
+          <>
+            <KeyValueRow labelColor="info" label="TLS Version">
+              {response.tls.version}
+            </KeyValueRow>
+            <KeyValueRow labelColor="info" label="TLS Cipher">
+              {response.tls.cipher}
+            </KeyValueRow>
+            {response.tls.peerCertificates.map((c, i) => (
+              <KeyValueRow
+                labelColor="info"
+                key={i}
+                label={i === 0 ? 'Certificate' : `Intermediate ${i}`}
+              >
+                <pre className="select-text cursor-text text-xs">{c}</pre>
+              </KeyValueRow>
+            ))}
+          </>
+        )}
       </KeyValueRows>
     </div>
   );
@@ -0,0 +1,60 @@
+import { convertFileSrc } from '@tauri-apps/api/core';
+import { useQuery } from '@tanstack/react-query';
+import type { HttpResponse } from '@yaakapp-internal/models';
+import { KeyValueRow, KeyValueRows } from './core/KeyValueRow';
+import { Separator } from './core/Separator';
+import { EmptyStateText } from './EmptyStateText';
+
+interface Props {
+  response: HttpResponse;
+}
+
+export function ResponseRequest({ response }: Props) {
+  const request = response.request;
+  const bodyPath = request?.bodyPath ?? null;
+  const body = useQuery({
+    enabled: bodyPath != null,
+    queryKey: ['sent_request_body', response.id, bodyPath],
+    queryFn: async () => {
+      if (bodyPath == null) return null;
+      const res = await fetch(convertFileSrc(bodyPath));
+      const bytes = await res.arrayBuffer();
+      return { text: new TextDecoder().decode(bytes), size: bytes.byteLength };
+    },
+  });
+
+  if (request == null) {
+    return <EmptyStateText>The request was not recorded</EmptyStateText>;
+  }
+
+  const savedSize = body.data?.size ?? 0;
+  const truncated = request.contentLength != null && request.contentLength > savedSize;
+
+  return (
+    <div className="overflow-auto h-full pb-4">
+      <KeyValueRows>
+        <KeyValueRow labelColor="info" label="Method">
+          {request.method}
+        </KeyValueRow>
+        <KeyValueRow labelColor="info" label="URL">
+          <span className="select-text cursor-text">{request.url}</span>
+        </KeyValueRow>
+        {request.headers.map((h, i) => (
+          <KeyValueRow labelColor="primary" key={i} label={h.name}>
+            {h.value}
+          </KeyValueRow>
+        ))}
+      </KeyValueRows>
+      {body.data != null && (
+        <>
+          <Separator className="my-3">
+            Body{truncated ? ` (first ${savedSize} of ${request.contentLength} bytes)` : ''}
+          </Separator>
+          <pre className="select-text cursor-text text-sm whitespace-pre-wrap break-all">
+            {body.data.text}
+          </pre>
+        </>
+      )}
+    </div>
+  );
+}
@@ -10,6 +10,8 @@ import { HStack } from '../core/Stacks';
 import type { TabItem } from '../core/Tabs/Tabs';
 import { TabContent, Tabs } from '../core/Tabs/Tabs';
 import { HeaderSize } from '../HeaderSize';
+import { SettingsCertificates } from './SettingsCertificates';
+import { SettingsDns } from './SettingsDns';
 import { SettingsInterface } from './SettingsInterface';
 import { SettingsGeneral } from './SettingsGeneral';
 import { SettingsLicense } from './SettingsLicense';
@@ -25,9 +27,20 @@ const TAB_GENERAL = 'general';
 const TAB_INTERFACE = 'interface';
 const TAB_THEME = 'theme';
 const TAB_PROXY = 'proxy';
+const TAB_CERTIFICATES = 'certificates';
+const TAB_DNS = 'dns';
 const TAB_PLUGINS = 'plugins';
 const TAB_LICENSE = 'license';
-const tabs = [TAB_GENERAL, TAB_THEME, TAB_INTERFACE, TAB_PROXY, TAB_PLUGINS, TAB_LICENSE] as const;
+const tabs = [
+  TAB_GENERAL,
+  TAB_THEME,
+  TAB_INTERFACE,
+  TAB_PROXY,
+  TAB_CERTIFICATES,
+  TAB_DNS,
+  TAB_PLUGINS,
+  TAB_LICENSE,
+] as const;
 export type SettingsTab = (typeof tabs)[number];
 
 export default function Settings({ hide }: Props) {
@@ -77,7 +90,7 @@ export default function Settings({ hide }: Props) {
         tabs={tabs.map(
           (value): TabItem => ({
             value,
-            label: capitalize(value),
+            label: value === TAB_DNS ? 'DNS' : capitalize(value),
             hidden: !appInfo.featureLicense && value === TAB_LICENSE,
           }),
         )}
@@ -97,6 +110,12 @@ export default function Settings({ hide }: Props) {
         <TabContent value={TAB_PROXY} className="overflow-y-auto h-full px-8 !py-4">
           <SettingsProxy />
         </TabContent>
+        <TabContent value={TAB_CERTIFICATES} className="overflow-y-auto h-full px-8 !py-4">
+          <SettingsCertificates />
+        </TabContent>
+        <TabContent value={TAB_DNS} className="overflow-y-auto h-full px-8 !py-4">
+          <SettingsDns />
+        </TabContent>
         <TabContent value={TAB_LICENSE} className="overflow-y-auto h-full px-8 !py-4">
           <SettingsLicense />
         </TabContent>
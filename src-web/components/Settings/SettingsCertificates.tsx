@@ -0,0 +1,182 @@
+import type { CaCertificate, ClientCertificate } from '@yaakapp-internal/models';
+import { patchModel } from '@yaakapp-internal/models';
+import { useAtomValue } from 'jotai';
+import type { ReactNode } from 'react';
+import { activeWorkspaceAtom } from '../../hooks/useActiveWorkspace';
+import { Button } from '../core/Button';
+import { Checkbox } from '../core/Checkbox';
+import { Heading } from '../core/Heading';
+import { IconButton } from '../core/IconButton';
+import { PlainInput } from '../core/PlainInput';
+import { Separator } from '../core/Separator';
+import { HStack, VStack } from '../core/Stacks';
+import { SelectFile } from '../SelectFile';
+
+export function SettingsCertificates() {
+  const workspace = useAtomValue(activeWorkspaceAtom);
+  if (workspace == null) {
+    return null;
+  }
+
+  const clientCertificates = workspace.settingClientCertificates;
+  const caCertificates = workspace.settingCaCertificates;
+
+  const patchClientCertificate = (i: number, patch: Partial<ClientCertificate>) =>
+    patchModel(workspace, {
+      settingClientCertificates: clientCertificates.map((c, j) =>
+        i === j ? { ...c, ...patch } : c,
+      ),
+    });
+
+  const patchCaCertificate = (i: number, patch: Partial<CaCertificate>) =>
+    patchModel(workspace, {
+      settingCaCertificates: caCertificates.map((c, j) => (i === j ? { ...c, ...patch } : c)),
+    });
+
+  return (
+    <VStack space={3} className="mb-4">
+      <Heading level={2}>Client Certificates</Heading>
+      <p className="text-text-subtle">
+        Sent to servers that require mutual TLS. The first enabled certificate with a matching
+        host is used, where <code>*</code> matches anything, like <code>*.example.com</code> or{' '}
+        <code>localhost:8443</code>.
+      </p>
+      {clientCertificates.map((c, i) => (
+        <CertificateCard
+          key={i}
+          host={c.host}
+          enabled={c.enabled !== false}
+          onChange={(patch) => patchClientCertificate(i, patch)}
+          onDelete={() =>
+            patchModel(workspace, {
+              settingClientCertificates: clientCertificates.filter((_, j) => i !== j),
+            })
+          }
+        >
+          <SelectFile
+            label="PKCS#12 File (.p12, .pfx)"
+            filePath={c.pfxFile}
+            onChange={({ filePath }) => patchClientCertificate(i, { pfxFile: filePath })}
+          />
+          <PlainInput
+            size="sm"
+            type="password"
+            name="passphrase"
+            label="PKCS#12 Passphrase"
+            defaultValue={c.passphrase ?? ''}
+            onChange={(passphrase) => patchClientCertificate(i, { passphrase })}
+          />
+          <SelectFile
+            label="Certificate File (PEM)"
+            filePath={c.crtFile}
+            disabled={!!c.pfxFile}
+            onChange={({ filePath }) => patchClientCertificate(i, { crtFile: filePath })}
+          />
+          <SelectFile
+            label="Key File (PEM)"
+            filePath={c.keyFile}
+            disabled={!!c.pfxFile}
+            onChange={({ filePath }) => patchClientCertificate(i, { keyFile: filePath })}
+          />
+        </CertificateCard>
+      ))}
+      <Button
+        size="sm"
+        variant="border"
+        className="mr-auto"
+        onClick={() =>
+          patchModel(workspace, {
+            settingClientCertificates: [
+              ...clientCertificates,
+              {
+                enabled: true,
+                host: '',
+                crtFile: null,
+                keyFile: null,
+                pfxFile: null,
+                passphrase: null,
+              },
+            ],
+          })
+        }
+      >
+        Add Client Certificate
+      </Button>
+
+      <Separator className="my-4" />
+
+      <Heading level={2}>CA Certificates</Heading>
+      <p className="text-text-subtle">
+        Trusted on top of the system certificate authorities, for servers signed by a private CA.
+      </p>
+      {caCertificates.map((c, i) => (
+        <CertificateCard
+          key={i}
+          host={c.host}
+          enabled={c.enabled !== false}
+          onChange={(patch) => patchCaCertificate(i, patch)}
+          onDelete={() =>
+            patchModel(workspace, {
+              settingCaCertificates: caCertificates.filter((_, j) => i !== j),
+            })
+          }
+        >
+          <SelectFile
+            label="CA Certificate File (PEM, DER)"
+            filePath={c.file || null}
+            onChange={({ filePath }) => patchCaCertificate(i, { file: filePath ?? '' })}
+          />
+        </CertificateCard>
+      ))}
+      <Button
+        size="sm"
+        variant="border"
+        className="mr-auto"
+        onClick={() =>
+          patchModel(workspace, {
+            settingCaCertificates: [...caCertificates, { enabled: true, host: '*', file: '' }],
+          })
+        }
+      >
+        Add CA Certificate
+      </Button>
+    </VStack>
+  );
+}
+
+function CertificateCard({
+  host,
+  enabled,
+  onChange,
+  onDelete,
+  children,
+}: {
+  host: string;
+  enabled: boolean;
+  onChange: (patch: { host?: string; enabled?: boolean }) => void;
+  onDelete: () => void;
+  children: ReactNode;
+}) {
+  return (
+    <VStack space={2} className="border border-border-subtle rounded-md p-3">
+      <HStack space={2} alignItems="end">
+        <PlainInput
+          required
+          size="sm"
+          name="host"
+          label="Host"
+          placeholder="*.example.com"
+          defaultValue={host}
+          onChange={(host) => onChange({ host })}
+        />
+        <IconButton size="sm" icon="trash" title="Delete certificate" onClick={onDelete} />
+      </HStack>
+      <Checkbox
+        checked={enabled}
+        title="Enabled"
+        onChange={(enabled) => onChange({ enabled })}
+      />
+      {children}
+    </VStack>
+  );
+}
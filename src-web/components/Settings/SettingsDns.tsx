@@ -0,0 +1,135 @@
+import type { DnsOverride } from '@yaakapp-internal/models';
+import { patchModel } from '@yaakapp-internal/models';
+import { useAtomValue } from 'jotai';
+import { activeWorkspaceAtom } from '../../hooks/useActiveWorkspace';
+import { Button } from '../core/Button';
+import { Checkbox } from '../core/Checkbox';
+import { Heading } from '../core/Heading';
+import { IconButton } from '../core/IconButton';
+import { PlainInput } from '../core/PlainInput';
+import { Select } from '../core/Select';
+import { Separator } from '../core/Separator';
+import { HStack, VStack } from '../core/Stacks';
+
+export function SettingsDns() {
+  const workspace = useAtomValue(activeWorkspaceAtom);
+  if (workspace == null) {
+    return null;
+  }
+
+  const overrides = workspace.settingDnsOverrides;
+  const resolver = workspace.settingDnsResolver;
+
+  const patchOverride = (i: number, patch: Partial<DnsOverride>) =>
+    patchModel(workspace, {
+      settingDnsOverrides: overrides.map((o, j) => (i === j ? { ...o, ...patch } : o)),
+    });
+
+  return (
+    <VStack space={3} className="mb-4">
+      <Heading level={2}>Resolver</Heading>
+      <Select
+        name="dnsResolver"
+        label="Resolver"
+        hideLabel
+        size="sm"
+        value={resolver.type}
+        onChange={async (type) => {
+          if (type === 'nameserver') {
+            await patchModel(workspace, { settingDnsResolver: { type, address: '' } });
+          } else if (type === 'https') {
+            await patchModel(workspace, { settingDnsResolver: { type, url: '' } });
+          } else {
+            await patchModel(workspace, { settingDnsResolver: { type: 'system' } });
+          }
+        }}
+        options={[
+          { label: 'System resolver', value: 'system' },
+          { label: 'Custom nameserver', value: 'nameserver' },
+          { label: 'DNS over HTTPS', value: 'https' },
+        ]}
+      />
+      {resolver.type === 'nameserver' && (
+        <PlainInput
+          size="sm"
+          name="address"
+          label="Nameserver Address"
+          placeholder="1.1.1.1"
+          defaultValue={resolver.address}
+          onChange={(address) =>
+            patchModel(workspace, { settingDnsResolver: { type: 'nameserver', address } })
+          }
+        />
+      )}
+      {resolver.type === 'https' && (
+        <PlainInput
+          size="sm"
+          name="url"
+          label="DNS-over-HTTPS URL"
+          placeholder="https://cloudflare-dns.com/dns-query"
+          defaultValue={resolver.url}
+          onChange={(url) => patchModel(workspace, { settingDnsResolver: { type: 'https', url } })}
+        />
+      )}
+
+      <Separator className="my-4" />
+
+      <Heading level={2}>Overrides</Heading>
+      <p className="text-text-subtle">
+        Connect to a specific address while keeping the URL, Host header and TLS server name, like
+        curl&apos;s <code>--resolve</code>. Applies to HTTP, gRPC and WebSocket requests, and{' '}
+        <code>*</code> matches anything, like <code>*.staging.example.com</code>.
+      </p>
+      {overrides.map((o, i) => (
+        <HStack key={i} space={2} alignItems="end">
+          <Checkbox
+            hideLabel
+            checked={o.enabled !== false}
+            title="Enabled"
+            onChange={(enabled) => patchOverride(i, { enabled })}
+          />
+          <PlainInput
+            required
+            size="sm"
+            name="hostname"
+            label="Hostname"
+            placeholder="api.example.com"
+            defaultValue={o.hostname}
+            onChange={(hostname) => patchOverride(i, { hostname })}
+          />
+          <PlainInput
+            required
+            size="sm"
+            name="ip"
+            label="IP Address"
+            placeholder="10.0.0.1"
+            defaultValue={o.ip}
+            onChange={(ip) => patchOverride(i, { ip })}
+          />
+          <IconButton
+            size="sm"
+            icon="trash"
+            title="Delete override"
+            onClick={() =>
+              patchModel(workspace, {
+                settingDnsOverrides: overrides.filter((_, j) => i !== j),
+              })
+            }
+          />
+        </HStack>
+      ))}
+      <Button
+        size="sm"
+        variant="border"
+        className="mr-auto"
+        onClick={() =>
+          patchModel(workspace, {
+            settingDnsOverrides: [...overrides, { enabled: true, hostname: '', ip: '' }],
+          })
+        }
+      >
+        Add Override
+      </Button>
+    </VStack>
+  );
+}
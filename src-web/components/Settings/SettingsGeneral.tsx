@@ -131,6 +131,21 @@ export function SettingsGeneral() {
             })
           }
         />
+
+        <PlainInput
+          required
+          size="sm"
+          name="maxRedirects"
+          label="Max Redirects"
+          labelClassName="w-[14rem]"
+          placeholder="10"
+          labelPosition="left"
+          disabled={!workspace.settingFollowRedirects}
+          defaultValue={`${workspace.settingMaxRedirects}`}
+          validate={(value) => parseInt(value) >= 0}
+          onChange={(v) => patchModel(workspace, { settingMaxRedirects: parseInt(v) || 0 })}
+          type="number"
+        />
       </VStack>
 
       <Separator className="my-4" />
@@ -80,6 +80,14 @@ const NODE_TYPE_DEFINITIONS: NodeTypeDefinition[] = [
     icon: '🔌',
     color: '#06b6d4',
   },
+  {
+    category: 'action',
+    subtype: 'sub_workflow',
+    name: 'Sub-Workflow',
+    description: 'Run another workflow',
+    icon: '🧩',
+    color: '#a855f7',
+  },
 
   // Logic Control
   {
@@ -106,6 +114,22 @@ const NODE_TYPE_DEFINITIONS: NodeTypeDefinition[] = [
     icon: '⚡',
     color: '#06b6d4',
   },
+  {
+    category: 'logic',
+    subtype: 'assertion',
+    name: 'Assertion',
+    description: 'Check results of a previous node',
+    icon: '✅',
+    color: '#22c55e',
+  },
+  {
+    category: 'logic',
+    subtype: 'set_variable',
+    name: 'Set Variable',
+    description: 'Capture values into variables',
+    icon: '📌',
+    color: '#eab308',
+  },
 ];
 
 interface CategoryState {
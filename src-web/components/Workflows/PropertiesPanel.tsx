@@ -3,7 +3,10 @@ import { selectedNodeAtom, selectedNodeIdAtom } from '@yaakapp-internal/models/g
 import { PlainInput } from '../core/PlainInput';
 import { Button } from '../core/Button';
 import { useState, useEffect } from 'react';
-import { patchModel } from '@yaakapp-internal/models';
+import type { WorkflowNode } from '@yaakapp-internal/models';
+import { canvasNodesAtom, environmentsAtom, patchModel } from '@yaakapp-internal/models';
+import { useWebhookBaseUrl, webhookUrl } from '../../hooks/useWebhookBaseUrl';
+import { useWorkflows } from '../../hooks/useWorkflows';
 
 export function PropertiesPanel() {
   const selectedNodeId = useAtomValue(selectedNodeIdAtom);
@@ -12,6 +15,12 @@ export function PropertiesPanel() {
   const [description, setDescription] = useState('');
   const [config, setConfig] = useState<Record<string, any>>({});
   const [hasChanges, setHasChanges] = useState(false);
+  const { baseUrl: webhookBaseUrl, error: webhookError } = useWebhookBaseUrl();
+  const workflows = useWorkflows(selectedNode?.workspaceId ?? null);
+  const allNodes = useAtomValue(canvasNodesAtom);
+  const environments = useAtomValue(environmentsAtom).filter(
+    (e) => e.workspaceId === selectedNode?.workspaceId,
+  );
 
   useEffect(() => {
     if (selectedNode) {
@@ -48,6 +57,11 @@ export function PropertiesPanel() {
     setHasChanges(true);
   };
 
+  const updatePolicy = (key: string, value: any) => {
+    setConfig((prev) => ({ ...prev, policy: { ...prev.policy, [key]: value } }));
+    setHasChanges(true);
+  };
+
   const getNodeIcon = (subtype: string) => {
     const icons: Record<string, string> = {
       manual_trigger: '⚡',
@@ -58,9 +72,11 @@ export function PropertiesPanel() {
       email: '✉️',
       database: '🗄️',
       websocket: '🔌',
+      sub_workflow: '🧩',
       conditional: '❓',
       loop: '🔁',
       parallel: '⚡',
+      set_variable: '📌',
     };
     return icons[subtype] || '📦';
   };
@@ -114,12 +130,14 @@ export function PropertiesPanel() {
             <textarea
               value={config.condition || ''}
               onChange={(e) => updateConfig('condition', e.target.value)}
-              placeholder='{{step[0].response.status}} == 200'
+              placeholder="status == 200 && contains(headers['content-type'], 'json')"
               className="w-full px-3 py-2 bg-surface border border-border rounded font-mono text-sm"
               rows={4}
             />
             <p className="text-xs text-text-subtle mt-1">
-              Use template syntax to reference previous steps
+              Fields of the previous node are available directly (eg. <code>body.items.length</code>).
+              Reference other nodes with <code>nodes.&lt;name&gt;.status</code>, variables with{' '}
+              <code>vars.name</code> and the current loop with <code>loop.index</code>
             </p>
           </div>
         );
@@ -175,6 +193,21 @@ export function PropertiesPanel() {
                 max={10}
               />
             </div>
+            <div>
+              <label className="block text-sm font-medium mb-1">Max Concurrency</label>
+              <PlainInput
+                type="number"
+                value={config.max_concurrency ?? ''}
+                onChange={(e) =>
+                  updateConfig(
+                    'max_concurrency',
+                    e.target.value === '' ? undefined : parseInt(e.target.value),
+                  )
+                }
+                placeholder="Unlimited"
+                min={1}
+              />
+            </div>
             <div>
               <label className="flex items-center gap-2">
                 <input
@@ -185,7 +218,8 @@ export function PropertiesPanel() {
                 <span className="text-sm">Fail-fast mode</span>
               </label>
               <p className="text-xs text-text-subtle mt-1">
-                Stop execution if any branch fails
+                Cancel remaining branches as soon as one fails. When off, all branches run and
+                every failure is reported.
               </p>
             </div>
           </>
@@ -271,20 +305,192 @@ export function PropertiesPanel() {
           </>
         );
 
-      case 'webhook_trigger':
+      case 'sub_workflow': {
+        const childNodes = allNodes.filter(
+          (n: WorkflowNode) => n.workflowId === config.workflow_id && n.deletedAt == null,
+        );
+        const outputs: string[] = config.outputs ?? [];
         return (
           <>
             <div>
-              <label className="block text-sm font-medium mb-1">Webhook URL</label>
+              <label className="block text-sm font-medium mb-1">Workflow</label>
+              <select
+                value={config.workflow_id || ''}
+                onChange={(e) => {
+                  updateConfig('workflow_id', e.target.value);
+                  updateConfig('outputs', []);
+                }}
+                className="w-full px-3 py-2 bg-surface border border-border rounded"
+              >
+                <option value="">Select a workflow</option>
+                {workflows
+                  .filter((w) => w.id !== selectedNode.workflowId)
+                  .map((w) => (
+                    <option key={w.id} value={w.id}>
+                      {w.name}
+                    </option>
+                  ))}
+              </select>
+            </div>
+            <div>
+              <label className="block text-sm font-medium mb-1">Inputs</label>
+              <textarea
+                key={selectedNode.id}
+                defaultValue={Object.entries(config.inputs ?? {})
+                  .map(([k, v]) => `${k}=${v}`)
+                  .join('\n')}
+                onChange={(e) => {
+                  const inputs: Record<string, string> = {};
+                  for (const line of e.target.value.split('\n')) {
+                    const i = line.indexOf('=');
+                    if (i > 0) inputs[line.slice(0, i).trim()] = line.slice(i + 1).trim();
+                  }
+                  updateConfig('inputs', inputs);
+                }}
+                placeholder="userId=${[ response.body.id ]}"
+                className="w-full px-3 py-2 bg-surface border border-border rounded font-mono text-sm"
+                rows={4}
+              />
+              <p className="text-xs text-text-subtle mt-1">
+                One <code>name=value</code> per line, available as variables in the workflow
+              </p>
+            </div>
+            <div>
+              <label className="block text-sm font-medium mb-1">Outputs</label>
+              {childNodes.length === 0 ? (
+                <p className="text-xs text-text-subtle">Select a workflow to pick its outputs</p>
+              ) : (
+                childNodes.map((n) => (
+                  <label key={n.id} className="flex items-center gap-2 text-sm">
+                    <input
+                      type="checkbox"
+                      checked={outputs.includes(n.name)}
+                      onChange={(e) =>
+                        updateConfig(
+                          'outputs',
+                          e.target.checked
+                            ? [...outputs, n.name]
+                            : outputs.filter((o) => o !== n.name),
+                        )
+                      }
+                    />
+                    {n.name}
+                  </label>
+                ))
+              )}
+              <p className="text-xs text-text-subtle mt-1">
+                Results of the checked nodes become this node&apos;s output, or all of them if none are
+                checked
+              </p>
+            </div>
+          </>
+        );
+      }
+
+      case 'set_variable': {
+        const variables: { name: string; type: string; path: string }[] = config.variables ?? [];
+        const updateVariable = (index: number, key: string, value: string) =>
+          updateConfig(
+            'variables',
+            variables.map((v, i) => (i === index ? { ...v, [key]: value } : v)),
+          );
+        return (
+          <>
+            <div>
+              <label className="block text-sm font-medium mb-1">Source Node</label>
               <PlainInput
-                value={config.url || ''}
-                onChange={(e) => updateConfig('url', e.target.value)}
-                placeholder="Auto-generated"
-                disabled
+                defaultValue={config.source_node || ''}
+                onChange={(value) => updateConfig('source_node', value)}
+                placeholder="Previous node"
               />
             </div>
             <div>
-              <label className="block text-sm font-medium mb-1">Allowed Methods</label>
+              <label className="block text-sm font-medium mb-1">Variables</label>
+              <div className="space-y-2">
+                {variables.map((v, i) => (
+                  <div key={i} className="flex gap-1">
+                    <input
+                      value={v.name}
+                      onChange={(e) => updateVariable(i, 'name', e.target.value)}
+                      placeholder="name"
+                      className="w-20 px-2 py-1 bg-surface border border-border rounded text-sm"
+                    />
+                    <select
+                      value={v.type}
+                      onChange={(e) => updateVariable(i, 'type', e.target.value)}
+                      className="px-1 py-1 bg-surface border border-border rounded text-sm"
+                    >
+                      <option value="json_path">JSONPath</option>
+                      <option value="regex">Regex</option>
+                      <option value="header">Header</option>
+                    </select>
+                    <input
+                      value={v.path}
+                      onChange={(e) => updateVariable(i, 'path', e.target.value)}
+                      placeholder={v.type === 'header' ? 'X-Token' : '$.body.token'}
+                      className="flex-1 min-w-0 px-2 py-1 bg-surface border border-border rounded font-mono text-sm"
+                    />
+                    <button
+                      onClick={() =>
+                        updateConfig(
+                          'variables',
+                          variables.filter((_, j) => j !== i),
+                        )
+                      }
+                      className="px-1 text-text-subtle hover:text-text"
+                      title="Remove variable"
+                    >
+                      ×
+                    </button>
+                  </div>
+                ))}
+              </div>
+              <Button
+                size="xs"
+                variant="border"
+                className="mt-2"
+                onClick={() =>
+                  updateConfig('variables', [...variables, { name: '', type: 'json_path', path: '' }])
+                }
+              >
+                Add Variable
+              </Button>
+            </div>
+            <div>
+              <label className="block text-sm font-medium mb-1">Save to Environment</label>
+              <select
+                value={config.environment_id || ''}
+                onChange={(e) => updateConfig('environment_id', e.target.value)}
+                className="w-full px-3 py-2 bg-surface border border-border rounded"
+              >
+                <option value="">Only this run</option>
+                {environments.map((e) => (
+                  <option key={e.id} value={e.id}>
+                    {e.name}
+                  </option>
+                ))}
+              </select>
+              <p className="text-xs text-text-subtle mt-1">
+                Saved values can be used by regular requests after the run
+              </p>
+            </div>
+          </>
+        );
+      }
+
+      case 'webhook_trigger':
+        return (
+          <>
+            <div>
+              <label className="block text-sm font-medium mb-1">Webhook URL</label>
+              <div className="w-full px-3 py-2 bg-surface border border-border rounded font-mono text-xs break-all select-text">
+                {webhookUrl(webhookBaseUrl, selectedNode.id) ??
+                  webhookError ??
+                  'Webhook listener is not running'}
+              </div>
+            </div>
+            <div>
+              <label className="block text-sm font-medium mb-1">Allowed Method</label>
               <select
                 value={config.method || 'POST'}
                 onChange={(e) => updateConfig('method', e.target.value)}
@@ -293,9 +499,43 @@ export function PropertiesPanel() {
                 <option>GET</option>
                 <option>POST</option>
                 <option>PUT</option>
+                <option>PATCH</option>
                 <option>DELETE</option>
               </select>
             </div>
+            <div>
+              <label className="block text-sm font-medium mb-1">Auth Token</label>
+              <PlainInput
+                value={config.auth_token || ''}
+                onChange={(e) => updateConfig('auth_token', e.target.value)}
+                placeholder="Required"
+              />
+              <Button
+                size="xs"
+                variant="border"
+                className="mt-2"
+                onClick={() => updateConfig('auth_token', crypto.randomUUID().replaceAll('-', ''))}
+              >
+                Generate Token
+              </Button>
+              <p className="text-xs text-text-subtle mt-1">
+                Send as <code>Authorization: Bearer &lt;token&gt;</code> or an{' '}
+                <code>X-Webhook-Token</code> header
+              </p>
+            </div>
+            <div>
+              <label className="flex items-center gap-2">
+                <input
+                  type="checkbox"
+                  checked={config.allow_unauthenticated ?? false}
+                  onChange={(e) => updateConfig('allow_unauthenticated', e.target.checked)}
+                />
+                <span className="text-sm">Allow requests without a token</span>
+              </label>
+              <p className="text-xs text-text-subtle mt-1">
+                Only used when no token is set. Any local process could then start this workflow.
+              </p>
+            </div>
           </>
         );
 
@@ -331,8 +571,22 @@ export function PropertiesPanel() {
                   onChange={(e) => updateConfig('cron_expression', e.target.value)}
                   placeholder="0 0 * * *"
                 />
+                <p className="text-xs text-text-subtle mt-1">
+                  minute hour day-of-month month day-of-week, in local time
+                </p>
               </div>
             )}
+            <div>
+              <label className="block text-sm font-medium mb-1">If Still Running</label>
+              <select
+                value={config.overlap_policy || 'skip'}
+                onChange={(e) => updateConfig('overlap_policy', e.target.value)}
+                className="w-full px-3 py-2 bg-surface border border-border rounded"
+              >
+                <option value="skip">Skip this run</option>
+                <option value="queue">Run when the previous one finishes</option>
+              </select>
+            </div>
           </>
         );
 
@@ -392,6 +646,57 @@ export function PropertiesPanel() {
             {renderConfigFields()}
           </div>
         </div>
+
+        {selectedNode.nodeType !== 'trigger' && (
+          <div className="border-t border-border pt-4">
+            <h4 className="font-medium text-sm mb-3">Error Handling</h4>
+            <div className="space-y-4">
+              <div>
+                <label className="block text-sm font-medium mb-1">Max Retries</label>
+                <PlainInput
+                  type="number"
+                  value={config.policy?.max_retries ?? 0}
+                  onChange={(e) => updatePolicy('max_retries', parseInt(e.target.value) || 0)}
+                  min={0}
+                  max={10}
+                />
+              </div>
+              <div>
+                <label className="block text-sm font-medium mb-1">Retry Backoff (ms)</label>
+                <PlainInput
+                  type="number"
+                  value={config.policy?.retry_backoff_ms ?? 1000}
+                  onChange={(e) => updatePolicy('retry_backoff_ms', parseInt(e.target.value) || 0)}
+                  min={0}
+                />
+                <p className="text-xs text-text-subtle mt-1">Doubles after every retry</p>
+              </div>
+              <div>
+                <label className="block text-sm font-medium mb-1">Timeout (ms)</label>
+                <PlainInput
+                  type="number"
+                  value={config.policy?.timeout_ms ?? ''}
+                  onChange={(e) =>
+                    updatePolicy(
+                      'timeout_ms',
+                      e.target.value === '' ? undefined : parseInt(e.target.value),
+                    )
+                  }
+                  placeholder="No timeout"
+                  min={0}
+                />
+              </div>
+              <label className="flex items-center gap-2">
+                <input
+                  type="checkbox"
+                  checked={config.policy?.continue_on_error ?? false}
+                  onChange={(e) => updatePolicy('continue_on_error', e.target.checked)}
+                />
+                <span className="text-sm">Continue on error</span>
+              </label>
+            </div>
+          </div>
+        )}
       </div>
 
       {/* Footer */}
@@ -1,5 +1,10 @@
 import { Button } from '../core/Button';
+import { SelectFile } from '../SelectFile';
+import { WorkflowLoadTestDialog } from './WorkflowLoadTestDialog';
+import { showDialog } from '../../lib/dialog';
 import { useAtomValue } from 'jotai';
+import { patchModel } from '@yaakapp-internal/models';
+import { useWorkflow } from '../../hooks/useWorkflows';
 import { isExecutingAtom } from '@yaakapp-internal/models/guest-js/atoms';
 import { invoke } from '@tauri-apps/api/core';
 import { useState } from 'react';
@@ -34,6 +39,7 @@ export function Toolbar({
   zoom = 100,
 }: ToolbarProps) {
   const isExecuting = useAtomValue(isExecutingAtom);
+  const workflow = useWorkflow(workflowId);
   const [showEnvironmentSelector, setShowEnvironmentSelector] = useState(false);
 
   const handleExecute = async () => {
@@ -69,6 +75,21 @@ export function Toolbar({
           {isExecuting ? 'Executing...' : 'Execute Workflow'}
         </Button>
 
+        <Button
+          variant="border"
+          title="Run the workflow many times concurrently and measure latency"
+          onClick={() =>
+            showDialog({
+              id: 'workflow-load-test',
+              title: 'Load Test',
+              size: 'lg',
+              render: () => <WorkflowLoadTestDialog workflowId={workflowId} />,
+            })
+          }
+        >
+          Load Test
+        </Button>
+
         <div className="w-px h-6 bg-border mx-2" />
 
         <button
@@ -131,6 +152,17 @@ export function Toolbar({
 
       {/* Right Section - Settings */}
       <div className="flex items-center gap-2">
+        {workflow != null && (
+          <div className="w-64">
+            <SelectFile
+              size="xs"
+              noun="Dataset"
+              filePath={workflow.datasetPath}
+              help="Run the workflow once per row of a CSV or JSON file, with the row's fields as variables"
+              onChange={({ filePath }) => patchModel(workflow, { datasetPath: filePath })}
+            />
+          </div>
+        )}
         <button
           className="px-3 py-1.5 text-sm rounded hover:bg-surface-highlight"
           title="Settings"
@@ -1,8 +1,15 @@
+import { save } from '@tauri-apps/plugin-dialog';
+import type { WorkflowExecution, WorkflowNode } from '@yaakapp-internal/models';
+import { canvasNodesAtom } from '@yaakapp-internal/models';
+import { useAtomValue } from 'jotai';
 import { useState } from 'react';
 import { useWorkflowExecutions } from '../../hooks/useWorkflowExecution';
+import { invokeCmd } from '../../lib/tauri';
+import { showToast } from '../../lib/toast';
 import { Badge } from '../core/Badge';
 import { Button } from '../core/Button';
 import { Dialog } from '../core/Dialog';
+import { Dropdown } from '../core/Dropdown';
 import { EmptyStateText } from '../EmptyStateText';
 import { WorkflowExecutionResults } from './WorkflowExecutionResults';
 
@@ -10,8 +17,49 @@ interface Props {
   workflowId: string;
 }
 
+type ReportFormat = 'junit' | 'json';
+
+async function exportReport(execution: WorkflowExecution, format: ReportFormat) {
+  const filePath = await save({
+    defaultPath: format === 'junit' ? `${execution.id}.xml` : `${execution.id}.json`,
+    title: format === 'junit' ? 'Export JUnit Report' : 'Export JSON Report',
+  });
+  if (filePath == null) return;
+
+  try {
+    await invokeCmd('cmd_export_workflow_execution_report', {
+      executionId: execution.id,
+      format,
+      filePath,
+    });
+    showToast({ message: 'Report exported', color: 'success' });
+  } catch (err) {
+    showToast({ message: `Failed to export report: ${err}`, color: 'danger' });
+  }
+}
+
+async function resumeExecution(execution: WorkflowExecution, nodeId: string | null) {
+  try {
+    await invokeCmd('cmd_resume_workflow_execution', { executionId: execution.id, nodeId });
+  } catch (err) {
+    showToast({ message: `Failed to resume execution: ${err}`, color: 'danger' });
+  }
+}
+
 export function WorkflowExecutionHistory({ workflowId }: Props) {
-  const executions = useWorkflowExecutions(workflowId);
+  const allExecutions = useWorkflowExecutions(workflowId);
+  // Dataset rows are listed under the execution that grouped them, and load test runs are only
+  // summarized by their load test
+  const executions = allExecutions.filter(
+    (e) => e.parentExecutionId == null && e.loadTestId == null,
+  );
+  const rowsOf = (execution: WorkflowExecution) =>
+    allExecutions
+      .filter((e) => e.parentExecutionId === execution.id)
+      .sort((a, b) => (a.datasetRow ?? 0) - (b.datasetRow ?? 0));
+  const nodes = useAtomValue(canvasNodesAtom).filter(
+    (n: WorkflowNode) => n.workflowId === workflowId && n.deletedAt == null,
+  );
   const [selectedExecutionId, setSelectedExecutionId] = useState<string | null>(null);
 
   const getStateBadge = (state: string) => {
@@ -50,7 +98,14 @@ export function WorkflowExecutionHistory({ workflowId }: Props) {
               <span className="text-sm font-medium">
                 {new Date(execution.createdAt).toLocaleString()}
               </span>
-              {getStateBadge(execution.state)}
+              <div className="flex items-center gap-1">
+                {execution.datasetRow != null && (
+                  <Badge size="sm">Row {execution.datasetRow + 1}</Badge>
+                )}
+                {execution.resumedFromId != null && <Badge size="sm">Resumed</Badge>}
+                {execution.callerExecutionId != null && <Badge size="sm">Sub-workflow</Badge>}
+                {getStateBadge(execution.state)}
+              </div>
             </div>
 
             {execution.elapsed != null && (
@@ -59,9 +114,81 @@ export function WorkflowExecutionHistory({ workflowId }: Props) {
               </div>
             )}
 
+            {execution.assertionsPassed + execution.assertionsFailed > 0 && (
+              <div className="text-xs text-text-subtle">
+                Assertions: {execution.assertionsPassed} passed, {execution.assertionsFailed} failed
+              </div>
+            )}
+
             {execution.error && (
               <div className="text-xs text-danger mt-1 truncate">{execution.error}</div>
             )}
+
+            {rowsOf(execution).length > 0 && (
+              <div className="flex flex-col gap-1 mt-2">
+                {rowsOf(execution).map((row) => (
+                  <div
+                    key={row.id}
+                    className="flex items-center justify-between text-xs px-2 py-1 rounded hover:bg-surface"
+                    onClick={(e) => {
+                      e.stopPropagation();
+                      setSelectedExecutionId(row.id);
+                    }}
+                  >
+                    <span>Row {(row.datasetRow ?? 0) + 1}</span>
+                    <div className="flex items-center gap-1">
+                      {(row.state === 'failed' || row.state === 'cancelled') && (
+                        <Button
+                          size="2xs"
+                          variant="border"
+                          onClick={(e) => {
+                            e.stopPropagation();
+                            resumeExecution(row, null);
+                          }}
+                        >
+                          Resume
+                        </Button>
+                      )}
+                      {getStateBadge(row.state)}
+                    </div>
+                  </div>
+                ))}
+              </div>
+            )}
+
+            {execution.state !== 'running' && execution.state !== 'initialized' && (
+              <div className="flex gap-1 mt-2" onClick={(e) => e.stopPropagation()}>
+                <Button size="2xs" variant="border" onClick={() => exportReport(execution, 'junit')}>
+                  Export JUnit
+                </Button>
+                <Button size="2xs" variant="border" onClick={() => exportReport(execution, 'json')}>
+                  Export JSON
+                </Button>
+                {(execution.state === 'failed' || execution.state === 'cancelled') &&
+                  rowsOf(execution).length === 0 && (
+                    <Button
+                      size="2xs"
+                      variant="border"
+                      title="Run again from the first failed node, reusing the results of completed nodes"
+                      onClick={() => resumeExecution(execution, null)}
+                    >
+                      Resume
+                    </Button>
+                  )}
+                {rowsOf(execution).length === 0 && (
+                  <Dropdown
+                    items={nodes.map((n: WorkflowNode) => ({
+                      label: n.name || n.nodeSubtype,
+                      onSelect: () => resumeExecution(execution, n.id),
+                    }))}
+                  >
+                    <Button size="2xs" variant="border">
+                      Re-run From…
+                    </Button>
+                  </Dropdown>
+                )}
+              </div>
+            )}
           </div>
         ))}
       </div>
@@ -1,9 +1,11 @@
+import type { WorkflowNode } from '@yaakapp-internal/models';
+import { canvasNodesAtom } from '@yaakapp-internal/models';
 import classNames from 'classnames';
+import { useAtomValue } from 'jotai';
 import {
   useWorkflowExecution,
-  useWorkflowStepExecutions,
+  useWorkflowNodeExecutions,
 } from '../../hooks/useWorkflowExecution';
-import { useWorkflowSteps } from '../../hooks/useWorkflowSteps';
 import { Badge } from '../core/Badge';
 import { Icon } from '../core/Icon';
 import { LoadingIcon } from '../core/LoadingIcon';
@@ -15,8 +17,12 @@ interface Props {
 
 export function WorkflowExecutionResults({ executionId }: Props) {
   const execution = useWorkflowExecution(executionId);
-  const stepExecutions = useWorkflowStepExecutions(executionId);
-  const steps = useWorkflowSteps(execution?.workflowId ?? null);
+  const nodeExecutions = useWorkflowNodeExecutions(executionId);
+  const nodes = useAtomValue(canvasNodesAtom);
+  const nodeName = (nodeId: string) => {
+    const node = nodes.find((n: WorkflowNode) => n.id === nodeId);
+    return node == null ? nodeId : node.name || node.nodeSubtype;
+  };
 
   if (!execution) {
     return (
@@ -67,24 +73,22 @@ export function WorkflowExecutionResults({ executionId }: Props) {
         )}
       </div>
 
-      {/* Step Executions */}
+      {/* Node Executions */}
       <div>
-        <h4 className="font-semibold mb-3">Step Results</h4>
+        <h4 className="font-semibold mb-3">Node Results</h4>
         <div className="flex flex-col gap-2">
-          {steps.map((step, index) => {
-            const stepExec = stepExecutions.find((se) => se.workflowStepId === step.id);
-            const stepState = stepExec?.state ?? 'pending';
-            const isStepRunning = stepState === 'running';
-            const isStepCompleted = stepState === 'completed';
-            const isStepFailed = stepState === 'failed';
+          {nodeExecutions.map((nodeExec, index) => {
+            const isNodeRunning = nodeExec.state === 'running';
+            const isNodeCompleted = nodeExec.state === 'completed';
+            const isNodeFailed = nodeExec.state === 'failed';
 
             return (
               <div
-                key={step.id}
+                key={nodeExec.id}
                 className={classNames(
                   'border border-border rounded-md p-3',
-                  isStepFailed && 'border-danger bg-danger-subtle',
-                  isStepCompleted && 'border-success',
+                  isNodeFailed && 'border-danger bg-danger-subtle',
+                  isNodeCompleted && 'border-success',
                 )}
               >
                 <div className="flex items-center gap-3">
@@ -92,23 +96,23 @@ export function WorkflowExecutionResults({ executionId }: Props) {
 
                   <div className="flex-1">
                     <div className="flex items-center gap-2">
-                      <span className="font-medium">{step.name}</span>
-                      {getStateBadge(stepState)}
+                      <span className="font-medium">{nodeName(nodeExec.workflowNodeId)}</span>
+                      {getStateBadge(nodeExec.state)}
                     </div>
-                    {stepExec?.elapsed != null && (
+                    {nodeExec.elapsed != null && (
                       <div className="text-xs text-text-subtle mt-1">
-                        {stepExec.elapsed}ms
+                        {nodeExec.elapsed}ms
                       </div>
                     )}
-                    {stepExec?.error && (
-                      <div className="text-xs text-danger mt-1">{stepExec.error}</div>
+                    {nodeExec.error && (
+                      <div className="text-xs text-danger mt-1">{nodeExec.error}</div>
                     )}
                   </div>
 
                   <div className="flex-shrink-0">
-                    {isStepRunning && <LoadingIcon />}
-                    {isStepCompleted && <Icon icon="check" className="text-success" />}
-                    {isStepFailed && <Icon icon="x" className="text-danger" />}
+                    {isNodeRunning && <LoadingIcon />}
+                    {isNodeCompleted && <Icon icon="check" className="text-success" />}
+                    {isNodeFailed && <Icon icon="x" className="text-danger" />}
                   </div>
                 </div>
               </div>
@@ -0,0 +1,230 @@
+import { save } from '@tauri-apps/plugin-dialog';
+import type { WorkflowLoadTest } from '@yaakapp-internal/models';
+import { deleteModel, workflowLoadTestsAtom } from '@yaakapp-internal/models';
+import { useAtomValue } from 'jotai';
+import { useState } from 'react';
+import { useWorkflowExecution } from '../../hooks/useWorkflowExecution';
+import { invokeCmd } from '../../lib/tauri';
+import { showToast } from '../../lib/toast';
+import { Badge } from '../core/Badge';
+import { Button } from '../core/Button';
+import { PlainInput } from '../core/PlainInput';
+import { EmptyStateText } from '../EmptyStateText';
+
+interface Props {
+  workflowId: string;
+}
+
+interface LatencyStats {
+  min: number;
+  max: number;
+  mean: number;
+  p50: number;
+  p95: number;
+  p99: number;
+}
+
+interface NodeSummary {
+  nodeId: string;
+  nodeName: string;
+  nodeSubtype: string;
+  count: number;
+  errors: number;
+  errorRate: number;
+  throughput: number;
+  latency: LatencyStats | null;
+  ttfb: LatencyStats | null;
+}
+
+interface LoadTestSummary {
+  completed: number;
+  failed: number;
+  errorRate: number;
+  throughput: number;
+  latency: LatencyStats | null;
+  nodes: NodeSummary[];
+  sampleFailedExecutionId: string | null;
+}
+
+const formatPercent = (rate: number) => `${(rate * 100).toFixed(1)}%`;
+const formatPercentiles = (l: LatencyStats | null) =>
+  l == null ? '–' : `${l.p50} / ${l.p95} / ${l.p99}`;
+
+export function WorkflowLoadTestDialog({ workflowId }: Props) {
+  const loadTests = useAtomValue(workflowLoadTestsAtom).filter(
+    (t: WorkflowLoadTest) => t.workflowId === workflowId,
+  );
+  const [iterations, setIterations] = useState<string>('100');
+  const [concurrency, setConcurrency] = useState<string>('10');
+  const [rampUpMs, setRampUpMs] = useState<string>('0');
+
+  const start = async () => {
+    try {
+      await invokeCmd('cmd_run_workflow_load_test', {
+        req: {
+          workflowId,
+          environmentId: null,
+          iterations: parseInt(iterations) || 1,
+          concurrency: parseInt(concurrency) || 1,
+          rampUpMs: parseInt(rampUpMs) || 0,
+        },
+      });
+    } catch (err) {
+      showToast({ message: `Failed to start load test: ${err}`, color: 'danger' });
+    }
+  };
+
+  return (
+    <div className="flex flex-col gap-4 pb-4">
+      <div className="grid grid-cols-[1fr_1fr_1fr_auto] gap-2 items-end">
+        <PlainInput
+          type="number"
+          label="Iterations"
+          defaultValue={iterations}
+          onChange={setIterations}
+        />
+        <PlainInput
+          type="number"
+          label="Concurrency"
+          defaultValue={concurrency}
+          onChange={setConcurrency}
+        />
+        <PlainInput
+          type="number"
+          label="Ramp-up (ms)"
+          defaultValue={rampUpMs}
+          onChange={setRampUpMs}
+        />
+        <Button color="primary" onClick={start}>
+          Start
+        </Button>
+      </div>
+
+      {loadTests.length === 0 ? (
+        <EmptyStateText>No load tests yet</EmptyStateText>
+      ) : (
+        loadTests.map((t: WorkflowLoadTest) => <LoadTestResult key={t.id} loadTest={t} />)
+      )}
+    </div>
+  );
+}
+
+function LoadTestResult({ loadTest }: { loadTest: WorkflowLoadTest }) {
+  const summary = loadTest.summary as LoadTestSummary | null;
+  const sample = useWorkflowExecution(summary?.sampleFailedExecutionId ?? null);
+  const isRunning = loadTest.state === 'running';
+
+  const exportSample = async (executionId: string) => {
+    const filePath = await save({ defaultPath: `${executionId}.json`, title: 'Export JSON Report' });
+    if (filePath == null) return;
+    try {
+      await invokeCmd('cmd_export_workflow_execution_report', {
+        executionId,
+        format: 'json',
+        filePath,
+      });
+    } catch (err) {
+      showToast({ message: `Failed to export report: ${err}`, color: 'danger' });
+    }
+  };
+
+  return (
+    <div className="border border-border rounded-md p-3 flex flex-col gap-2">
+      <div className="flex items-center justify-between">
+        <span className="text-sm font-medium">
+          {new Date(loadTest.createdAt).toLocaleString()}
+        </span>
+        <div className="flex items-center gap-1">
+          <Badge size="sm">
+            {loadTest.completed + loadTest.failed} / {loadTest.iterations}
+          </Badge>
+          <Badge
+            size="sm"
+            color={
+              loadTest.state === 'completed'
+                ? 'success'
+                : loadTest.state === 'failed'
+                  ? 'danger'
+                  : loadTest.state === 'cancelled'
+                    ? 'warning'
+                    : 'primary'
+            }
+          >
+            {loadTest.state}
+          </Badge>
+          {isRunning ? (
+            <Button
+              size="2xs"
+              variant="border"
+              onClick={() =>
+                invokeCmd('cmd_cancel_workflow_execution_canvas', { executionId: loadTest.id })
+              }
+            >
+              Cancel
+            </Button>
+          ) : (
+            <Button size="2xs" variant="border" onClick={() => deleteModel(loadTest)}>
+              Delete
+            </Button>
+          )}
+        </div>
+      </div>
+
+      <div className="text-xs text-text-subtle">
+        {loadTest.concurrency} concurrent, {loadTest.rampUpMs}ms ramp-up
+        {loadTest.elapsed != null && `, took ${loadTest.elapsed}ms`}
+      </div>
+
+      {loadTest.error && <div className="text-xs text-danger">{loadTest.error}</div>}
+
+      {summary != null && (
+        <>
+          <div className="text-xs">
+            {summary.throughput.toFixed(1)} runs/s, {formatPercent(summary.errorRate)} failed, p50 /
+            p95 / p99 {formatPercentiles(summary.latency)} ms
+          </div>
+          <table className="text-xs w-full">
+            <thead>
+              <tr className="text-text-subtle text-left">
+                <th className="font-normal">Node</th>
+                <th className="font-normal">Count</th>
+                <th className="font-normal">Errors</th>
+                <th className="font-normal">Per second</th>
+                <th className="font-normal">p50 / p95 / p99 (ms)</th>
+                <th className="font-normal">TTFB p50 / p95 / p99 (ms)</th>
+              </tr>
+            </thead>
+            <tbody>
+              {summary.nodes.map((n) => (
+                <tr key={n.nodeId}>
+                  <td>{n.nodeName || n.nodeSubtype}</td>
+                  <td>{n.count}</td>
+                  <td className={n.errors > 0 ? 'text-danger' : undefined}>
+                    {formatPercent(n.errorRate)}
+                  </td>
+                  <td>{n.throughput.toFixed(1)}</td>
+                  <td>{formatPercentiles(n.latency)}</td>
+                  <td>{formatPercentiles(n.ttfb)}</td>
+                </tr>
+              ))}
+            </tbody>
+          </table>
+          {summary.sampleFailedExecutionId != null && (
+            <div className="flex items-center justify-between gap-2 text-xs">
+              <span className="text-danger truncate">
+                Sample failure: {sample?.error ?? summary.sampleFailedExecutionId}
+              </span>
+              <Button
+                size="2xs"
+                variant="border"
+                onClick={() => exportSample(summary.sampleFailedExecutionId!)}
+              >
+                Export Report
+              </Button>
+            </div>
+          )}
+        </>
+      )}
+    </div>
+  );
+}
@@ -1,4 +1,3 @@
-export { AddStepDialog } from './AddStepDialog';
 export { CreateWorkflowDialog } from './CreateWorkflowDialog';
 export { RequestsPanel } from './RequestsPanel';
 export { WorkflowCanvas } from './WorkflowCanvas';
@@ -6,6 +5,5 @@ export { WorkflowEditor } from './WorkflowEditor';
 export { WorkflowExecutionButton } from './WorkflowExecutionButton';
 export { WorkflowExecutionHistory } from './WorkflowExecutionHistory';
 export { WorkflowExecutionResults } from './WorkflowExecutionResults';
-export { WorkflowNode } from './WorkflowNode';
+export { WorkflowLoadTestDialog } from './WorkflowLoadTestDialog';
 export { WorkflowsList } from './WorkflowsList';
-export { WorkflowStepCard } from './WorkflowStepCard';
@@ -0,0 +1,24 @@
+import { NodeProps } from 'reactflow';
+import { BaseNode } from './BaseNode';
+
+export function AssertionNode({ data, selected }: NodeProps) {
+  const assertions = data.node.config?.assertions ?? [];
+  const preview =
+    assertions.length === 0
+      ? 'No assertions'
+      : `${assertions.length} assertion${assertions.length === 1 ? '' : 's'}`;
+
+  return (
+    <BaseNode
+      data={data}
+      selected={selected}
+      icon="✅"
+      color="#22c55e"
+      title={data.node.name}
+      subtitle="Check results of a previous node"
+      executionStatus={data.executionStatus}
+    >
+      <div className="text-xs text-text-subtle font-mono truncate max-w-full">{preview}</div>
+    </BaseNode>
+  );
+}
@@ -0,0 +1,22 @@
+import { NodeProps } from 'reactflow';
+import { BaseNode } from './BaseNode';
+
+export function SetVariableNode({ data, selected }: NodeProps) {
+  const variables: { name: string }[] = data.node.config?.variables ?? [];
+  const preview =
+    variables.length === 0 ? 'No variables' : variables.map((v) => v.name).join(', ');
+
+  return (
+    <BaseNode
+      data={data}
+      selected={selected}
+      icon="📌"
+      color="#eab308"
+      title={data.node.name}
+      subtitle="Capture values into variables"
+      executionStatus={data.executionStatus}
+    >
+      <div className="text-xs text-text-subtle font-mono truncate max-w-full">{preview}</div>
+    </BaseNode>
+  );
+}
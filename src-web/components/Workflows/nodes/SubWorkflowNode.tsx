@@ -0,0 +1,25 @@
+import { NodeProps } from 'reactflow';
+import { useWorkflow } from '../../../hooks/useWorkflows';
+import { BaseNode } from './BaseNode';
+
+export function SubWorkflowNode({ data, selected }: NodeProps) {
+  const workflow = useWorkflow(data.node.config?.workflow_id || null);
+  const inputCount = Object.keys(data.node.config?.inputs ?? {}).length;
+  const preview = workflow
+    ? `${workflow.name} · ${inputCount} input${inputCount === 1 ? '' : 's'}`
+    : 'Not configured';
+
+  return (
+    <BaseNode
+      data={data}
+      selected={selected}
+      icon="🧩"
+      color="#a855f7"
+      title={data.node.name}
+      subtitle="Run another workflow"
+      executionStatus={data.executionStatus}
+    >
+      <div className="text-xs text-text-subtle font-mono truncate max-w-full">{preview}</div>
+    </BaseNode>
+  );
+}
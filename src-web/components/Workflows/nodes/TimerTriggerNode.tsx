@@ -1,10 +1,21 @@
 import { NodeProps } from 'reactflow';
+import { useKeyValue } from '../../../hooks/useKeyValue';
 import { BaseNode } from './BaseNode';
 
+interface ScheduleState {
+  nextFireAt: string | null;
+  lastFireAt: string | null;
+}
+
 export function TimerTriggerNode({ data, selected }: NodeProps) {
   const scheduleType = data.node.config?.schedule_type;
   const cronExpression = data.node.config?.cron_expression;
   const intervalMinutes = data.node.config?.interval_minutes;
+  const { value: scheduleState } = useKeyValue<ScheduleState | null>({
+    namespace: 'workflow_scheduler',
+    key: data.node.id,
+    fallback: null,
+  });
 
   let schedulePreview = 'Not configured';
   if (scheduleType === 'cron' && cronExpression) {
@@ -28,6 +39,11 @@ export function TimerTriggerNode({ data, selected }: NodeProps) {
       <div className="text-xs text-text-subtle font-mono">
         {schedulePreview}
       </div>
+      {data.node.enabled && scheduleState?.nextFireAt && (
+        <div className="text-xs text-text-subtlest">
+          Next: {new Date(`${scheduleState.nextFireAt}Z`).toLocaleString()}
+        </div>
+      )}
     </BaseNode>
   );
 }
@@ -1,9 +1,11 @@
 import { NodeProps } from 'reactflow';
+import { useWebhookBaseUrl } from '../../../hooks/useWebhookBaseUrl';
 import { BaseNode } from './BaseNode';
 
 export function WebhookTriggerNode({ data, selected }: NodeProps) {
-  const url = data.node.config?.url;
-  const urlPreview = url ? new URL(url).pathname : 'Not configured';
+  const { baseUrl } = useWebhookBaseUrl();
+  const method = data.node.config?.method ?? 'POST';
+  const urlPreview = baseUrl ? `${method} /webhooks/${data.node.id}` : 'Listener not running';
 
   return (
     <BaseNode
@@ -17,11 +19,7 @@ export function WebhookTriggerNode({ data, selected }: NodeProps) {
       outputHandles={[{ id: 'output' }]}
       executionStatus={data.executionStatus}
     >
-      {url && (
-        <div className="text-xs text-text-subtle font-mono truncate max-w-full">
-          {urlPreview}
-        </div>
-      )}
+      <div className="text-xs text-text-subtle font-mono truncate max-w-full">{urlPreview}</div>
     </BaseNode>
   );
 }
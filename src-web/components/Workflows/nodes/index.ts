@@ -7,9 +7,12 @@ import { GrpcRequestNode } from './GrpcRequestNode';
 import { EmailNode } from './EmailNode';
 import { DatabaseNode } from './DatabaseNode';
 import { WebSocketNode } from './WebSocketNode';
+import { SubWorkflowNode } from './SubWorkflowNode';
 import { ConditionalNode } from './ConditionalNode';
 import { LoopNode } from './LoopNode';
 import { ParallelNode } from './ParallelNode';
+import { AssertionNode } from './AssertionNode';
+import { SetVariableNode } from './SetVariableNode';
 
 export const nodeTypes: NodeTypes = {
   manual_trigger: ManualTriggerNode,
@@ -20,9 +23,12 @@ export const nodeTypes: NodeTypes = {
   email: EmailNode,
   database: DatabaseNode,
   websocket: WebSocketNode,
+  sub_workflow: SubWorkflowNode,
   conditional: ConditionalNode,
   loop: LoopNode,
   parallel: ParallelNode,
+  assertion: AssertionNode,
+  set_variable: SetVariableNode,
 };
 
 export {
@@ -34,7 +40,10 @@ export {
   EmailNode,
   DatabaseNode,
   WebSocketNode,
+  SubWorkflowNode,
   ConditionalNode,
   LoopNode,
   ParallelNode,
+  AssertionNode,
+  SetVariableNode,
 };
@@ -0,0 +1,15 @@
+import { useQuery } from '@tanstack/react-query';
+import { invokeCmd } from '../lib/tauri';
+
+/** Base URL of the local webhook listener, or null if it isn't running, and why it failed to */
+export function useWebhookBaseUrl() {
+  const { data, error } = useQuery({
+    queryKey: ['webhook_base_url'],
+    queryFn: () => invokeCmd<string | null>('cmd_webhook_base_url'),
+  });
+  return { baseUrl: data ?? null, error: error == null ? null : String(error) };
+}
+
+export function webhookUrl(baseUrl: string | null, nodeId: string) {
+  return baseUrl == null ? null : `${baseUrl}/webhooks/${nodeId}`;
+}
@@ -1,12 +1,6 @@
 import { useQuery } from '@tanstack/react-query';
-import type {
-  WorkflowExecution,
-  WorkflowStepExecution,
-} from '@yaakapp-internal/models';
-import {
-  workflowExecutionsAtom,
-  workflowStepExecutionsAtom,
-} from '@yaakapp-internal/models';
+import type { WorkflowExecution, WorkflowNodeExecution } from '@yaakapp-internal/models';
+import { canvasNodeExecutionsAtom, workflowExecutionsAtom } from '@yaakapp-internal/models';
 import { useAtomValue } from 'jotai';
 import { useCallback, useEffect, useMemo, useState } from 'react';
 import { invokeCmd } from '../lib/tauri';
@@ -28,7 +22,7 @@ interface CancelWorkflowExecutionRequest {
 
 interface GetWorkflowExecutionResultsResponse {
   execution: WorkflowExecution;
-  stepExecutions: WorkflowStepExecution[];
+  nodeExecutions: WorkflowNodeExecution[];
 }
 
 interface ListWorkflowExecutionsResponse {
@@ -41,9 +35,9 @@ interface WorkflowExecutionUpdate {
   elapsed: number | null;
 }
 
-interface WorkflowStepCompleted {
+interface WorkflowNodeCompleted {
   executionId: string;
-  stepId: string;
+  nodeId: string;
   state: string;
 }
 
@@ -51,7 +45,7 @@ export function useExecuteWorkflow() {
   return useFastMutation<ExecuteWorkflowResponse, unknown, ExecuteWorkflowRequest>({
     mutationKey: ['execute_workflow'],
     mutationFn: async (req: ExecuteWorkflowRequest) => {
-      return invokeCmd<ExecuteWorkflowResponse>('cmd_execute_workflow', { req });
+      return invokeCmd<ExecuteWorkflowResponse>('cmd_execute_workflow_canvas', { req });
     },
   });
 }
@@ -60,7 +54,9 @@ export function useCancelWorkflowExecution() {
   return useFastMutation<void, unknown, CancelWorkflowExecutionRequest>({
     mutationKey: ['cancel_workflow_execution'],
     mutationFn: async (req: CancelWorkflowExecutionRequest) => {
-      return invokeCmd<void>('cmd_cancel_workflow_execution', { req });
+      return invokeCmd<void>('cmd_cancel_workflow_execution_canvas', {
+        executionId: req.executionId,
+      });
     },
   });
 }
@@ -81,14 +77,14 @@ export function useWorkflowExecution(executionId: string | null): WorkflowExecut
   }, [executions, executionId]);
 }
 
-export function useWorkflowStepExecutions(
+export function useWorkflowNodeExecutions(
   executionId: string | null,
-): WorkflowStepExecution[] {
-  const stepExecutions = useAtomValue(workflowStepExecutionsAtom);
+): WorkflowNodeExecution[] {
+  const nodeExecutions = useAtomValue(canvasNodeExecutionsAtom);
   return useMemo(() => {
     if (!executionId) return [];
-    return stepExecutions.filter((se) => se.workflowExecutionId === executionId);
-  }, [stepExecutions, executionId]);
+    return nodeExecutions.filter((ne) => ne.workflowExecutionId === executionId);
+  }, [nodeExecutions, executionId]);
 }
 
 export function useWorkflowExecutionResults(executionId: string | null) {
@@ -136,11 +132,11 @@ export function useWorkflowExecutionUpdates(
   });
 }
 
-export function useWorkflowStepCompletedEvents(
-  onStepCompleted?: (completed: WorkflowStepCompleted) => void,
+export function useWorkflowNodeCompletedEvents(
+  onNodeCompleted?: (completed: WorkflowNodeCompleted) => void,
 ) {
-  useListenToTauriEvent<WorkflowStepCompleted>('workflow_step_completed', (event) => {
-    onStepCompleted?.(event.payload);
+  useListenToTauriEvent<WorkflowNodeCompleted>('workflow_node_completed', (event) => {
+    onNodeCompleted?.(event.payload);
   });
 }
 
@@ -149,11 +145,11 @@ export function useWorkflowExecutionStatus(executionId: string | null) {
   const [status, setStatus] = useState<{
     state: string;
     elapsed: number | null;
-    completedSteps: Set<string>;
+    completedNodes: Set<string>;
   }>({
     state: 'initialized',
     elapsed: null,
-    completedSteps: new Set(),
+    completedNodes: new Set(),
   });
 
   const execution = useWorkflowExecution(executionId);
@@ -164,7 +160,7 @@ export function useWorkflowExecutionStatus(executionId: string | null) {
       setStatus({
         state: execution.state,
         elapsed: execution.elapsed,
-        completedSteps: new Set(),
+        completedNodes: new Set(),
       });
     }
   }, [execution]);
@@ -183,13 +179,13 @@ export function useWorkflowExecutionStatus(executionId: string | null) {
     [executionId],
   );
 
-  // Listen to step completion
-  const handleStepCompleted = useCallback(
-    (completed: WorkflowStepCompleted) => {
+  // Listen to node completion
+  const handleNodeCompleted = useCallback(
+    (completed: WorkflowNodeCompleted) => {
       if (completed.executionId === executionId) {
         setStatus((prev) => ({
           ...prev,
-          completedSteps: new Set([...prev.completedSteps, completed.stepId]),
+          completedNodes: new Set([...prev.completedNodes, completed.nodeId]),
         }));
       }
     },
@@ -197,7 +193,7 @@ export function useWorkflowExecutionStatus(executionId: string | null) {
   );
 
   useWorkflowExecutionUpdates(handleUpdate);
-  useWorkflowStepCompletedEvents(handleStepCompleted);
+  useWorkflowNodeCompletedEvents(handleNodeCompleted);
 
   return status;
 }
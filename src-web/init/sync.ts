@@ -71,7 +71,10 @@ function isModelRelevant(m: AnyModel) {
     m.model !== 'environment' &&
     m.model !== 'http_request' &&
     m.model !== 'grpc_request' &&
-    m.model !== 'websocket_request'
+    m.model !== 'websocket_request' &&
+    m.model !== 'workflow' &&
+    m.model !== 'workflow_node' &&
+    m.model !== 'workflow_edge'
   ) {
     return false;
   } else if (m.model === 'workspace') {
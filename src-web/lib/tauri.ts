@@ -43,9 +43,6 @@ type TauriCmd =
   | 'cmd_template_function_summaries'
   | 'cmd_template_function_config'
   | 'cmd_template_tokens_to_string'
-  | 'cmd_execute_workflow'
-  | 'cmd_cancel_workflow_execution'
-  | 'cmd_get_workflow_with_steps'
   | 'cmd_get_workflow_execution_results'
   | 'cmd_list_workflow_executions'
   | 'cmd_create_workflow_node'
@@ -58,9 +55,12 @@ type TauriCmd =
   | 'cmd_validate_workflow_graph'
   | 'cmd_execute_workflow_canvas'
   | 'cmd_cancel_workflow_execution_canvas'
+  | 'cmd_resume_workflow_execution'
+  | 'cmd_run_workflow_load_test'
+  | 'cmd_webhook_base_url'
   | 'cmd_export_workflow_json'
-  | 'cmd_import_workflow_json'
-  | 'cmd_migrate_workflow_to_canvas';
+  | 'cmd_export_workflow_execution_report'
+  | 'cmd_import_workflow_json';
 
 export async function invokeCmd<T>(cmd: TauriCmd, args?: InvokeArgs): Promise<T> {
   // console.log('RUN COMMAND', cmd, args);